@@ -91,17 +91,18 @@ fn main() {
 
     // compile bindings
     let mut build = cc::Build::new();
-    //build.file("src/glue/color.cpp");
-    //build.file("src/glue/imagebuf.cpp");
-    //build.file("src/glue/imagebufalgo.cpp");
+    build.file("src/glue/color.cpp");
+    build.file("src/glue/deepdata.cpp");
+    build.file("src/glue/imagebuf.cpp");
+    build.file("src/glue/imagebufalgo.cpp");
     //build.file("src/glue/imagecache.cpp");
     build.file("src/glue/helpers.cpp");
     build.file("src/glue/imageinput.cpp");
+    build.file("src/glue/ioproxy.cpp");
     build.file("src/glue/imageoutput.cpp");
     build.file("src/glue/imagespec.cpp");
     build.file("src/glue/imagecache.cpp");
     build.file("src/glue/oiio.cpp");
-    //build.file("src/glue/roi.cpp");
     build.include("src/glue");
     for p in include_paths {
         build.include(p);
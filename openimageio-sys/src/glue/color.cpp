@@ -1,83 +1,147 @@
 #include <OpenImageIO/color.h>
 
-#include "color.hpp"
+#include "helpers.hpp"
+#include "oiio.h"
+#include <string>
+
+namespace {
+
+OIIO::ColorConfig *unwrapColorConfig(OIIO_ColorConfig *c) {
+  return reinterpret_cast<OIIO::ColorConfig *>(c);
+}
+
+const OIIO::ColorConfig *unwrapColorConfig(const OIIO_ColorConfig *c) {
+  return reinterpret_cast<const OIIO::ColorConfig *>(c);
+}
+
+const OIIO::ColorProcessorHandle &unwrapColorProcessor(const OIIO_ColorProcessor *p) {
+  return *reinterpret_cast<const OIIO::ColorProcessorHandle *>(p);
+}
+
+OIIO_ColorProcessor *wrapColorProcessor(OIIO::ColorProcessorHandle handle) {
+  if (!handle) {
+    return nullptr;
+  }
+  return reinterpret_cast<OIIO_ColorProcessor *>(new OIIO::ColorProcessorHandle{std::move(handle)});
+}
+
+// Copies a name returned by ColorConfig, which may be null.
+const char *makeName(const char *name) {
+  if (!name) {
+    return nullptr;
+  }
+  return helpers::makeCString(name);
+}
+
+} // namespace
 
 extern "C" {
 
-ColorConfig* New_ColorConfig() {
-	return static_cast<ColorConfig*>(new OIIO::ColorConfig());
+OIIO_ColorConfig *OIIO_ColorConfig_new() {
+  return reinterpret_cast<OIIO_ColorConfig *>(new OIIO::ColorConfig{});
 }
 
-ColorConfig* New_ColorConfigPath(const char * filename) {
-	return static_cast<ColorConfig*>(new OIIO::ColorConfig(filename));
+OIIO_ColorConfig *OIIO_ColorConfig_new_file(OIIO_StringRef filename) {
+  OIIO::string_view sv_filename{filename.ptr, filename.len};
+  return reinterpret_cast<OIIO_ColorConfig *>(new OIIO::ColorConfig{sv_filename});
 }
 
-void deleteColorConfig(ColorConfig* c) {
-	delete static_cast<OIIO::ColorConfig*>(c);
+void OIIO_ColorConfig_delete(OIIO_ColorConfig *c) { delete unwrapColorConfig(c); }
+
+bool OIIO_ColorConfig_supportsOpenColorIO() { return OIIO::ColorConfig::supportsOpenColorIO(); }
+
+bool OIIO_ColorConfig_has_error(const OIIO_ColorConfig *c) {
+  return unwrapColorConfig(c)->error();
 }
 
-bool supportsOpenColorIO() {
-	return OIIO::ColorConfig::supportsOpenColorIO();
+const char *OIIO_ColorConfig_geterror(OIIO_ColorConfig *c) {
+  std::string sstring = unwrapColorConfig(c)->geterror();
+  return helpers::makeCString(sstring);
 }
 
-bool ColorConfig_error(ColorConfig* c) {
-	return static_cast<OIIO::ColorConfig*>(c)->error();
+int OIIO_ColorConfig_getNumColorSpaces(const OIIO_ColorConfig *c) {
+  return unwrapColorConfig(c)->getNumColorSpaces();
 }
 
-const char* ColorConfig_geterror(ColorConfig* c) {
-	if (!ColorConfig_error(c)) {
-		return NULL;
-	}
-	return static_cast<OIIO::ColorConfig*>(c)->geterror().c_str();
+const char *OIIO_ColorConfig_getColorSpaceNameByIndex(const OIIO_ColorConfig *c, int index) {
+  return makeName(unwrapColorConfig(c)->getColorSpaceNameByIndex(index));
 }
 
-int ColorConfig_getNumColorSpaces(ColorConfig* c) {
-	return static_cast<OIIO::ColorConfig*>(c)->getNumColorSpaces();
+const char *OIIO_ColorConfig_getColorSpaceNameByRole(const OIIO_ColorConfig *c,
+                                                     OIIO_StringRef role) {
+  OIIO::string_view sv_role{role.ptr, role.len};
+  return makeName(unwrapColorConfig(c)->getColorSpaceNameByRole(sv_role));
 }
 
-const char * ColorConfig_getColorSpaceNameByIndex(ColorConfig* c, int index) {
-	return static_cast<OIIO::ColorConfig*>(c)->getColorSpaceNameByIndex(index);
+int OIIO_ColorConfig_getNumRoles(const OIIO_ColorConfig *c) {
+  return unwrapColorConfig(c)->getNumRoles();
 }
 
-int ColorConfig_getNumLooks(ColorConfig* c) {
-	return static_cast<OIIO::ColorConfig*>(c)->getNumLooks();
+const char *OIIO_ColorConfig_getRoleByIndex(const OIIO_ColorConfig *c, int index) {
+  return makeName(unwrapColorConfig(c)->getRoleByIndex(index));
 }
 
-const char * ColorConfig_getLookNameByIndex(ColorConfig* c, int index) {
-	return static_cast<OIIO::ColorConfig*>(c)->getLookNameByIndex(index);
+int OIIO_ColorConfig_getNumLooks(const OIIO_ColorConfig *c) {
+  return unwrapColorConfig(c)->getNumLooks();
 }
 
-int ColorConfig_getNumDisplays(ColorConfig* c) {
-	return static_cast<OIIO::ColorConfig*>(c)->getNumDisplays();	
+const char *OIIO_ColorConfig_getLookNameByIndex(const OIIO_ColorConfig *c, int index) {
+  return makeName(unwrapColorConfig(c)->getLookNameByIndex(index));
 }
 
-const char * ColorConfig_getDisplayNameByIndex(ColorConfig* c, int index) {
-	return static_cast<OIIO::ColorConfig*>(c)->getDisplayNameByIndex(index);
+int OIIO_ColorConfig_getNumDisplays(const OIIO_ColorConfig *c) {
+  return unwrapColorConfig(c)->getNumDisplays();
 }
 
-int ColorConfig_getNumViews(ColorConfig* c, const char * display) {
-	return static_cast<OIIO::ColorConfig*>(c)->getNumViews(display);	
+const char *OIIO_ColorConfig_getDisplayNameByIndex(const OIIO_ColorConfig *c, int index) {
+  return makeName(unwrapColorConfig(c)->getDisplayNameByIndex(index));
 }
 
-const char * ColorConfig_getViewNameByIndex(ColorConfig* c, const char * display, int index) {
-	return static_cast<OIIO::ColorConfig*>(c)->getViewNameByIndex(display, index);
+const char *OIIO_ColorConfig_getDefaultDisplayName(const OIIO_ColorConfig *c) {
+  return makeName(unwrapColorConfig(c)->getDefaultDisplayName());
 }
 
-const char * ColorConfig_getColorSpaceNameByRole(ColorConfig* c, const char *role) {
-	return static_cast<OIIO::ColorConfig*>(c)->getColorSpaceNameByRole(role);	
+int OIIO_ColorConfig_getNumViews(const OIIO_ColorConfig *c, OIIO_StringRef display) {
+  OIIO::string_view sv_display{display.ptr, display.len};
+  return unwrapColorConfig(c)->getNumViews(sv_display);
 }
 
-void deleteColorProcessor(ColorProcessor* processor) {
-	OIIO::ColorConfig::deleteColorProcessor(static_cast<OIIO::ColorProcessor*>(processor));
+const char *OIIO_ColorConfig_getViewNameByIndex(const OIIO_ColorConfig *c, OIIO_StringRef display,
+                                                int index) {
+  OIIO::string_view sv_display{display.ptr, display.len};
+  return makeName(unwrapColorConfig(c)->getViewNameByIndex(sv_display, index));
 }
 
-ColorProcessor* ColorConfig_createColorProcessor(ColorConfig* c, const char * inputColorSpace,
-                                     				             const char * outputColorSpace) {
-	OIIO::ColorProcessor *cp;
-	cp = static_cast<OIIO::ColorConfig*>(c)->createColorProcessor(inputColorSpace, outputColorSpace);	
-	return static_cast<ColorProcessor*>(cp);
+const char *OIIO_ColorConfig_getDefaultViewName(const OIIO_ColorConfig *c,
+                                                OIIO_StringRef display) {
+  OIIO::string_view sv_display{display.ptr, display.len};
+  return makeName(unwrapColorConfig(c)->getDefaultViewName(sv_display));
 }
 
-} // extern "C"
+OIIO_ColorProcessor *OIIO_ColorConfig_createColorProcessor(const OIIO_ColorConfig *c,
+                                                           OIIO_StringRef inputColorSpace,
+                                                           OIIO_StringRef outputColorSpace) {
+  OIIO::string_view sv_input{inputColorSpace.ptr, inputColorSpace.len};
+  OIIO::string_view sv_output{outputColorSpace.ptr, outputColorSpace.len};
+  return wrapColorProcessor(unwrapColorConfig(c)->createColorProcessor(sv_input, sv_output));
+}
+
+void OIIO_ColorProcessor_delete(OIIO_ColorProcessor *p) {
+  delete reinterpret_cast<OIIO::ColorProcessorHandle *>(p);
+}
 
+bool OIIO_ColorProcessor_isNoOp(const OIIO_ColorProcessor *p) {
+  return unwrapColorProcessor(p)->isNoOp();
+}
+
+bool OIIO_ColorProcessor_hasChannelCrosstalk(const OIIO_ColorProcessor *p) {
+  return unwrapColorProcessor(p)->hasChannelCrosstalk();
+}
 
+void OIIO_ColorProcessor_apply(const OIIO_ColorProcessor *p, float *data, int width, int height,
+                               int nchannels, stride_t chanstride, stride_t xstride,
+                               stride_t ystride) {
+  unwrapColorProcessor(p)->apply(data, width, height, nchannels, chanstride, xstride, ystride);
+}
+
+} // extern "C"
@@ -0,0 +1,106 @@
+#include <OpenImageIO/deepdata.h>
+#include <OpenImageIO/imageio.h>
+
+#include <string>
+#include <vector>
+
+#include "helpers.hpp"
+#include "oiio.h"
+
+extern "C" {
+
+OIIO_DeepData *OIIO_DeepData_new() { return (OIIO_DeepData *)new OIIO::DeepData; }
+
+OIIO_DeepData *OIIO_DeepData_clone(const OIIO_DeepData *dd) {
+  return (OIIO_DeepData *)new OIIO::DeepData{*OIIO_RS_CAST_CONST(DeepData, dd)};
+}
+
+void OIIO_DeepData_delete(OIIO_DeepData *dd) { delete OIIO_RS_CAST(DeepData, dd); }
+
+void OIIO_DeepData_init(OIIO_DeepData *dd, int64_t npixels, int nchannels,
+                        const OIIO_TypeDesc *channeltypes, const OIIO_StringRef *channelnames) {
+  std::vector<OIIO::TypeDesc> types;
+  std::vector<std::string> names;
+  for (int c = 0; c < nchannels; ++c) {
+    types.push_back(helpers::unwrapTypeDesc(channeltypes[c]));
+    names.emplace_back(channelnames[c].ptr, channelnames[c].len);
+  }
+  OIIO_RS_CAST(DeepData, dd)->init(npixels, nchannels, types, names);
+}
+
+void OIIO_DeepData_init_spec(OIIO_DeepData *dd, const OIIO_ImageSpec *spec) {
+  OIIO_RS_CAST(DeepData, dd)->init(*OIIO_RS_CAST_CONST(ImageSpec, spec));
+}
+
+int64_t OIIO_DeepData_pixels(const OIIO_DeepData *dd) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->pixels();
+}
+
+int OIIO_DeepData_channels(const OIIO_DeepData *dd) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->channels();
+}
+
+int OIIO_DeepData_Z_channel(const OIIO_DeepData *dd) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->Z_channel();
+}
+
+int OIIO_DeepData_Zback_channel(const OIIO_DeepData *dd) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->Zback_channel();
+}
+
+int OIIO_DeepData_A_channel(const OIIO_DeepData *dd) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->A_channel();
+}
+
+const char *OIIO_DeepData_channelname(const OIIO_DeepData *dd, int c) {
+  return helpers::makeCString(std::string{OIIO_RS_CAST_CONST(DeepData, dd)->channelname(c)});
+}
+
+OIIO_TypeDesc OIIO_DeepData_channeltype(const OIIO_DeepData *dd, int c) {
+  return helpers::wrapTypeDesc(OIIO_RS_CAST_CONST(DeepData, dd)->channeltype(c));
+}
+
+int OIIO_DeepData_samples(const OIIO_DeepData *dd, int64_t pixel) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->samples(pixel);
+}
+
+void OIIO_DeepData_set_samples(OIIO_DeepData *dd, int64_t pixel, int samps) {
+  OIIO_RS_CAST(DeepData, dd)->set_samples(pixel, samps);
+}
+
+void OIIO_DeepData_insert_samples(OIIO_DeepData *dd, int64_t pixel, int samplepos, int n) {
+  OIIO_RS_CAST(DeepData, dd)->insert_samples(pixel, samplepos, n);
+}
+
+void OIIO_DeepData_erase_samples(OIIO_DeepData *dd, int64_t pixel, int samplepos, int n) {
+  OIIO_RS_CAST(DeepData, dd)->erase_samples(pixel, samplepos, n);
+}
+
+float OIIO_DeepData_deep_value(const OIIO_DeepData *dd, int64_t pixel, int channel, int sample) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->deep_value(pixel, channel, sample);
+}
+
+uint32_t OIIO_DeepData_deep_value_uint(const OIIO_DeepData *dd, int64_t pixel, int channel,
+                                       int sample) {
+  return OIIO_RS_CAST_CONST(DeepData, dd)->deep_value_uint(pixel, channel, sample);
+}
+
+void OIIO_DeepData_set_deep_value(OIIO_DeepData *dd, int64_t pixel, int channel, int sample,
+                                  float value) {
+  OIIO_RS_CAST(DeepData, dd)->set_deep_value(pixel, channel, sample, value);
+}
+
+void OIIO_DeepData_set_deep_value_uint(OIIO_DeepData *dd, int64_t pixel, int channel, int sample,
+                                       uint32_t value) {
+  OIIO_RS_CAST(DeepData, dd)->set_deep_value(pixel, channel, sample, value);
+}
+
+void OIIO_DeepData_sort(OIIO_DeepData *dd, int64_t pixel) {
+  OIIO_RS_CAST(DeepData, dd)->sort(pixel);
+}
+
+void OIIO_DeepData_merge_overlaps(OIIO_DeepData *dd, int64_t pixel) {
+  OIIO_RS_CAST(DeepData, dd)->merge_overlaps(pixel);
+}
+
+} // extern "C"
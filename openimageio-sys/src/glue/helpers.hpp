@@ -1,5 +1,6 @@
 // Private header - helper functions for binding OIIO methods
 #include "oiio.h"
+#include <OpenImageIO/imageio.h>
 #include <OpenImageIO/typedesc.h>
 #include <cstdlib>
 #include <string>
@@ -17,6 +18,16 @@ static inline OIIO::TypeDesc unwrapTypeDesc(OIIO_TypeDesc typeDesc) {
                         typeDesc.arraylen};
 }
 
+static inline OIIO_ROI wrapROI(const OIIO::ROI &roi) {
+  return OIIO_ROI{roi.xbegin, roi.xend, roi.ybegin, roi.yend,
+                  roi.zbegin, roi.zend, roi.chbegin, roi.chend};
+}
+
+static inline OIIO::ROI unwrapROI(OIIO_ROI roi) {
+  return OIIO::ROI{roi.xbegin, roi.xend, roi.ybegin, roi.yend,
+                   roi.zbegin, roi.zend, roi.chbegin, roi.chend};
+}
+
 char *makeCString(const std::string &str);
 void freeCString(const char *ptr);
 
@@ -1,363 +1,245 @@
 #include <OpenImageIO/imagebuf.h>
 
+#include "helpers.hpp"
 #include "oiio.h"
-#include "imagespec.hpp"
-
-OIIO::ImageBuf::IBStorage fromIBStorage(IBStorage s) {
-	switch (s) {
-	case IBSTORAGE_LOCALBUFFER: return OIIO::ImageBuf::LOCALBUFFER;
-	case IBSTORAGE_APPBUFFER: 	return OIIO::ImageBuf::APPBUFFER;
-	case IBSTORAGE_IMAGECACHE: 	return OIIO::ImageBuf::IMAGECACHE;	
-	case IBSTORAGE_UNINITIALIZED: 	return OIIO::ImageBuf::UNINITIALIZED;	
-	}
-}
-
-IBStorage toIBStorage(OIIO::ImageBuf::IBStorage s) {
-	if (s == OIIO::ImageBuf::LOCALBUFFER) return IBSTORAGE_LOCALBUFFER;
-	if (s == OIIO::ImageBuf::APPBUFFER)   return IBSTORAGE_APPBUFFER;
-	if (s == OIIO::ImageBuf::IMAGECACHE)  return IBSTORAGE_IMAGECACHE;
-	return IBSTORAGE_UNINITIALIZED;
-}
-
+#include <string>
 
 extern "C" {
 
-char* ImageBuf_geterror(ImageBuf* buf) {
-	if (!static_cast<OIIO::ImageBuf*>(buf)->has_error()) {
-		return NULL;
-	}
-	std::string err = static_cast<OIIO::ImageBuf*>(buf)->geterror();
-	return strdup(err.c_str());
-}
+OIIO_ImageBuf *OIIO_ImageBuf_new() { return (OIIO_ImageBuf *)new OIIO::ImageBuf(); }
 
-void deleteImageBuf(ImageBuf *buf) {
-	delete static_cast<OIIO::ImageBuf*>(buf);
+OIIO_ImageBuf *OIIO_ImageBuf_new_file(OIIO_StringRef name, int subimage, int miplevel,
+                                      OIIO_ImageCache *imagecache, const OIIO_ImageSpec *config) {
+  std::string s_name{name.ptr, name.len};
+  return (OIIO_ImageBuf *)new OIIO::ImageBuf(s_name, subimage, miplevel,
+                                             OIIO_RS_CAST(ImageCache, imagecache),
+                                             OIIO_RS_CAST_CONST(ImageSpec, config));
 }
 
-ImageBuf* ImageBuf_New() {
-	return (ImageBuf*) new OIIO::ImageBuf();
+OIIO_ImageBuf *OIIO_ImageBuf_new_spec(const OIIO_ImageSpec *spec, bool zero) {
+  return (OIIO_ImageBuf *)new OIIO::ImageBuf(*OIIO_RS_CAST_CONST(ImageSpec, spec),
+                                             zero ? OIIO::InitializePixels::Yes
+                                                  : OIIO::InitializePixels::No);
 }
 
-ImageBuf* ImageBuf_New_WithCache(const char* name, ImageCache *imagecache) {
-	std::string s_name(name);
-	return (ImageBuf*) new OIIO::ImageBuf(s_name, static_cast<OIIO::ImageCache*>(imagecache));
-}
-
-ImageBuf* ImageBuf_New_Spec(const ImageSpec* spec) {
-	return (ImageBuf*) new OIIO::ImageBuf(*(static_cast<const OIIO::ImageSpec*>(spec)));
-}
-
-ImageBuf* ImageBuf_New_WithBuffer(const char* name, const ImageSpec* spec, void *buffer) {
-	std::string s_name(name);
-	return (ImageBuf*) new OIIO::ImageBuf(s_name, *(static_cast<const OIIO::ImageSpec*>(spec)), buffer);
-}
-
-ImageBuf* ImageBuf_New_SubImage(const char* name, int subimage, int miplevel, ImageCache* imagecache) {
-	std::string s_name(name);
-	return (ImageBuf*) new OIIO::ImageBuf(s_name, subimage, miplevel, 
-											static_cast<OIIO::ImageCache*>(imagecache));
-}
+void OIIO_ImageBuf_delete(OIIO_ImageBuf *buf) { delete OIIO_RS_CAST(ImageBuf, buf); }
 
+void OIIO_ImageBuf_clear(OIIO_ImageBuf *buf) { OIIO_RS_CAST(ImageBuf, buf)->clear(); }
 
-void ImageBuf_clear(ImageBuf* buf) {
-	static_cast<OIIO::ImageBuf*>(buf)->clear();
+void OIIO_ImageBuf_reset_file(OIIO_ImageBuf *buf, OIIO_StringRef name, int subimage, int miplevel,
+                              OIIO_ImageCache *imagecache, const OIIO_ImageSpec *config) {
+  std::string s_name{name.ptr, name.len};
+  OIIO_RS_CAST(ImageBuf, buf)
+      ->reset(s_name, subimage, miplevel, OIIO_RS_CAST(ImageCache, imagecache),
+              OIIO_RS_CAST_CONST(ImageSpec, config));
 }
 
-void ImageBuf_reset_subimage(ImageBuf* buf, const char* name, int subimage, int miplevel, 
-							 ImageCache *imagecache) {
-	std::string s_name(name);
-	static_cast<OIIO::ImageBuf*>(buf)->reset(s_name, subimage, miplevel, 
-												static_cast<OIIO::ImageCache*>(imagecache));
+void OIIO_ImageBuf_reset_spec(OIIO_ImageBuf *buf, const OIIO_ImageSpec *spec, bool zero) {
+  OIIO_RS_CAST(ImageBuf, buf)
+      ->reset(*OIIO_RS_CAST_CONST(ImageSpec, spec),
+              zero ? OIIO::InitializePixels::Yes : OIIO::InitializePixels::No);
 }
 
-void ImageBuf_reset_name_cache(ImageBuf* buf, const char* name, ImageCache *imagecache) {
-	std::string s_name(name);
-	static_cast<OIIO::ImageBuf*>(buf)->reset(s_name, static_cast<OIIO::ImageCache*>(imagecache));
+OIIO_ImageBuf_IBStorage OIIO_ImageBuf_storage(const OIIO_ImageBuf *buf) {
+  return static_cast<OIIO_ImageBuf_IBStorage>(OIIO_RS_CAST_CONST(ImageBuf, buf)->storage());
 }
 
-void ImageBuf_reset_name_spec(ImageBuf* buf, const char* name, const ImageSpec* spec) {
-	std::string s_name(name);
-	static_cast<OIIO::ImageBuf*>(buf)->reset(s_name, *(static_cast<const OIIO::ImageSpec*>(spec)));
+bool OIIO_ImageBuf_initialized(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->initialized();
 }
 
-void ImageBuf_reset_spec(ImageBuf* buf, ImageSpec* spec) {
-	static_cast<OIIO::ImageBuf*>(buf)->reset(*(static_cast<const OIIO::ImageSpec*>(spec)));
-}
-
-
-IBStorage ImageBuf_storage(ImageBuf* buf) {
-	return toIBStorage(static_cast<OIIO::ImageBuf*>(buf)->storage());
-}
-
-bool ImageBuf_initialized(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->initialized();
-}
-
-bool ImageBuf_init_spec(ImageBuf* buf, const char* filename, int subimage, int miplevel) {
-	return static_cast<OIIO::ImageBuf*>(buf)->init_spec(filename, subimage, miplevel);
-}
-
-bool ImageBuf_read(ImageBuf* buf, int subimage, int miplevel, bool force, TypeDesc convert, void *cbk_data) {
-	ProgressCallback cbk = NULL;
-	if (cbk_data != NULL) {
-		cbk = &image_progress_callback;
-	}
-	return static_cast<OIIO::ImageBuf*>(buf)->read(subimage,
-												   miplevel,
-												   force,
-												   fromTypeDesc(convert), 
-												   cbk,
-												   cbk_data);
-}
-
-
-bool ImageBuf_write_file(ImageBuf* buf, const char* filename, const char* fileformat, void *cbk_data) {
-	ProgressCallback cbk = NULL;
-	if (cbk_data != NULL) {
-		cbk = &image_progress_callback;
-	}
-	return static_cast<OIIO::ImageBuf*>(buf)->write(filename, fileformat, cbk, cbk_data);
+bool OIIO_ImageBuf_init_spec(OIIO_ImageBuf *buf, OIIO_StringRef filename, int subimage,
+                             int miplevel) {
+  std::string s_filename{filename.ptr, filename.len};
+  return OIIO_RS_CAST(ImageBuf, buf)->init_spec(s_filename, subimage, miplevel);
 }
 
-bool ImageBuf_write_output(ImageBuf* buf, ImageOutput *out, void *cbk_data) {
-	OIIO::ImageOutput *out_ptr = static_cast<OIIO::ImageOutput*>(out);
-	ProgressCallback cbk = NULL;
-	if (cbk_data != NULL) {
-		cbk = &image_progress_callback;
-	}
-	return static_cast<OIIO::ImageBuf*>(buf)->write(out_ptr, cbk, cbk_data);
+bool OIIO_ImageBuf_read(OIIO_ImageBuf *buf, int subimage, int miplevel, bool force,
+                        OIIO_TypeDesc convert, ProgressCallback progress_callback,
+                        void *progress_callback_data) {
+  return OIIO_RS_CAST(ImageBuf, buf)
+      ->read(subimage, miplevel, force, helpers::unwrapTypeDesc(convert), progress_callback,
+             progress_callback_data);
 }
 
-void ImageBuf_set_write_format(ImageBuf* buf, TypeDesc format) {
-	static_cast<OIIO::ImageBuf*>(buf)->set_write_format(fromTypeDesc(format));
+bool OIIO_ImageBuf_write_file(const OIIO_ImageBuf *buf, OIIO_StringRef filename,
+                              OIIO_TypeDesc dtype, OIIO_StringRef fileformat,
+                              ProgressCallback progress_callback, void *progress_callback_data) {
+  std::string s_filename{filename.ptr, filename.len};
+  OIIO::string_view sv_fileformat{fileformat.ptr, fileformat.len};
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)
+      ->write(s_filename, helpers::unwrapTypeDesc(dtype), sv_fileformat, progress_callback,
+              progress_callback_data);
 }
 
-void ImageBuf_set_write_tiles(ImageBuf* buf, int width, int height, int depth) {
-	static_cast<OIIO::ImageBuf*>(buf)->set_write_tiles(width, height, depth);
+bool OIIO_ImageBuf_write_output(const OIIO_ImageBuf *buf, OIIO_ImageOutput *out,
+                                ProgressCallback progress_callback, void *progress_callback_data) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)
+      ->write(OIIO_RS_CAST(ImageOutput, out), progress_callback, progress_callback_data);
 }
 
-void ImageBuf_copy_metadata(ImageBuf* dst, const ImageBuf* src) {
-	const OIIO::ImageBuf *src_ptr = static_cast<const OIIO::ImageBuf*>(src);
-	static_cast<OIIO::ImageBuf*>(dst)->copy_metadata(*src_ptr);
+void OIIO_ImageBuf_set_write_format(OIIO_ImageBuf *buf, OIIO_TypeDesc format) {
+  OIIO_RS_CAST(ImageBuf, buf)->set_write_format(helpers::unwrapTypeDesc(format));
 }
 
-bool ImageBuf_copy_pixels(ImageBuf* dst, const ImageBuf* src) {
-	const OIIO::ImageBuf *src_ptr = static_cast<const OIIO::ImageBuf*>(src);
-	return static_cast<OIIO::ImageBuf*>(dst)->copy_pixels(*src_ptr);
+void OIIO_ImageBuf_set_write_tiles(OIIO_ImageBuf *buf, int width, int height, int depth) {
+  OIIO_RS_CAST(ImageBuf, buf)->set_write_tiles(width, height, depth);
 }
 
-bool ImageBuf_copy(ImageBuf* dst, const ImageBuf* src) {
-	const OIIO::ImageBuf *src_ptr = static_cast<const OIIO::ImageBuf*>(src);
-	return static_cast<OIIO::ImageBuf*>(dst)->copy(*src_ptr);
+void OIIO_ImageBuf_copy_metadata(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src) {
+  OIIO_RS_CAST(ImageBuf, dst)->copy_metadata(*OIIO_RS_CAST_CONST(ImageBuf, src));
 }
 
-void ImageBuf_swap(ImageBuf* buf, ImageBuf* other) {
-	OIIO::ImageBuf *other_ptr = static_cast<OIIO::ImageBuf*>(other);
-	static_cast<OIIO::ImageBuf*>(buf)->swap(*other_ptr);
+bool OIIO_ImageBuf_copy_pixels(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src) {
+  return OIIO_RS_CAST(ImageBuf, dst)->copy_pixels(*OIIO_RS_CAST_CONST(ImageBuf, src));
 }
 
-const ImageSpec* ImageBuf_spec(ImageBuf* buf) {
-	OIIO::ImageSpec *spec = new OIIO::ImageSpec(static_cast<OIIO::ImageBuf*>(buf)->spec());
-	return static_cast<const ImageSpec*>(spec);
+bool OIIO_ImageBuf_copy(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_TypeDesc format) {
+  return OIIO_RS_CAST(ImageBuf, dst)
+      ->copy(*OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapTypeDesc(format));
 }
 
-ImageSpec* ImageBuf_specmod(ImageBuf* buf) {
-	OIIO::ImageSpec *spec = &(static_cast<OIIO::ImageBuf*>(buf)->specmod());
-	return static_cast<ImageSpec*>(spec);
+void OIIO_ImageBuf_swap(OIIO_ImageBuf *buf, OIIO_ImageBuf *other) {
+  OIIO_RS_CAST(ImageBuf, buf)->swap(*OIIO_RS_CAST(ImageBuf, other));
 }
 
-const ImageSpec* ImageBuf_nativespec(ImageBuf* buf) {
-	OIIO::ImageSpec *spec = new OIIO::ImageSpec(static_cast<OIIO::ImageBuf*>(buf)->nativespec());
-	return static_cast<const ImageSpec*>(spec);
+bool OIIO_ImageBuf_has_error(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->has_error();
 }
 
-const char* ImageBuf_name(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->name().c_str();
+const char *OIIO_ImageBuf_geterror(const OIIO_ImageBuf *buf) {
+  std::string sstring = OIIO_RS_CAST_CONST(ImageBuf, buf)->geterror();
+  return helpers::makeCString(sstring);
 }
 
-const char* ImageBuf_file_format_name(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->file_format_name().c_str();
+const OIIO_ImageSpec *OIIO_ImageBuf_spec(const OIIO_ImageBuf *buf) {
+  return (const OIIO_ImageSpec *)(&(OIIO_RS_CAST_CONST(ImageBuf, buf)->spec()));
 }
 
-int ImageBuf_subimage(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->subimage();
+OIIO_ImageSpec *OIIO_ImageBuf_specmod(OIIO_ImageBuf *buf) {
+  return (OIIO_ImageSpec *)(&(OIIO_RS_CAST(ImageBuf, buf)->specmod()));
 }
 
-int ImageBuf_nsubimages(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->nsubimages();
+const OIIO_ImageSpec *OIIO_ImageBuf_nativespec(const OIIO_ImageBuf *buf) {
+  return (const OIIO_ImageSpec *)(&(OIIO_RS_CAST_CONST(ImageBuf, buf)->nativespec()));
 }
 
-int ImageBuf_miplevel(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->miplevel();
+const char *OIIO_ImageBuf_name(const OIIO_ImageBuf *buf) {
+  std::string sstring = OIIO_RS_CAST_CONST(ImageBuf, buf)->name();
+  return helpers::makeCString(sstring);
 }
 
-int ImageBuf_nmiplevels(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->nmiplevels();
+const char *OIIO_ImageBuf_file_format_name(const OIIO_ImageBuf *buf) {
+  std::string sstring = OIIO_RS_CAST_CONST(ImageBuf, buf)->file_format_name();
+  return helpers::makeCString(sstring);
 }
 
-int ImageBuf_nchannels(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->nchannels();
+int OIIO_ImageBuf_subimage(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->subimage();
 }
 
-bool ImageBuf_get_pixel_channels(ImageBuf* buf, 
-								 int xbegin, int xend, 
-								 int ybegin, int yend, 
-								 int zbegin, int zend, 
-								 int chbegin, int chend, 
-								 TypeDesc format, 
-								 void *result)
-{
-	return static_cast<OIIO::ImageBuf*>(buf)->get_pixel_channels(xbegin, xend,
-														 		 ybegin, yend, 
-														 		 zbegin, zend, 
-														 		 chbegin, chend, 
-														 		 fromTypeDesc(format), 
-														 		 result );	
+int OIIO_ImageBuf_nsubimages(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->nsubimages();
 }
 
-int ImageBuf_orientation(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->orientation();
+int OIIO_ImageBuf_miplevel(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->miplevel();
 }
 
-int ImageBuf_oriented_width(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_width();
+int OIIO_ImageBuf_nmiplevels(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->nmiplevels();
 }
 
-int ImageBuf_oriented_height(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_height();
+int OIIO_ImageBuf_nchannels(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->nchannels();
 }
 
-int ImageBuf_oriented_x(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_x();
+int OIIO_ImageBuf_orientation(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->orientation();
 }
 
-int ImageBuf_oriented_y(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_y();
+void OIIO_ImageBuf_set_orientation(OIIO_ImageBuf *buf, int orient) {
+  OIIO_RS_CAST(ImageBuf, buf)->set_orientation(orient);
 }
 
-int ImageBuf_oriented_full_width(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_full_width();
+int OIIO_ImageBuf_oriented_width(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_width();
 }
 
-int ImageBuf_oriented_full_height(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_full_height();
+int OIIO_ImageBuf_oriented_height(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_height();
 }
 
-int ImageBuf_oriented_full_x(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_full_x();
+int OIIO_ImageBuf_oriented_x(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_x();
 }
 
-int ImageBuf_oriented_full_y(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->oriented_full_y();
+int OIIO_ImageBuf_oriented_y(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_y();
 }
 
-int ImageBuf_xbegin(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->xbegin();
+int OIIO_ImageBuf_oriented_full_width(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_full_width();
 }
 
-int ImageBuf_xend(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->xend();
+int OIIO_ImageBuf_oriented_full_height(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_full_height();
 }
 
-int ImageBuf_ybegin(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->ybegin();
+int OIIO_ImageBuf_oriented_full_x(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_full_x();
 }
 
-int ImageBuf_yend(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->yend();
+int OIIO_ImageBuf_oriented_full_y(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->oriented_full_y();
 }
 
-int ImageBuf_zbegin(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->zbegin();
+void OIIO_ImageBuf_set_origin(OIIO_ImageBuf *buf, int x, int y, int z) {
+  OIIO_RS_CAST(ImageBuf, buf)->set_origin(x, y, z);
 }
 
-int ImageBuf_zend(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->zend();
+OIIO_ROI OIIO_ImageBuf_roi(const OIIO_ImageBuf *buf) {
+  return helpers::wrapROI(OIIO_RS_CAST_CONST(ImageBuf, buf)->roi());
 }
 
-int ImageBuf_xmin(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->xmin();
+OIIO_ROI OIIO_ImageBuf_roi_full(const OIIO_ImageBuf *buf) {
+  return helpers::wrapROI(OIIO_RS_CAST_CONST(ImageBuf, buf)->roi_full());
 }
 
-int ImageBuf_xmax(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->xmax();
+void OIIO_ImageBuf_set_roi_full(OIIO_ImageBuf *buf, OIIO_ROI newroi) {
+  OIIO_RS_CAST(ImageBuf, buf)->set_roi_full(helpers::unwrapROI(newroi));
 }
 
-int ImageBuf_ymin(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->ymin();
+bool OIIO_ImageBuf_pixels_valid(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->pixels_valid();
 }
 
-int ImageBuf_ymax(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->ymax();
+OIIO_TypeDesc OIIO_ImageBuf_pixeltype(const OIIO_ImageBuf *buf) {
+  return helpers::wrapTypeDesc(OIIO_RS_CAST_CONST(ImageBuf, buf)->pixeltype());
 }
 
-int ImageBuf_zmin(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->zmin();
+bool OIIO_ImageBuf_cachedpixels(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->cachedpixels();
 }
 
-int ImageBuf_zmax(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->zmax();
+bool OIIO_ImageBuf_deep(const OIIO_ImageBuf *buf) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)->deep();
 }
 
-void ImageBuf_set_full(ImageBuf* buf, int xbegin, int xend, int ybegin, int yend, int zbegin, int zend) {
-	static_cast<OIIO::ImageBuf*>(buf)->set_full(xbegin, xend, ybegin, yend, zbegin, zend);
+bool OIIO_ImageBuf_get_pixels(const OIIO_ImageBuf *buf, OIIO_ROI roi, OIIO_TypeDesc format,
+                              void *result, stride_t xstride, stride_t ystride,
+                              stride_t zstride) {
+  return OIIO_RS_CAST_CONST(ImageBuf, buf)
+      ->get_pixels(helpers::unwrapROI(roi), helpers::unwrapTypeDesc(format), result, xstride,
+                   ystride, zstride);
 }
 
-ROI* ImageBuf_roi(ImageBuf* buf) {
-	OIIO::ROI roi(static_cast<OIIO::ImageBuf*>(buf)->roi());
-	OIIO::ROI *ptr = new OIIO::ROI();
-	*ptr = roi;
-	return static_cast<ROI*>(ptr);
+bool OIIO_ImageBuf_set_pixels(OIIO_ImageBuf *buf, OIIO_ROI roi, OIIO_TypeDesc format,
+                              const void *data, stride_t xstride, stride_t ystride,
+                              stride_t zstride) {
+  return OIIO_RS_CAST(ImageBuf, buf)
+      ->set_pixels(helpers::unwrapROI(roi), helpers::unwrapTypeDesc(format), data, xstride,
+                   ystride, zstride);
 }
 
-ROI* ImageBuf_roi_full(ImageBuf* buf) {
-	OIIO::ROI roi(static_cast<OIIO::ImageBuf*>(buf)->roi_full());
-	OIIO::ROI *ptr = new OIIO::ROI();
-	*ptr = roi;
-	return static_cast<ROI*>(ptr);
-}
-
-void ImageBuf_set_roi_full(ImageBuf* buf, ROI* newroi) {
-	static_cast<OIIO::ImageBuf*>(buf)->set_roi_full(*(static_cast<OIIO::ROI*>(newroi)));
-}
-
-bool ImageBuf_pixels_valid(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->pixels_valid();
-}
-
-TypeDesc ImageBuf_pixeltype(ImageBuf* buf) {
-	return toTypeDesc(static_cast<OIIO::ImageBuf*>(buf)->pixeltype());
-}
-
-// void* ImageBuf_localpixels(ImageBuf* buf);
-
-// const void* ImageBuf_localpixels(ImageBuf* buf);
-
-bool ImageBuf_cachedpixels(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->cachedpixels();
-}
-
-ImageCache* ImageBuf_imagecache(ImageBuf* buf) {
-	OIIO::ImageCache *cache = static_cast<OIIO::ImageBuf*>(buf)->imagecache();
-	return static_cast<ImageCache*>(cache);
-}
-
-// void* ImageBuf_pixeladdr(ImageBuf* buf, int x, int y);
-
-// void* ImageBuf_pixeladdr_z(ImageBuf* buf, int x, int y, int z);
-
-bool ImageBuf_deep(ImageBuf* buf) {
-	return static_cast<OIIO::ImageBuf*>(buf)->deep();
-}
-
-// int ImageBuf_deep_samples(ImageBuf* buf, int x, int y, int z);
-
-// const void* ImageBuf_deep_pixel_ptr(ImageBuf* buf, int x, int y, int z, int c);
-
-// float ImageBuf_deep_value(ImageBuf* buf, int x, int y, int z, int c, int s);
-
-// DeepData* ImageBuf_deepdata(ImageBuf* buf);
-
 } // extern "C"
-
-
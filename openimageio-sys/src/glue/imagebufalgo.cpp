@@ -1,342 +1,476 @@
-#include <OpenImageIO/imagebufalgo.h>
+#include <OpenImageIO/Imath.h>
 #include <OpenImageIO/color.h>
+#include <OpenImageIO/imagebufalgo.h>
+#include <OpenImageIO/imagebufalgo_util.h>
 
-#include <stdint.h>
+#include "helpers.hpp"
+#include "oiio.h"
+#include <algorithm>
 #include <string>
+#include <vector>
 
-#include "oiio.h"
-#include "color.hpp"
+namespace {
+
+OIIO::ImageBufAlgo::Image_or_Const unwrapImageOrConst(OIIO_ImageOrConst x) {
+  if (x.img) {
+    return OIIO::ImageBufAlgo::Image_or_Const{*OIIO_RS_CAST_CONST(ImageBuf, x.img)};
+  } else {
+    return OIIO::ImageBufAlgo::Image_or_Const{OIIO::cspan<float>{x.values, x.nvalues}};
+  }
+}
+
+} // namespace
 
 extern "C" {
 
-bool zero(ImageBuf *dst, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::zero(*(static_cast<OIIO::ImageBuf*>(dst)),
-									*(static_cast<OIIO::ROI*>(roi)),
-									nthreads);
-}
-
-bool fill(ImageBuf *dst, const float *values, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::fill(*(static_cast<OIIO::ImageBuf*>(dst)),
-									values,
-									*(static_cast<OIIO::ROI*>(roi)),
-									nthreads);
-}
-
-bool checker(ImageBuf *dst, int width, int height, int depth, const float *color1, const float *color2,
-			  int xoffset, int yoffset, int zoffset, ROI* roi, int nthreads) 
-{
-	return OIIO::ImageBufAlgo::checker(
-		*(static_cast<OIIO::ImageBuf*>(dst)),
-		width, height, depth, 
-		color1, color2,
-		xoffset, yoffset, zoffset,
-		*(static_cast<OIIO::ROI*>(roi)),
-		nthreads);	
-}
-
-bool channels(ImageBuf *dst, const ImageBuf *src, int nchannels, const int32_t *channelorder,
-			   const float *channelvalues, const char **newchannelnames,
-			   bool shuffle_channel_names)
-{
-	std::vector<std::string> vec_names;
-
-	if (nchannels > 0 && newchannelnames != NULL) {
-		vec_names.assign(newchannelnames, newchannelnames+nchannels);
-	}
-
-	bool ok = OIIO::ImageBufAlgo::channels(*(static_cast<OIIO::ImageBuf*>(dst)),
-											*(static_cast<const OIIO::ImageBuf*>(src)),
-											nchannels, channelorder, channelvalues,
-											&vec_names[0], shuffle_channel_names );
-	return ok;
-}
-
-bool channel_append(ImageBuf *dst, const ImageBuf *A, const ImageBuf *B, 
-	ROI* roi, int nthreads) 
-{
-	return OIIO::ImageBufAlgo::channel_append(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			*(static_cast<const OIIO::ImageBuf*>(B)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);		
-}
-
-bool flatten(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::flatten(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool crop(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::crop(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool cut (ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::cut(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool paste(ImageBuf *dst, int xbegin, int ybegin, int zbegin, int chbegin,
-			const ImageBuf *src, ROI* srcroi, int nthreads) 
-{
-	return OIIO::ImageBufAlgo::paste(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			xbegin,
-			ybegin,
-			zbegin,
-			chbegin,
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(srcroi)),
-			nthreads);
-}
-
-bool flip(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::flip(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool flop(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::flop(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-/*
-bool flipflop(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::flipflop(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}*/
-
-bool transpose(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::transpose(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool add(ImageBuf *dst, const ImageBuf *A, const ImageBuf *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::add(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			*(static_cast<const OIIO::ImageBuf*>(B)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool add_values(ImageBuf *dst, const ImageBuf *A, const float *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::add(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			B,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);		
-}
-
-bool add_value(ImageBuf *dst, const ImageBuf *A, float B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::add(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			B,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool sub(ImageBuf *dst, const ImageBuf *A, const ImageBuf *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::sub(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			*(static_cast<const OIIO::ImageBuf*>(B)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool sub_values(ImageBuf *dst, const ImageBuf *A, const float *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::sub(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			B,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool sub_value(ImageBuf *dst, const ImageBuf *A, float B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::sub(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			B,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool mul(ImageBuf *dst, const ImageBuf *A, const ImageBuf *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::mul(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			*(static_cast<const OIIO::ImageBuf*>(B)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool mul_values(ImageBuf *dst, const ImageBuf *A, const float *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::mul(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			B,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool mul_value(ImageBuf *dst, const ImageBuf *A, float B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::mul(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			B,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-/*
-bool colorconvert(ImageBuf *dst, const ImageBuf *src, const char *from, const char *to,
-				   bool unpremult, ROI* roi, int nthreads) 
-{
-	return OIIO::ImageBufAlgo::colorconvert(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			from,
-			to,
-			unpremult,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-
-}*/
-
-bool colorconvert_processor(ImageBuf *dst, const ImageBuf *src, const ColorProcessor *processor,
-				   			bool unpremult, ROI* roi, int nthreads) 
-{
-	return OIIO::ImageBufAlgo::colorconvert(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			static_cast<const OIIO::ColorProcessor*>(processor),
-			unpremult,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool unpremult(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::unpremult(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool premult(ImageBuf *dst, const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::premult(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool is_constant_color(const ImageBuf *src, float *color, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::isConstantColor(
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			color,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
-
-bool is_constant_channel(const ImageBuf *src, int channel, float val, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::isConstantChannel(
-				*(static_cast<const OIIO::ImageBuf*>(src)),
-				channel,
-				val, 
-				*(static_cast<OIIO::ROI*>(roi)),
-				nthreads);		
-}
-
-bool is_monochrome(const ImageBuf *src, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::isMonochrome(
-				*(static_cast<const OIIO::ImageBuf*>(src)),
-				*(static_cast<OIIO::ROI*>(roi)),
-				nthreads);	
-}
-
-char* computePixelHashSHA1(const ImageBuf *src, const char *extrainfo,
-						   ROI* roi, int blocksize, int nthreads) {
-	std::string aHash = OIIO::ImageBufAlgo::computePixelHashSHA1(
-							*(static_cast<const OIIO::ImageBuf*>(src)),
-							extrainfo,
-							*(static_cast<OIIO::ROI*>(roi)),
-							blocksize,
-							nthreads);
-
-    return strdup(aHash.c_str());
-}
-
-bool resize(ImageBuf *dst, const ImageBuf *src, const char *filtername,
-			 float filterwidth, ROI* roi, int nthreads) 
-{
-	return OIIO::ImageBufAlgo::resize(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			filtername,
-			filterwidth,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-
-}
-
-bool resample(ImageBuf *dst, const ImageBuf *src, bool interpolate, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::resample(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(src)),
-			interpolate,
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);
-}
-
-bool over(ImageBuf *dst, const ImageBuf *A, const ImageBuf *B, ROI* roi, int nthreads) {
-	return OIIO::ImageBufAlgo::over(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			*(static_cast<const OIIO::ImageBuf*>(A)),
-			*(static_cast<const OIIO::ImageBuf*>(B)),
-			*(static_cast<OIIO::ROI*>(roi)),
-			nthreads);	
-}
+// Allocates an uninitialized `dst` the way ImageBufAlgo functions do, and resolves an undefined
+// `roi`. If `roi_full` is true, the full window of `dst` is set to `roi` instead of being copied
+// from `src` (as for resize).
+bool OIIO_ImageBufAlgo_prep(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI *roi,
+                            bool roi_full) {
+  OIIO::ROI r = helpers::unwrapROI(*roi);
+  int prepflags = roi_full ? OIIO::ImageBufAlgo::IBAprep_NO_COPY_ROI_FULL : 0;
+  if (!OIIO::ImageBufAlgo::IBAprep(r, OIIO_RS_CAST(ImageBuf, dst),
+                                   OIIO_RS_CAST_CONST(ImageBuf, src), nullptr, nullptr,
+                                   prepflags)) {
+    return false;
+  }
+  *roi = helpers::wrapROI(r);
+  return true;
+}
 
-bool render_text(ImageBuf *dst, int x, int y, const char *text, int fontsize,
-				  const char *fontname, const float *textcolor) {
+bool OIIO_ImageBufAlgo_crop(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                            int nthreads) {
+  return OIIO::ImageBufAlgo::crop(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                  helpers::unwrapROI(roi), nthreads);
+}
 
-	if (fontsize <= 0) fontsize = 16;
+bool OIIO_ImageBufAlgo_cut(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                           int nthreads) {
+  return OIIO::ImageBufAlgo::cut(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                 helpers::unwrapROI(roi), nthreads);
+}
 
-	return OIIO::ImageBufAlgo::render_text(
-			*(static_cast<OIIO::ImageBuf*>(dst)),
-			x, y, 
-			OIIO::string_view(text), 
-			fontsize, 
-			OIIO::string_view(fontname),
-			textcolor);
+bool OIIO_ImageBufAlgo_paste(OIIO_ImageBuf *dst, int xbegin, int ybegin, int zbegin, int chbegin,
+                             const OIIO_ImageBuf *src, OIIO_ROI srcroi, int nthreads) {
+  return OIIO::ImageBufAlgo::paste(*OIIO_RS_CAST(ImageBuf, dst), xbegin, ybegin, zbegin, chbegin,
+                                   *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(srcroi),
+                                   nthreads);
 }
 
-} // extern "C"
+bool OIIO_ImageBufAlgo_flip(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                            int nthreads) {
+  return OIIO::ImageBufAlgo::flip(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                  helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_flop(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                            int nthreads) {
+  return OIIO::ImageBufAlgo::flop(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                  helpers::unwrapROI(roi), nthreads);
+}
 
+bool OIIO_ImageBufAlgo_transpose(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads) {
+  return OIIO::ImageBufAlgo::transpose(*OIIO_RS_CAST(ImageBuf, dst),
+                                       *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                       nthreads);
+}
+
+bool OIIO_ImageBufAlgo_rotate90(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                int nthreads) {
+  return OIIO::ImageBufAlgo::rotate90(*OIIO_RS_CAST(ImageBuf, dst),
+                                      *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                      nthreads);
+}
+
+bool OIIO_ImageBufAlgo_rotate180(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads) {
+  return OIIO::ImageBufAlgo::rotate180(*OIIO_RS_CAST(ImageBuf, dst),
+                                       *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                       nthreads);
+}
+
+bool OIIO_ImageBufAlgo_rotate270(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads) {
+  return OIIO::ImageBufAlgo::rotate270(*OIIO_RS_CAST(ImageBuf, dst),
+                                       *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                       nthreads);
+}
+
+bool OIIO_ImageBufAlgo_reorient(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, int nthreads) {
+  return OIIO::ImageBufAlgo::reorient(*OIIO_RS_CAST(ImageBuf, dst),
+                                      *OIIO_RS_CAST_CONST(ImageBuf, src), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_circular_shift(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, int xshift,
+                                      int yshift, int zshift, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::circular_shift(*OIIO_RS_CAST(ImageBuf, dst),
+                                            *OIIO_RS_CAST_CONST(ImageBuf, src), xshift, yshift,
+                                            zshift, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_rotate(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, float angle,
+                              OIIO_StringRef filtername, float filterwidth, bool recompute_roi,
+                              OIIO_ROI roi, int nthreads) {
+  OIIO::string_view sv_filtername{filtername.ptr, filtername.len};
+  return OIIO::ImageBufAlgo::rotate(*OIIO_RS_CAST(ImageBuf, dst),
+                                    *OIIO_RS_CAST_CONST(ImageBuf, src), angle, sv_filtername,
+                                    filterwidth, recompute_roi, helpers::unwrapROI(roi), nthreads);
+}
 
+bool OIIO_ImageBufAlgo_rotate_around(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, float angle,
+                                     float center_x, float center_y, OIIO_StringRef filtername,
+                                     float filterwidth, bool recompute_roi, OIIO_ROI roi,
+                                     int nthreads) {
+  OIIO::string_view sv_filtername{filtername.ptr, filtername.len};
+  return OIIO::ImageBufAlgo::rotate(*OIIO_RS_CAST(ImageBuf, dst),
+                                    *OIIO_RS_CAST_CONST(ImageBuf, src), angle, center_x, center_y,
+                                    sv_filtername, filterwidth, recompute_roi,
+                                    helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_add(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::add(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_sub(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::sub(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_mul(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::mul(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_div(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::div(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_absdiff(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                               OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::absdiff(*OIIO_RS_CAST(ImageBuf, dst),
+                                     *OIIO_RS_CAST_CONST(ImageBuf, A), unwrapImageOrConst(B),
+                                     helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_min(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::min(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_max(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::max(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_mad(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ImageOrConst C, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::mad(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 unwrapImageOrConst(B), unwrapImageOrConst(C),
+                                 helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_pow(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, const float *b, int nb,
+                           OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::pow(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 OIIO::cspan<float>{b, nb}, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_abs(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ROI roi,
+                           int nthreads) {
+  return OIIO::ImageBufAlgo::abs(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                 helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_clamp(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, const float *min,
+                             int nmin, const float *max, int nmax, bool clampalpha01,
+                             OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::clamp(*OIIO_RS_CAST(ImageBuf, dst),
+                                   *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                   OIIO::cspan<float>{min, nmin}, OIIO::cspan<float>{max, nmax},
+                                   clampalpha01, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_colorconvert_processor(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                              const OIIO_ColorProcessor *processor,
+                                              bool unpremult, OIIO_ROI roi, int nthreads) {
+  auto handle = reinterpret_cast<const OIIO::ColorProcessorHandle *>(processor);
+  return OIIO::ImageBufAlgo::colorconvert(*OIIO_RS_CAST(ImageBuf, dst),
+                                          *OIIO_RS_CAST_CONST(ImageBuf, src), handle->get(),
+                                          unpremult, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_colorconvert(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                    OIIO_StringRef fromspace, OIIO_StringRef tospace,
+                                    bool unpremult, OIIO_ColorConfig *colorconfig, OIIO_ROI roi,
+                                    int nthreads) {
+  OIIO::string_view sv_fromspace{fromspace.ptr, fromspace.len};
+  OIIO::string_view sv_tospace{tospace.ptr, tospace.len};
+  return OIIO::ImageBufAlgo::colorconvert(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src), sv_fromspace, sv_tospace,
+      unpremult, "", "", OIIO_RS_CAST(ColorConfig, colorconfig), helpers::unwrapROI(roi),
+      nthreads);
+}
+
+bool OIIO_ImageBufAlgo_ociodisplay(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                   OIIO_StringRef display, OIIO_StringRef view,
+                                   OIIO_StringRef fromspace, OIIO_StringRef looks, bool unpremult,
+                                   OIIO_ColorConfig *colorconfig, OIIO_ROI roi, int nthreads) {
+  OIIO::string_view sv_display{display.ptr, display.len};
+  OIIO::string_view sv_view{view.ptr, view.len};
+  OIIO::string_view sv_fromspace{fromspace.ptr, fromspace.len};
+  OIIO::string_view sv_looks{looks.ptr, looks.len};
+  return OIIO::ImageBufAlgo::ociodisplay(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src), sv_display, sv_view,
+      sv_fromspace, sv_looks, unpremult, "", "", OIIO_RS_CAST(ColorConfig, colorconfig),
+      helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_ociolook(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                OIIO_StringRef looks, OIIO_StringRef fromspace,
+                                OIIO_StringRef tospace, bool unpremult, bool inverse,
+                                OIIO_ColorConfig *colorconfig, OIIO_ROI roi, int nthreads) {
+  OIIO::string_view sv_looks{looks.ptr, looks.len};
+  OIIO::string_view sv_fromspace{fromspace.ptr, fromspace.len};
+  OIIO::string_view sv_tospace{tospace.ptr, tospace.len};
+  return OIIO::ImageBufAlgo::ociolook(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src), sv_looks, sv_fromspace,
+      sv_tospace, unpremult, inverse, "", "", OIIO_RS_CAST(ColorConfig, colorconfig),
+      helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_ociofiletransform(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                         OIIO_StringRef name, bool unpremult, bool inverse,
+                                         OIIO_ColorConfig *colorconfig, OIIO_ROI roi,
+                                         int nthreads) {
+  OIIO::string_view sv_name{name.ptr, name.len};
+  return OIIO::ImageBufAlgo::ociofiletransform(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src), sv_name, unpremult,
+      inverse, OIIO_RS_CAST(ColorConfig, colorconfig), helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_resize(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                              OIIO_StringRef filtername, float filterwidth, OIIO_ROI roi,
+                              int nthreads) {
+  OIIO::string_view sv_filtername{filtername.ptr, filtername.len};
+  return OIIO::ImageBufAlgo::resize(*OIIO_RS_CAST(ImageBuf, dst),
+                                    *OIIO_RS_CAST_CONST(ImageBuf, src), sv_filtername,
+                                    filterwidth, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_resample(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, bool interpolate,
+                                OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::resample(*OIIO_RS_CAST(ImageBuf, dst),
+                                      *OIIO_RS_CAST_CONST(ImageBuf, src), interpolate,
+                                      helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_fit(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                           OIIO_StringRef filtername, float filterwidth, OIIO_StringRef fillmode,
+                           bool exact, OIIO_ROI roi, int nthreads) {
+  OIIO::string_view sv_filtername{filtername.ptr, filtername.len};
+  OIIO::string_view sv_fillmode{fillmode.ptr, fillmode.len};
+  return OIIO::ImageBufAlgo::fit(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                 sv_filtername, filterwidth, sv_fillmode, exact,
+                                 helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_warp(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, const float *M,
+                            OIIO_StringRef filtername, float filterwidth, bool recompute_roi,
+                            OIIO_ImageBuf_WrapMode wrap, OIIO_ROI roi, int nthreads) {
+  OIIO::string_view sv_filtername{filtername.ptr, filtername.len};
+  Imath::M33f matrix{M[0], M[1], M[2], M[3], M[4], M[5], M[6], M[7], M[8]};
+  return OIIO::ImageBufAlgo::warp(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src), matrix, sv_filtername,
+      filterwidth, recompute_roi, static_cast<OIIO::ImageBuf::WrapMode>(wrap),
+      helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_over(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, const OIIO_ImageBuf *B,
+                            OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::over(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                  *OIIO_RS_CAST_CONST(ImageBuf, B), helpers::unwrapROI(roi),
+                                  nthreads);
+}
+
+bool OIIO_ImageBufAlgo_zover(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, const OIIO_ImageBuf *B,
+                             bool z_zeroisinf, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::zover(*OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+                                   *OIIO_RS_CAST_CONST(ImageBuf, B), z_zeroisinf,
+                                   helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_premult(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                               int nthreads) {
+  return OIIO::ImageBufAlgo::premult(*OIIO_RS_CAST(ImageBuf, dst),
+                                     *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                     nthreads);
+}
+
+bool OIIO_ImageBufAlgo_unpremult(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads) {
+  return OIIO::ImageBufAlgo::unpremult(*OIIO_RS_CAST(ImageBuf, dst),
+                                       *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                       nthreads);
+}
+
+bool OIIO_ImageBufAlgo_repremult(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads) {
+  return OIIO::ImageBufAlgo::repremult(*OIIO_RS_CAST(ImageBuf, dst),
+                                       *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                       nthreads);
+}
+
+bool OIIO_ImageBufAlgo_computePixelStats(const OIIO_ImageBuf *src, int nchannels, float *min,
+                                         float *max, float *avg, float *stddev,
+                                         imagesize_t *nancount, imagesize_t *infcount,
+                                         imagesize_t *finitecount, OIIO_ROI roi, int nthreads) {
+  OIIO::ImageBufAlgo::PixelStats stats;
+  if (!OIIO::ImageBufAlgo::computePixelStats(stats, *OIIO_RS_CAST_CONST(ImageBuf, src),
+                                             helpers::unwrapROI(roi), nthreads)) {
+    return false;
+  }
+  int n = std::min(nchannels, static_cast<int>(stats.min.size()));
+  std::copy_n(stats.min.begin(), n, min);
+  std::copy_n(stats.max.begin(), n, max);
+  std::copy_n(stats.avg.begin(), n, avg);
+  std::copy_n(stats.stddev.begin(), n, stddev);
+  std::copy_n(stats.nancount.begin(), n, nancount);
+  std::copy_n(stats.infcount.begin(), n, infcount);
+  std::copy_n(stats.finitecount.begin(), n, finitecount);
+  return true;
+}
+
+bool OIIO_ImageBufAlgo_histogram(const OIIO_ImageBuf *src, int channel, imagesize_t *result,
+                                 int bins, float min, float max, bool ignore_outside_range,
+                                 OIIO_ROI roi, int nthreads) {
+  std::vector<imagesize_t> hist = OIIO::ImageBufAlgo::histogram(
+      *OIIO_RS_CAST_CONST(ImageBuf, src), channel, bins, min, max, ignore_outside_range,
+      helpers::unwrapROI(roi), nthreads);
+  if (hist.size() != static_cast<size_t>(bins)) {
+    return false;
+  }
+  std::copy(hist.begin(), hist.end(), result);
+  return true;
+}
+
+bool OIIO_ImageBufAlgo_color_range_check(const OIIO_ImageBuf *src, imagesize_t *lowcount,
+                                         imagesize_t *highcount, imagesize_t *inrangecount,
+                                         const float *low, int nlow, const float *high,
+                                         int nhigh, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::color_range_check(
+      *OIIO_RS_CAST_CONST(ImageBuf, src), lowcount, highcount, inrangecount,
+      OIIO::cspan<float>{low, nlow}, OIIO::cspan<float>{high, nhigh}, helpers::unwrapROI(roi),
+      nthreads);
+}
+
+OIIO_ROI OIIO_ImageBufAlgo_nonzero_region(const OIIO_ImageBuf *src, OIIO_ROI roi, int nthreads) {
+  return helpers::wrapROI(OIIO::ImageBufAlgo::nonzero_region(*OIIO_RS_CAST_CONST(ImageBuf, src),
+                                                             helpers::unwrapROI(roi), nthreads));
+}
+
+bool OIIO_ImageBufAlgo_isConstantColor(const OIIO_ImageBuf *src, float threshold, float *color,
+                                       int ncolor, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::isConstantColor(*OIIO_RS_CAST_CONST(ImageBuf, src), threshold,
+                                             OIIO::span<float>{color, ncolor},
+                                             helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_isConstantChannel(const OIIO_ImageBuf *src, int channel, float val,
+                                         float threshold, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::isConstantChannel(*OIIO_RS_CAST_CONST(ImageBuf, src), channel, val,
+                                               threshold, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_isMonochrome(const OIIO_ImageBuf *src, float threshold, OIIO_ROI roi,
+                                    int nthreads) {
+  return OIIO::ImageBufAlgo::isMonochrome(*OIIO_RS_CAST_CONST(ImageBuf, src), threshold,
+                                          helpers::unwrapROI(roi), nthreads);
+}
+
+const char *OIIO_ImageBufAlgo_computePixelHashSHA1(const OIIO_ImageBuf *src,
+                                                   OIIO_StringRef extrainfo, OIIO_ROI roi,
+                                                   int blocksize, int nthreads) {
+  OIIO::string_view sv_extrainfo{extrainfo.ptr, extrainfo.len};
+  std::string sstring = OIIO::ImageBufAlgo::computePixelHashSHA1(
+      *OIIO_RS_CAST_CONST(ImageBuf, src), sv_extrainfo, helpers::unwrapROI(roi), blocksize,
+      nthreads);
+  return helpers::makeCString(sstring);
+}
+
+bool OIIO_ImageBufAlgo_flatten(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                               int nthreads) {
+  return OIIO::ImageBufAlgo::flatten(*OIIO_RS_CAST(ImageBuf, dst),
+                                     *OIIO_RS_CAST_CONST(ImageBuf, src), helpers::unwrapROI(roi),
+                                     nthreads);
+}
+
+bool OIIO_ImageBufAlgo_deepen(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, float zvalue,
+                              OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::deepen(*OIIO_RS_CAST(ImageBuf, dst),
+                                    *OIIO_RS_CAST_CONST(ImageBuf, src), zvalue,
+                                    helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_deep_merge(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A,
+                                  const OIIO_ImageBuf *B, bool occlusion_cull, OIIO_ROI roi,
+                                  int nthreads) {
+  return OIIO::ImageBufAlgo::deep_merge(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, A),
+      *OIIO_RS_CAST_CONST(ImageBuf, B), occlusion_cull, helpers::unwrapROI(roi), nthreads);
+}
+
+bool OIIO_ImageBufAlgo_deep_holdout(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                    const OIIO_ImageBuf *holdout, OIIO_ROI roi, int nthreads) {
+  return OIIO::ImageBufAlgo::deep_holdout(
+      *OIIO_RS_CAST(ImageBuf, dst), *OIIO_RS_CAST_CONST(ImageBuf, src),
+      *OIIO_RS_CAST_CONST(ImageBuf, holdout), helpers::unwrapROI(roi), nthreads);
+}
+
+static void wrapCompareResults(const OIIO::ImageBufAlgo::CompareResults &r,
+                               OIIO_CompareResults *result) {
+  result->meanerror = r.meanerror;
+  result->rms_error = r.rms_error;
+  result->PSNR = r.PSNR;
+  result->maxerror = r.maxerror;
+  result->maxx = r.maxx;
+  result->maxy = r.maxy;
+  result->maxz = r.maxz;
+  result->maxc = r.maxc;
+  result->nwarn = r.nwarn;
+  result->nfail = r.nfail;
+  result->error = r.error;
+}
+
+bool OIIO_ImageBufAlgo_compare(const OIIO_ImageBuf *A, const OIIO_ImageBuf *B, float failthresh,
+                               float warnthresh, OIIO_CompareResults *result, OIIO_ROI roi,
+                               int nthreads) {
+  auto r = OIIO::ImageBufAlgo::compare(*OIIO_RS_CAST_CONST(ImageBuf, A),
+                                       *OIIO_RS_CAST_CONST(ImageBuf, B), failthresh, warnthresh,
+                                       helpers::unwrapROI(roi), nthreads);
+  wrapCompareResults(r, result);
+  return !r.error;
+}
+
+int OIIO_ImageBufAlgo_compare_Yee(const OIIO_ImageBuf *A, const OIIO_ImageBuf *B,
+                                  OIIO_CompareResults *result, float luminance, float fov,
+                                  OIIO_ROI roi, int nthreads) {
+  OIIO::ImageBufAlgo::CompareResults r;
+  int nfail = OIIO::ImageBufAlgo::compare_Yee(*OIIO_RS_CAST_CONST(ImageBuf, A),
+                                              *OIIO_RS_CAST_CONST(ImageBuf, B), r, luminance, fov,
+                                              helpers::unwrapROI(roi), nthreads);
+  wrapCompareResults(r, result);
+  return nfail;
+}
+
+} // extern "C"
@@ -1,3 +1,4 @@
+#include <OpenImageIO/deepdata.h>
 #include <OpenImageIO/imageio.h>
 
 #include <string>
@@ -72,6 +73,18 @@ bool OIIO_ImageInput_seek_subimage_miplevel(OIIO_ImageInput *in, int subimage, i
       ->seek_subimage(subimage, miplevel, *OIIO_RS_CAST(ImageSpec, newspec));
 }
 
+OIIO_ImageSpec *OIIO_ImageInput_spec_level(OIIO_ImageInput *in, int subimage, int miplevel) {
+  auto input = OIIO_RS_CAST(ImageInput, in);
+  // seek and copy under the input lock, so that concurrent reads do not change the current level
+  input->lock();
+  OIIO::ImageSpec *spec = nullptr;
+  if (input->seek_subimage(subimage, miplevel)) {
+    spec = new OIIO::ImageSpec{input->spec()};
+  }
+  input->unlock();
+  return (OIIO_ImageSpec *)spec;
+}
+
 bool OIIO_ImageInput_read_image_floats(OIIO_ImageInput *in, float *data) {
   return OIIO_RS_CAST(ImageInput, in)->read_image(data);
 }
@@ -86,10 +99,11 @@ bool OIIO_ImageInput_read_scanlines(OIIO_ImageInput *in, int subimage, int miple
 
 bool OIIO_ImageInput_read_image(OIIO_ImageInput *in, int subimage, int miplevel, int chbegin,
                                 int chend, OIIO_TypeDesc format, void *data, stride_t xstride,
-                                stride_t ystride, stride_t zstride) {
+                                stride_t ystride, stride_t zstride,
+                                ProgressCallback progress_callback, void *progress_callback_data) {
   return OIIO_RS_CAST(ImageInput, in)
       ->read_image(subimage, miplevel, chbegin, chend, helpers::unwrapTypeDesc(format), data,
-                   xstride, ystride, zstride, nullptr, nullptr);
+                   xstride, ystride, zstride, progress_callback, progress_callback_data);
 }
 
 bool OIIO_ImageInput_read_tiles(OIIO_ImageInput *in, int subimage, int miplevel, int xbegin,
@@ -101,6 +115,29 @@ bool OIIO_ImageInput_read_tiles(OIIO_ImageInput *in, int subimage, int miplevel,
                    helpers::unwrapTypeDesc(format), data, xstride, ystride, zstride);
 }
 
+bool OIIO_ImageInput_read_native_deep_scanlines(OIIO_ImageInput *in, int subimage, int miplevel,
+                                                int ybegin, int yend, int z, int chbegin,
+                                                int chend, OIIO_DeepData *deepdata) {
+  return OIIO_RS_CAST(ImageInput, in)
+      ->read_native_deep_scanlines(subimage, miplevel, ybegin, yend, z, chbegin, chend,
+                                   *OIIO_RS_CAST(DeepData, deepdata));
+}
+
+bool OIIO_ImageInput_read_native_deep_tiles(OIIO_ImageInput *in, int subimage, int miplevel,
+                                            int xbegin, int xend, int ybegin, int yend,
+                                            int zbegin, int zend, int chbegin, int chend,
+                                            OIIO_DeepData *deepdata) {
+  return OIIO_RS_CAST(ImageInput, in)
+      ->read_native_deep_tiles(subimage, miplevel, xbegin, xend, ybegin, yend, zbegin, zend,
+                               chbegin, chend, *OIIO_RS_CAST(DeepData, deepdata));
+}
+
+bool OIIO_ImageInput_read_native_deep_image(OIIO_ImageInput *in, int subimage, int miplevel,
+                                            OIIO_DeepData *deepdata) {
+  return OIIO_RS_CAST(ImageInput, in)
+      ->read_native_deep_image(subimage, miplevel, *OIIO_RS_CAST(DeepData, deepdata));
+}
+
 /*bool OIIO_ImageInput_read_image_format(OIIO_ImageInput *in, OIIO_TypeDesc format, void *data,
                                        void *cbk_data) {
   ProgressCallback cbk = nullptr;
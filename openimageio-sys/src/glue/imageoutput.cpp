@@ -1,3 +1,4 @@
+#include <OpenImageIO/deepdata.h>
 #include <OpenImageIO/imageio.h>
 
 #include "helpers.hpp"
@@ -21,9 +22,12 @@ bool OIIO_ImageOutput_close(OIIO_ImageOutput *out) {
 }
 
 bool OIIO_ImageOutput_write_image(OIIO_ImageOutput *out, OIIO_TypeDesc format, const void *data,
-                                  ptrdiff_t xstride, ptrdiff_t ystride, ptrdiff_t zstride) {
+                                  ptrdiff_t xstride, ptrdiff_t ystride, ptrdiff_t zstride,
+                                  ProgressCallback progress_callback,
+                                  void *progress_callback_data) {
   return OIIO_RS_CAST(ImageOutput, out)
-      ->write_image(helpers::unwrapTypeDesc(format), data, xstride, ystride, zstride);
+      ->write_image(helpers::unwrapTypeDesc(format), data, xstride, ystride, zstride,
+                    progress_callback, progress_callback_data);
 }
 
 bool OIIO_ImageOutput_write_scanline(OIIO_ImageOutput *out, int y, int z, OIIO_TypeDesc format,
@@ -32,6 +36,66 @@ bool OIIO_ImageOutput_write_scanline(OIIO_ImageOutput *out, int y, int z, OIIO_T
       ->write_scanline(y, z, helpers::unwrapTypeDesc(format), data, xstride);
 }
 
+bool OIIO_ImageOutput_write_scanlines(OIIO_ImageOutput *out, int ybegin, int yend, int z,
+                                      OIIO_TypeDesc format, const void *data, stride_t xstride,
+                                      stride_t ystride) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->write_scanlines(ybegin, yend, z, helpers::unwrapTypeDesc(format), data, xstride, ystride);
+}
+
+bool OIIO_ImageOutput_write_rectangle(OIIO_ImageOutput *out, int xbegin, int xend, int ybegin,
+                                      int yend, int zbegin, int zend, OIIO_TypeDesc format,
+                                      const void *data, stride_t xstride, stride_t ystride,
+                                      stride_t zstride) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->write_rectangle(xbegin, xend, ybegin, yend, zbegin, zend, helpers::unwrapTypeDesc(format),
+                        data, xstride, ystride, zstride);
+}
+
+bool OIIO_ImageOutput_write_tile(OIIO_ImageOutput *out, int x, int y, int z, OIIO_TypeDesc format,
+                                 const void *data, stride_t xstride, stride_t ystride,
+                                 stride_t zstride) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->write_tile(x, y, z, helpers::unwrapTypeDesc(format), data, xstride, ystride, zstride);
+}
+
+bool OIIO_ImageOutput_write_tiles(OIIO_ImageOutput *out, int xbegin, int xend, int ybegin, int yend,
+                                  int zbegin, int zend, OIIO_TypeDesc format, const void *data,
+                                  stride_t xstride, stride_t ystride, stride_t zstride) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->write_tiles(xbegin, xend, ybegin, yend, zbegin, zend, helpers::unwrapTypeDesc(format), data,
+                    xstride, ystride, zstride);
+}
+
+bool OIIO_ImageOutput_write_deep_scanlines(OIIO_ImageOutput *out, int ybegin, int yend, int z,
+                                           const OIIO_DeepData *deepdata) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->write_deep_scanlines(ybegin, yend, z, *OIIO_RS_CAST_CONST(DeepData, deepdata));
+}
+
+bool OIIO_ImageOutput_write_deep_tiles(OIIO_ImageOutput *out, int xbegin, int xend, int ybegin,
+                                       int yend, int zbegin, int zend,
+                                       const OIIO_DeepData *deepdata) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->write_deep_tiles(xbegin, xend, ybegin, yend, zbegin, zend,
+                         *OIIO_RS_CAST_CONST(DeepData, deepdata));
+}
+
+bool OIIO_ImageOutput_write_deep_image(OIIO_ImageOutput *out, const OIIO_DeepData *deepdata) {
+  return OIIO_RS_CAST(ImageOutput, out)->write_deep_image(*OIIO_RS_CAST_CONST(DeepData, deepdata));
+}
+
+bool OIIO_ImageOutput_copy_image(OIIO_ImageOutput *out, OIIO_ImageInput *in, int subimage,
+                                 int miplevel) {
+  auto input = OIIO_RS_CAST(ImageInput, in);
+  // copy_image reads the current subimage and MIP level: seek and copy under the input lock
+  input->lock();
+  bool ok = input->seek_subimage(subimage, miplevel) &&
+            OIIO_RS_CAST(ImageOutput, out)->copy_image(input);
+  input->unlock();
+  return ok;
+}
+
 bool OIIO_ImageOutput_open2(OIIO_ImageOutput *out, OIIO_StringRef name, int subimages,
                             const OIIO_ImageSpec *const *specs) {
   std::string s_filename{name.ptr, name.len};
@@ -11,16 +11,26 @@ OIIO_ImageSpec *OIIO_ImageSpec_new(OIIO_TypeDesc fmt) {
   return (OIIO_ImageSpec *)new OIIO::ImageSpec(helpers::unwrapTypeDesc(fmt));
 }
 
-OIIO_ImageSpec *OIIO_ImageSpec_clone(OIIO_ImageSpec *from) {
-  return (OIIO_ImageSpec *)new OIIO::ImageSpec(*OIIO_RS_CAST(ImageSpec, from));
+OIIO_ImageSpec *OIIO_ImageSpec_clone(const OIIO_ImageSpec *from) {
+  return (OIIO_ImageSpec *)new OIIO::ImageSpec(*OIIO_RS_CAST_CONST(ImageSpec, from));
 }
 
 OIIO_ImageSpec *OIIO_ImageSpec_new_2d(int xres, int yres, int nchans, bool separateformats,
                                       const OIIO_TypeDesc *channelformats,
                                       const OIIO_StringRef *channelnames) {
   auto spec = new OIIO::ImageSpec(xres, yres, nchans, helpers::unwrapTypeDesc(*channelformats));
+  // replace the default channel names, and find the alpha and depth channels among the new ones
+  spec->channelnames.clear();
+  spec->alpha_channel = -1;
+  spec->z_channel = -1;
   for (int i = 0; i < nchans; ++i) {
-    spec->channelnames.push_back(std::string{channelnames[i].ptr, channelnames[i].len});
+    std::string name{channelnames[i].ptr, channelnames[i].len};
+    if (name == "A" || name == "Alpha") {
+      spec->alpha_channel = i;
+    } else if (name == "Z" || name == "Depth") {
+      spec->z_channel = i;
+    }
+    spec->channelnames.push_back(std::move(name));
     if (separateformats) {
       spec->channelformats.push_back(helpers::unwrapTypeDesc(channelformats[i]));
     }
@@ -275,9 +285,10 @@ char *OIIO_ImageSpec_to_xml(OIIO_ImageSpec *spec) {
   return strdup(s.c_str());
 }
 
-void OIIO_ImageSpec_attribute_type_data(OIIO_ImageSpec *spec, const char *name, OIIO_TypeDesc type,
-                                        const void *value) {
-  OIIO_RS_CAST(ImageSpec, spec)->attribute(name, helpers::unwrapTypeDesc(type), value);
+void OIIO_ImageSpec_attribute_type_data(OIIO_ImageSpec *spec, OIIO_StringRef name,
+                                        OIIO_TypeDesc type, const void *value) {
+  OIIO_RS_CAST(ImageSpec, spec)
+      ->attribute(OIIO::string_view{name.ptr, name.len}, helpers::unwrapTypeDesc(type), value);
 }
 
 void OIIO_ImageSpec_attribute_type_char(OIIO_ImageSpec *spec, const char *name, OIIO_TypeDesc type,
@@ -0,0 +1,116 @@
+#include <OpenImageIO/filesystem.h>
+#include <OpenImageIO/imageio.h>
+
+#include <algorithm>
+
+#include "helpers.hpp"
+#include "oiio.h"
+
+namespace {
+
+/// Read-only IOProxy forwarding reads to a callback (implemented on the Rust side).
+class CallbackReader : public OIIO::Filesystem::IOProxy {
+public:
+  CallbackReader(void *opaque, OIIO_IOProxy_ReadAtFn read_at, int64_t size)
+      : IOProxy("", IOProxy::Read), m_opaque{opaque}, m_read_at{read_at}, m_size{size} {}
+
+  const char *proxytype() const override { return "rust"; }
+
+  size_t read(void *buf, size_t size) override {
+    size_t n = pread(buf, size, m_pos);
+    m_pos += n;
+    return n;
+  }
+
+  size_t pread(void *buf, size_t size, int64_t offset) override {
+    return m_read_at(m_opaque, buf, size, offset);
+  }
+
+  size_t size() const override { return size_t(m_size); }
+
+private:
+  void *m_opaque;
+  OIIO_IOProxy_ReadAtFn m_read_at;
+  int64_t m_size;
+};
+
+/// Write-only IOProxy forwarding writes to a callback (implemented on the Rust side).
+class CallbackWriter : public OIIO::Filesystem::IOProxy {
+public:
+  CallbackWriter(void *opaque, OIIO_IOProxy_WriteAtFn write_at)
+      : IOProxy("", IOProxy::Write), m_opaque{opaque}, m_write_at{write_at} {}
+
+  const char *proxytype() const override { return "rust"; }
+
+  size_t write(const void *buf, size_t size) override {
+    size_t n = pwrite(buf, size, m_pos);
+    m_pos += n;
+    return n;
+  }
+
+  size_t pwrite(const void *buf, size_t size, int64_t offset) override {
+    size_t n = m_write_at(m_opaque, buf, size, offset);
+    m_size = std::max(m_size, offset + int64_t(n));
+    return n;
+  }
+
+  size_t size() const override { return size_t(m_size); }
+
+private:
+  void *m_opaque;
+  OIIO_IOProxy_WriteAtFn m_write_at;
+  int64_t m_size = 0;
+};
+
+} // namespace
+
+extern "C" {
+
+OIIO_IOProxy *OIIO_IOProxy_new_memreader(const void *data, size_t size) {
+  auto proxy = new OIIO::Filesystem::IOMemReader(const_cast<void *>(data), size);
+  return reinterpret_cast<OIIO_IOProxy *>(static_cast<OIIO::Filesystem::IOProxy *>(proxy));
+}
+
+OIIO_IOProxy *OIIO_IOProxy_new_reader(void *opaque, OIIO_IOProxy_ReadAtFn read_at, int64_t size) {
+  auto proxy = new CallbackReader(opaque, read_at, size);
+  return reinterpret_cast<OIIO_IOProxy *>(static_cast<OIIO::Filesystem::IOProxy *>(proxy));
+}
+
+OIIO_IOProxy *OIIO_IOProxy_new_writer(void *opaque, OIIO_IOProxy_WriteAtFn write_at) {
+  auto proxy = new CallbackWriter(opaque, write_at);
+  return reinterpret_cast<OIIO_IOProxy *>(static_cast<OIIO::Filesystem::IOProxy *>(proxy));
+}
+
+OIIO_IOProxy *OIIO_IOProxy_new_vecoutput() {
+  auto proxy = new OIIO::Filesystem::IOVecOutput();
+  return reinterpret_cast<OIIO_IOProxy *>(static_cast<OIIO::Filesystem::IOProxy *>(proxy));
+}
+
+const unsigned char *OIIO_IOProxy_vecoutput_data(const OIIO_IOProxy *proxy, size_t *size) {
+  auto vecoutput = static_cast<const OIIO::Filesystem::IOVecOutput *>(
+      reinterpret_cast<const OIIO::Filesystem::IOProxy *>(proxy));
+  *size = vecoutput->buffer().size();
+  return vecoutput->buffer().data();
+}
+
+void OIIO_IOProxy_delete(OIIO_IOProxy *proxy) {
+  delete reinterpret_cast<OIIO::Filesystem::IOProxy *>(proxy);
+}
+
+bool OIIO_ImageInput_open_ioproxy(OIIO_ImageInput *in, OIIO_IOProxy *proxy,
+                                  const OIIO_ImageSpec *config) {
+  auto ioproxy = reinterpret_cast<OIIO::Filesystem::IOProxy *>(proxy);
+  OIIO::ImageSpec cfg = config ? *OIIO_RS_CAST_CONST(ImageSpec, config) : OIIO::ImageSpec{};
+  cfg.attribute("oiio:ioproxy", OIIO::TypeDesc::PTR, &ioproxy);
+  // a previous reader may have moved the position
+  ioproxy->seek(0);
+  OIIO::ImageSpec newspec;
+  return OIIO_RS_CAST(ImageInput, in)->open("", newspec, cfg);
+}
+
+bool OIIO_ImageOutput_set_ioproxy(OIIO_ImageOutput *out, OIIO_IOProxy *proxy) {
+  return OIIO_RS_CAST(ImageOutput, out)
+      ->set_ioproxy(reinterpret_cast<OIIO::Filesystem::IOProxy *>(proxy));
+}
+
+} // extern "C"
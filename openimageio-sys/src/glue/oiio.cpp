@@ -1,8 +1,15 @@
 #include "oiio.h"
 #include "helpers.hpp"
 #include <OpenImageIO/imageio.h>
+#include <string>
 
 extern "C" {
 const char *OIIO_geterror() { return helpers::makeCString(OIIO::geterror()); }
 void OIIO_freeString(const char *ptr) { helpers::freeCString(ptr); }
+
+const char *OIIO_input_format_list() {
+  std::string list;
+  OIIO::getattribute("input_format_list", list);
+  return helpers::makeCString(list);
+}
 }
\ No newline at end of file
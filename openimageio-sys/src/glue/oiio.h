@@ -66,6 +66,60 @@ typedef struct OIIO_ImageOutput OIIO_ImageOutput;
 typedef struct OIIO_ImageCache OIIO_ImageCache;
 typedef struct OIIO_ImageCache_ImageHandle OIIO_ImageCache_ImageHandle;
 typedef struct OIIO_ImageCache_Perthread OIIO_ImageCache_Perthread;
+typedef struct OIIO_ImageBuf OIIO_ImageBuf;
+typedef struct OIIO_ColorConfig OIIO_ColorConfig;
+typedef struct OIIO_ColorProcessor OIIO_ColorProcessor;
+typedef struct OIIO_IOProxy OIIO_IOProxy;
+typedef struct OIIO_DeepData OIIO_DeepData;
+
+typedef struct {
+  int xbegin;  ///< Start of the x range (INT_MIN for an undefined ROI)
+  int xend;    ///< One past the end of the x range
+  int ybegin;  ///< Start of the y range
+  int yend;    ///< One past the end of the y range
+  int zbegin;  ///< Start of the z range
+  int zend;    ///< One past the end of the z range
+  int chbegin; ///< First channel
+  int chend;   ///< One past the last channel
+} OIIO_ROI;
+
+typedef enum {
+  OIIO_ImageBuf_IBStorage_Uninitialized,
+  OIIO_ImageBuf_IBStorage_LocalBuffer,
+  OIIO_ImageBuf_IBStorage_AppBuffer,
+  OIIO_ImageBuf_IBStorage_ImageCache,
+} OIIO_ImageBuf_IBStorage;
+
+typedef enum {
+  OIIO_ImageBuf_WrapMode_WrapDefault,
+  OIIO_ImageBuf_WrapMode_WrapBlack,
+  OIIO_ImageBuf_WrapMode_WrapClamp,
+  OIIO_ImageBuf_WrapMode_WrapPeriodic,
+  OIIO_ImageBuf_WrapMode_WrapMirror,
+} OIIO_ImageBuf_WrapMode;
+
+/// Operand of an ImageBufAlgo function: either an image (if `img` is not null), or per-channel
+/// constant values.
+typedef struct {
+  const OIIO_ImageBuf *img;
+  const float *values;
+  int nvalues;
+} OIIO_ImageOrConst;
+
+/// Result of an image comparison.
+typedef struct {
+  double meanerror;
+  double rms_error;
+  double PSNR;
+  double maxerror;
+  int maxx;
+  int maxy;
+  int maxz;
+  int maxc;
+  imagesize_t nwarn;
+  imagesize_t nfail;
+  bool error;
+} OIIO_CompareResults;
 
 typedef enum {
   OIIO_ImageOutput_OpenMode_Create,
@@ -75,6 +129,12 @@ typedef enum {
 
 typedef bool (*ProgressCallback)(void *opaque_data, float portion_done);
 
+/// Reads up to `size` bytes at `offset` into `buf`, and returns the number of bytes read.
+typedef size_t (*OIIO_IOProxy_ReadAtFn)(void *opaque, void *buf, size_t size, int64_t offset);
+/// Writes `size` bytes from `buf` at `offset`, and returns the number of bytes written.
+typedef size_t (*OIIO_IOProxy_WriteAtFn)(void *opaque, const void *buf, size_t size,
+                                         int64_t offset);
+
 typedef struct {
   const char *ptr;
   size_t len;
@@ -82,6 +142,17 @@ typedef struct {
 
 void OIIO_freeString(const char *ptr);
 const char *OIIO_geterror();
+const char *OIIO_input_format_list();
+
+//---------------------------------------------------------------------
+// OIIO_IOProxy
+
+OIIO_IOProxy *OIIO_IOProxy_new_memreader(const void *data, size_t size);
+OIIO_IOProxy *OIIO_IOProxy_new_reader(void *opaque, OIIO_IOProxy_ReadAtFn read_at, int64_t size);
+OIIO_IOProxy *OIIO_IOProxy_new_writer(void *opaque, OIIO_IOProxy_WriteAtFn write_at);
+OIIO_IOProxy *OIIO_IOProxy_new_vecoutput();
+const unsigned char *OIIO_IOProxy_vecoutput_data(const OIIO_IOProxy *proxy, size_t *size);
+void OIIO_IOProxy_delete(OIIO_IOProxy *proxy);
 
 //---------------------------------------------------------------------
 // OIIO_ImageInput
@@ -98,6 +169,9 @@ bool OIIO_ImageInput_supports(const OIIO_ImageInput *in, OIIO_StringRef feature)
 bool OIIO_ImageInput_close(OIIO_ImageInput *in);
 int OIIO_ImageInput_current_subimage(const OIIO_ImageInput *in);
 int OIIO_ImageInput_current_miplevel(const OIIO_ImageInput *in);
+OIIO_ImageSpec *OIIO_ImageInput_spec_level(OIIO_ImageInput *in, int subimage, int miplevel);
+bool OIIO_ImageInput_open_ioproxy(OIIO_ImageInput *in, OIIO_IOProxy *proxy,
+                                  const OIIO_ImageSpec *config);
 
 // bool OIIO_ImageInput_seek_subimage(OIIO_ImageInput *in, int subimage, OIIO_ImageSpec *newspec);
 // bool OIIO_ImageInput_seek_subimage_miplevel(OIIO_ImageInput *in, int subimage, int miplevel,
@@ -108,7 +182,8 @@ bool OIIO_ImageInput_read_scanlines(OIIO_ImageInput *in, int subimage, int miple
 
 bool OIIO_ImageInput_read_image(OIIO_ImageInput *in, int subimage, int miplevel, int chbegin,
                                 int chend, OIIO_TypeDesc format, void *data, stride_t xstride,
-                                stride_t ystride, stride_t zstride);
+                                stride_t ystride, stride_t zstride,
+                                ProgressCallback progress_callback, void *progress_callback_data);
 
 bool OIIO_ImageInput_read_tiles(OIIO_ImageInput *in, int subimage, int miplevel, int xbegin,
                                 int xend, int ybegin, int yend, int zbegin, int zend, int chbegin,
@@ -125,6 +200,15 @@ bool OIIO_ImageInput_read_tiles(OIIO_ImageInput *in, int subimage, int miplevel,
 bool OIIO_ImageInput_read_image_format2(OIIO_ImageInput *in, int chbegin, int chend,
                                         OIIO_TypeDesc format, void *data, stride_t xstride,
                                         stride_t ystride, stride_t zstride, void *cbk_data);*/
+bool OIIO_ImageInput_read_native_deep_scanlines(OIIO_ImageInput *in, int subimage, int miplevel,
+                                                int ybegin, int yend, int z, int chbegin,
+                                                int chend, OIIO_DeepData *deepdata);
+bool OIIO_ImageInput_read_native_deep_tiles(OIIO_ImageInput *in, int subimage, int miplevel,
+                                            int xbegin, int xend, int ybegin, int yend,
+                                            int zbegin, int zend, int chbegin, int chend,
+                                            OIIO_DeepData *deepdata);
+bool OIIO_ImageInput_read_native_deep_image(OIIO_ImageInput *in, int subimage, int miplevel,
+                                            OIIO_DeepData *deepdata);
 const char *OIIO_ImageInput_geterror(const OIIO_ImageInput *in);
 
 // bool ImageInput_read_native_scanline(OIIO_ImageInput *in, int y, int z, void *data);
@@ -154,10 +238,65 @@ bool OIIO_ImageOutput_open(OIIO_ImageOutput *out, OIIO_StringRef name,
 bool OIIO_ImageOutput_open2(OIIO_ImageOutput *out, OIIO_StringRef name, int subimages,
                             const OIIO_ImageSpec *const *specs);
 bool OIIO_ImageOutput_close(OIIO_ImageOutput *out);
+bool OIIO_ImageOutput_set_ioproxy(OIIO_ImageOutput *out, OIIO_IOProxy *proxy);
 bool OIIO_ImageOutput_write_image(OIIO_ImageOutput *out, OIIO_TypeDesc format, const void *data,
-                                  ptrdiff_t xstride, ptrdiff_t ystride, ptrdiff_t zstride);
+                                  ptrdiff_t xstride, ptrdiff_t ystride, ptrdiff_t zstride,
+                                  ProgressCallback progress_callback,
+                                  void *progress_callback_data);
 bool OIIO_ImageOutput_write_scanline(OIIO_ImageOutput *out, int y, int z, OIIO_TypeDesc format,
                                      const void *data, stride_t xstride);
+bool OIIO_ImageOutput_write_scanlines(OIIO_ImageOutput *out, int ybegin, int yend, int z,
+                                      OIIO_TypeDesc format, const void *data, stride_t xstride,
+                                      stride_t ystride);
+bool OIIO_ImageOutput_write_rectangle(OIIO_ImageOutput *out, int xbegin, int xend, int ybegin,
+                                      int yend, int zbegin, int zend, OIIO_TypeDesc format,
+                                      const void *data, stride_t xstride, stride_t ystride,
+                                      stride_t zstride);
+bool OIIO_ImageOutput_write_tile(OIIO_ImageOutput *out, int x, int y, int z, OIIO_TypeDesc format,
+                                 const void *data, stride_t xstride, stride_t ystride,
+                                 stride_t zstride);
+bool OIIO_ImageOutput_write_tiles(OIIO_ImageOutput *out, int xbegin, int xend, int ybegin, int yend,
+                                  int zbegin, int zend, OIIO_TypeDesc format, const void *data,
+                                  stride_t xstride, stride_t ystride, stride_t zstride);
+bool OIIO_ImageOutput_write_deep_scanlines(OIIO_ImageOutput *out, int ybegin, int yend, int z,
+                                           const OIIO_DeepData *deepdata);
+bool OIIO_ImageOutput_write_deep_tiles(OIIO_ImageOutput *out, int xbegin, int xend, int ybegin,
+                                       int yend, int zbegin, int zend,
+                                       const OIIO_DeepData *deepdata);
+bool OIIO_ImageOutput_write_deep_image(OIIO_ImageOutput *out, const OIIO_DeepData *deepdata);
+bool OIIO_ImageOutput_copy_image(OIIO_ImageOutput *out, OIIO_ImageInput *in, int subimage,
+                                 int miplevel);
+
+//---------------------------------------------------------------------
+// OIIO_DeepData
+//
+
+OIIO_DeepData *OIIO_DeepData_new();
+OIIO_DeepData *OIIO_DeepData_clone(const OIIO_DeepData *dd);
+void OIIO_DeepData_delete(OIIO_DeepData *dd);
+void OIIO_DeepData_init(OIIO_DeepData *dd, int64_t npixels, int nchannels,
+                        const OIIO_TypeDesc *channeltypes, const OIIO_StringRef *channelnames);
+void OIIO_DeepData_init_spec(OIIO_DeepData *dd, const OIIO_ImageSpec *spec);
+int64_t OIIO_DeepData_pixels(const OIIO_DeepData *dd);
+int OIIO_DeepData_channels(const OIIO_DeepData *dd);
+int OIIO_DeepData_Z_channel(const OIIO_DeepData *dd);
+int OIIO_DeepData_Zback_channel(const OIIO_DeepData *dd);
+int OIIO_DeepData_A_channel(const OIIO_DeepData *dd);
+const char *OIIO_DeepData_channelname(const OIIO_DeepData *dd, int c);
+OIIO_TypeDesc OIIO_DeepData_channeltype(const OIIO_DeepData *dd, int c);
+int OIIO_DeepData_samples(const OIIO_DeepData *dd, int64_t pixel);
+void OIIO_DeepData_set_samples(OIIO_DeepData *dd, int64_t pixel, int samps);
+void OIIO_DeepData_insert_samples(OIIO_DeepData *dd, int64_t pixel, int samplepos, int n);
+void OIIO_DeepData_erase_samples(OIIO_DeepData *dd, int64_t pixel, int samplepos, int n);
+float OIIO_DeepData_deep_value(const OIIO_DeepData *dd, int64_t pixel, int channel, int sample);
+uint32_t OIIO_DeepData_deep_value_uint(const OIIO_DeepData *dd, int64_t pixel, int channel,
+                                       int sample);
+void OIIO_DeepData_set_deep_value(OIIO_DeepData *dd, int64_t pixel, int channel, int sample,
+                                  float value);
+void OIIO_DeepData_set_deep_value_uint(OIIO_DeepData *dd, int64_t pixel, int channel, int sample,
+                                       uint32_t value);
+void OIIO_DeepData_sort(OIIO_DeepData *dd, int64_t pixel);
+void OIIO_DeepData_merge_overlaps(OIIO_DeepData *dd, int64_t pixel);
 
 //---------------------------------------------------------------------
 // OIIO_ImageSpec
@@ -166,7 +305,7 @@ bool OIIO_ImageOutput_write_scanline(OIIO_ImageOutput *out, int y, int z, OIIO_T
 void OIIO_ImageSpec_delete(OIIO_ImageSpec *spec);
 
 OIIO_ImageSpec *OIIO_ImageSpec_new(OIIO_TypeDesc fmt);
-OIIO_ImageSpec *OIIO_ImageSpec_clone(OIIO_ImageSpec *from);
+OIIO_ImageSpec *OIIO_ImageSpec_clone(const OIIO_ImageSpec *from);
 OIIO_ImageSpec *OIIO_ImageSpec_new_2d(int xres, int yres, int nchans, bool separateformats,
                                       const OIIO_TypeDesc *channelformats,
                                       const OIIO_StringRef *channelnames);
@@ -240,8 +379,8 @@ void OIIO_ImageSpec_set_z_channel(OIIO_ImageSpec *spec, int val);
 bool OIIO_ImageSpec_deep(const OIIO_ImageSpec *spec);
 void OIIO_ImageSpec_set_deep(OIIO_ImageSpec *spec, bool val);
 
-void OIIO_ImageSpec_attribute_type_data(OIIO_ImageSpec *spec, const char *name, OIIO_TypeDesc type,
-                                        const void *value);
+void OIIO_ImageSpec_attribute_type_data(OIIO_ImageSpec *spec, OIIO_StringRef name,
+                                        OIIO_TypeDesc type, const void *value);
 void OIIO_ImageSpec_attribute_type_char(OIIO_ImageSpec *spec, const char *name, OIIO_TypeDesc type,
                                         const char *value);
 void OIIO_ImageSpec_attribute_uint(OIIO_ImageSpec *spec, const char *name, unsigned int value);
@@ -259,141 +398,249 @@ void OIIO_ImageSpec_erase_attribute(OIIO_ImageSpec *spec, const char *name,
 // find_attribute(const char* name, OIIO_TypeDesc searchtype=OIIO_TypeDesc::UNKNOWN, bool
 // casesensitive=false);
 
-// ImageBuf
+//---------------------------------------------------------------------
+// OIIO_ImageBuf
 //
-/*
-ImageBuf* ImageBuf_New();
-ImageBuf* ImageBuf_New_WithCache(const char* name, ImageCache *imagecache);
-ImageBuf* ImageBuf_New_WithBuffer(const char* name, const OIIO_ImageSpec* spec, void *buffer);
-ImageBuf* ImageBuf_New_SubImage(const char* name, int subimage, int miplevel, ImageCache*
-imagecache); ImageBuf* ImageBuf_New_Spec(const OIIO_ImageSpec* spec);
-
-void ImageBuf_delete(ImageBuf* buf);
-
-void ImageBuf_clear(ImageBuf* buf);
-void ImageBuf_reset_subimage(ImageBuf* buf, const char* name, int subimage, int miplevel, ImageCache
-*imagecache); void ImageBuf_reset_name_cache(ImageBuf* buf, const char* name, ImageCache
-*imagecache); void ImageBuf_reset_spec(ImageBuf* buf, OIIO_ImageSpec* spec); void
-ImageBuf_reset_name_spec(ImageBuf* buf, const char* name, const OIIO_ImageSpec* spec);
-
-IBStorage ImageBuf_storage(ImageBuf* buf);
-bool ImageBuf_initialized(ImageBuf* buf);
-bool ImageBuf_read(ImageBuf* buf, int subimage, int miplevel, bool force, OIIO_TypeDesc convert,
-void *cbk_data); bool ImageBuf_init_spec(ImageBuf* buf, const char* filename, int subimage, int
-miplevel); bool ImageBuf_write_file(ImageBuf* buf, const char* filename, const char* fileformat,
-void *cbk_data); bool ImageBuf_write_output(ImageBuf* buf, OIIO_ImageOutput *out, void *cbk_data);
-void ImageBuf_set_write_format(ImageBuf* buf, OIIO_TypeDesc format);
-void ImageBuf_set_write_tiles(ImageBuf* buf, int width, int height, int depth);
-void ImageBuf_copy_metadata(ImageBuf* dst, const ImageBuf* src);
-bool ImageBuf_copy_pixels(ImageBuf* dst, const ImageBuf* src);
-bool ImageBuf_copy(ImageBuf* dst, const ImageBuf* src);
-void ImageBuf_swap(ImageBuf* buf, ImageBuf* other);
-char* ImageBuf_geterror(ImageBuf* buf);
-const OIIO_ImageSpec* ImageBuf_spec(ImageBuf* buf);
-OIIO_ImageSpec* ImageBuf_specmod(ImageBuf* buf);
-const OIIO_ImageSpec* ImageBuf_nativespec(ImageBuf* buf);
-const char* ImageBuf_name(ImageBuf* buf);
-const char* ImageBuf_file_format_name(ImageBuf* buf);
-int ImageBuf_subimage(ImageBuf* buf);
-int ImageBuf_nsubimages(ImageBuf* buf);
-int ImageBuf_miplevel(ImageBuf* buf);
-int ImageBuf_nmiplevels(ImageBuf* buf);
-int ImageBuf_nchannels(ImageBuf* buf);
-// float ImageBuf_getchannel(ImageBuf* buf, int x, int y, int z, int c, WrapMode wrap);
-// void ImageBuf_getpixel(ImageBuf* buf, int x, int y, float *pixel, int maxchannels);
-// void ImageBuf_getpixel_xyz(ImageBuf* buf, int x, int y, int z, float *pixel, int maxchannels,
-WrapMode wrap);
-// void ImageBuf_interppixel(ImageBuf* buf, float x, float y, float *pixel, WrapMode wrap);
-// void ImageBuf_interppixel_NDC(ImageBuf* buf, float s, float t, float *pixel, WrapMode wrap);
-// void ImageBuf_interppixel_NDC_full(ImageBuf* buf, float s, float t, float *pixel, WrapMode wrap);
-// void ImageBuf_setpixel(ImageBuf* buf, int x, int y, const float *pixel, int maxchannels);
-// void ImageBuf_setpixel_xyz(ImageBuf* buf, int x, int y, int z, const float *pixel, int
-maxchannels);
-// void ImageBuf_setpixel_index(ImageBuf* buf, int i, const float *pixel, int maxchannels);
-bool ImageBuf_get_pixel_channels(ImageBuf* buf, int xbegin, int xend, int ybegin, int yend, int
-zbegin, int zend, int chbegin, int chend, OIIO_TypeDesc format, void *result);
-// bool ImageBuf_get_pixels(ImageBuf* buf, int xbegin, int xend, int ybegin, int yend, int zbegin,
-int zend, OIIO_TypeDesc format, void *result);
-
-int ImageBuf_orientation(ImageBuf* buf);
-int ImageBuf_oriented_width(ImageBuf* buf);
-int ImageBuf_oriented_height(ImageBuf* buf);
-int ImageBuf_oriented_x(ImageBuf* buf);
-int ImageBuf_oriented_y(ImageBuf* buf);
-int ImageBuf_oriented_full_width(ImageBuf* buf);
-int ImageBuf_oriented_full_height(ImageBuf* buf);
-int ImageBuf_oriented_full_x(ImageBuf* buf);
-int ImageBuf_oriented_full_y(ImageBuf* buf);
-
-int ImageBuf_xbegin(ImageBuf* buf);
-int ImageBuf_xend(ImageBuf* buf);
-int ImageBuf_ybegin(ImageBuf* buf);
-int ImageBuf_yend(ImageBuf* buf);
-int ImageBuf_zbegin(ImageBuf* buf);
-int ImageBuf_zend(ImageBuf* buf);
-int ImageBuf_xmin(ImageBuf* buf);
-int ImageBuf_xmax(ImageBuf* buf);
-int ImageBuf_ymin(ImageBuf* buf);
-int ImageBuf_ymax(ImageBuf* buf);
-int ImageBuf_zmin(ImageBuf* buf);
-int ImageBuf_zmax(ImageBuf* buf);
-
-void ImageBuf_set_full(ImageBuf* buf, int xbegin, int xend, int ybegin, int yend, int zbegin, int
-zend);
-// void ImageBuf_set_full_border(ImageBuf* buf, int xbegin, int xend, int ybegin, int yend, int
-zbegin, int zend, const float *bordercolor);
-
-ROI* ImageBuf_roi(ImageBuf* buf);
-ROI* ImageBuf_roi_full(ImageBuf* buf);
-void ImageBuf_set_roi_full(ImageBuf* buf, ROI* newroi);
-
-bool ImageBuf_pixels_valid(ImageBuf* buf);
-OIIO_TypeDesc ImageBuf_pixeltype(ImageBuf* buf);
-// void* ImageBuf_localpixels(ImageBuf* buf);
-// const void* ImageBuf_localpixels(ImageBuf* buf);
-bool ImageBuf_cachedpixels(ImageBuf* buf);
-ImageCache* ImageBuf_imagecache(ImageBuf* buf);
-// void* ImageBuf_pixeladdr(ImageBuf* buf, int x, int y);
-// void* ImageBuf_pixeladdr_z(ImageBuf* buf, int x, int y, int z);
-bool ImageBuf_deep(ImageBuf* buf);
-// int ImageBuf_deep_samples(ImageBuf* buf, int x, int y, int z);
-// const void* ImageBuf_deep_pixel_ptr(ImageBuf* buf, int x, int y, int z, int c);
-// float ImageBuf_deep_value(ImageBuf* buf, int x, int y, int z, int c, int s);
-// DeepData* ImageBuf_deepdata(ImageBuf* buf);
-
-// ROI
+
+OIIO_ImageBuf *OIIO_ImageBuf_new();
+OIIO_ImageBuf *OIIO_ImageBuf_new_file(OIIO_StringRef name, int subimage, int miplevel,
+                                      OIIO_ImageCache *imagecache, const OIIO_ImageSpec *config);
+OIIO_ImageBuf *OIIO_ImageBuf_new_spec(const OIIO_ImageSpec *spec, bool zero);
+void OIIO_ImageBuf_delete(OIIO_ImageBuf *buf);
+
+void OIIO_ImageBuf_clear(OIIO_ImageBuf *buf);
+void OIIO_ImageBuf_reset_file(OIIO_ImageBuf *buf, OIIO_StringRef name, int subimage, int miplevel,
+                              OIIO_ImageCache *imagecache, const OIIO_ImageSpec *config);
+void OIIO_ImageBuf_reset_spec(OIIO_ImageBuf *buf, const OIIO_ImageSpec *spec, bool zero);
+
+OIIO_ImageBuf_IBStorage OIIO_ImageBuf_storage(const OIIO_ImageBuf *buf);
+bool OIIO_ImageBuf_initialized(const OIIO_ImageBuf *buf);
+bool OIIO_ImageBuf_init_spec(OIIO_ImageBuf *buf, OIIO_StringRef filename, int subimage,
+                             int miplevel);
+bool OIIO_ImageBuf_read(OIIO_ImageBuf *buf, int subimage, int miplevel, bool force,
+                        OIIO_TypeDesc convert, ProgressCallback progress_callback,
+                        void *progress_callback_data);
+bool OIIO_ImageBuf_write_file(const OIIO_ImageBuf *buf, OIIO_StringRef filename,
+                              OIIO_TypeDesc dtype, OIIO_StringRef fileformat,
+                              ProgressCallback progress_callback, void *progress_callback_data);
+bool OIIO_ImageBuf_write_output(const OIIO_ImageBuf *buf, OIIO_ImageOutput *out,
+                                ProgressCallback progress_callback, void *progress_callback_data);
+void OIIO_ImageBuf_set_write_format(OIIO_ImageBuf *buf, OIIO_TypeDesc format);
+void OIIO_ImageBuf_set_write_tiles(OIIO_ImageBuf *buf, int width, int height, int depth);
+void OIIO_ImageBuf_copy_metadata(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src);
+bool OIIO_ImageBuf_copy_pixels(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src);
+bool OIIO_ImageBuf_copy(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_TypeDesc format);
+void OIIO_ImageBuf_swap(OIIO_ImageBuf *buf, OIIO_ImageBuf *other);
+bool OIIO_ImageBuf_has_error(const OIIO_ImageBuf *buf);
+const char *OIIO_ImageBuf_geterror(const OIIO_ImageBuf *buf);
+
+const OIIO_ImageSpec *OIIO_ImageBuf_spec(const OIIO_ImageBuf *buf);
+OIIO_ImageSpec *OIIO_ImageBuf_specmod(OIIO_ImageBuf *buf);
+const OIIO_ImageSpec *OIIO_ImageBuf_nativespec(const OIIO_ImageBuf *buf);
+const char *OIIO_ImageBuf_name(const OIIO_ImageBuf *buf);
+const char *OIIO_ImageBuf_file_format_name(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_subimage(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_nsubimages(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_miplevel(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_nmiplevels(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_nchannels(const OIIO_ImageBuf *buf);
+
+int OIIO_ImageBuf_orientation(const OIIO_ImageBuf *buf);
+void OIIO_ImageBuf_set_orientation(OIIO_ImageBuf *buf, int orient);
+int OIIO_ImageBuf_oriented_width(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_height(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_x(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_y(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_full_width(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_full_height(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_full_x(const OIIO_ImageBuf *buf);
+int OIIO_ImageBuf_oriented_full_y(const OIIO_ImageBuf *buf);
+
+void OIIO_ImageBuf_set_origin(OIIO_ImageBuf *buf, int x, int y, int z);
+OIIO_ROI OIIO_ImageBuf_roi(const OIIO_ImageBuf *buf);
+OIIO_ROI OIIO_ImageBuf_roi_full(const OIIO_ImageBuf *buf);
+void OIIO_ImageBuf_set_roi_full(OIIO_ImageBuf *buf, OIIO_ROI newroi);
+
+bool OIIO_ImageBuf_pixels_valid(const OIIO_ImageBuf *buf);
+OIIO_TypeDesc OIIO_ImageBuf_pixeltype(const OIIO_ImageBuf *buf);
+bool OIIO_ImageBuf_cachedpixels(const OIIO_ImageBuf *buf);
+bool OIIO_ImageBuf_deep(const OIIO_ImageBuf *buf);
+bool OIIO_ImageBuf_get_pixels(const OIIO_ImageBuf *buf, OIIO_ROI roi, OIIO_TypeDesc format,
+                              void *result, stride_t xstride, stride_t ystride, stride_t zstride);
+bool OIIO_ImageBuf_set_pixels(OIIO_ImageBuf *buf, OIIO_ROI roi, OIIO_TypeDesc format,
+                              const void *data, stride_t xstride, stride_t ystride,
+                              stride_t zstride);
+
+//---------------------------------------------------------------------
+// OIIO_ImageBufAlgo
 //
-void ROI_delete(ROI* roi);
 
-ROI* ROI_New();
-ROI* ROI_NewOptions(int xbeing, int xend, int ybegin, int yend, int zbegin, int zend, int chbegin,
-int chend); ROI* ROI_Copy(const ROI *roi);
+bool OIIO_ImageBufAlgo_prep(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI *roi,
+                            bool roi_full);
+bool OIIO_ImageBufAlgo_crop(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                            int nthreads);
+bool OIIO_ImageBufAlgo_cut(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                           int nthreads);
+bool OIIO_ImageBufAlgo_paste(OIIO_ImageBuf *dst, int xbegin, int ybegin, int zbegin, int chbegin,
+                             const OIIO_ImageBuf *src, OIIO_ROI srcroi, int nthreads);
+bool OIIO_ImageBufAlgo_flip(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                            int nthreads);
+bool OIIO_ImageBufAlgo_flop(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                            int nthreads);
+bool OIIO_ImageBufAlgo_transpose(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads);
+bool OIIO_ImageBufAlgo_rotate90(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                int nthreads);
+bool OIIO_ImageBufAlgo_rotate180(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads);
+bool OIIO_ImageBufAlgo_rotate270(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads);
+bool OIIO_ImageBufAlgo_reorient(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, int nthreads);
+bool OIIO_ImageBufAlgo_circular_shift(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, int xshift,
+                                      int yshift, int zshift, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_rotate(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, float angle,
+                              OIIO_StringRef filtername, float filterwidth, bool recompute_roi,
+                              OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_rotate_around(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, float angle,
+                                     float center_x, float center_y, OIIO_StringRef filtername,
+                                     float filterwidth, bool recompute_roi, OIIO_ROI roi,
+                                     int nthreads);
+bool OIIO_ImageBufAlgo_add(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_sub(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_mul(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_div(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_absdiff(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                               OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_min(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_max(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_mad(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ImageOrConst B,
+                           OIIO_ImageOrConst C, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_pow(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, const float *b, int nb,
+                           OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_abs(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, OIIO_ROI roi,
+                           int nthreads);
+bool OIIO_ImageBufAlgo_clamp(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, const float *min,
+                             int nmin, const float *max, int nmax, bool clampalpha01,
+                             OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_colorconvert_processor(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                              const OIIO_ColorProcessor *processor,
+                                              bool unpremult, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_colorconvert(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                    OIIO_StringRef fromspace, OIIO_StringRef tospace,
+                                    bool unpremult, OIIO_ColorConfig *colorconfig, OIIO_ROI roi,
+                                    int nthreads);
+bool OIIO_ImageBufAlgo_ociodisplay(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                   OIIO_StringRef display, OIIO_StringRef view,
+                                   OIIO_StringRef fromspace, OIIO_StringRef looks, bool unpremult,
+                                   OIIO_ColorConfig *colorconfig, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_ociolook(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                OIIO_StringRef looks, OIIO_StringRef fromspace,
+                                OIIO_StringRef tospace, bool unpremult, bool inverse,
+                                OIIO_ColorConfig *colorconfig, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_ociofiletransform(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                         OIIO_StringRef name, bool unpremult, bool inverse,
+                                         OIIO_ColorConfig *colorconfig, OIIO_ROI roi,
+                                         int nthreads);
+bool OIIO_ImageBufAlgo_resize(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                              OIIO_StringRef filtername, float filterwidth, OIIO_ROI roi,
+                              int nthreads);
+bool OIIO_ImageBufAlgo_resample(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, bool interpolate,
+                                OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_fit(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                           OIIO_StringRef filtername, float filterwidth, OIIO_StringRef fillmode,
+                           bool exact, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_warp(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, const float *M,
+                            OIIO_StringRef filtername, float filterwidth, bool recompute_roi,
+                            OIIO_ImageBuf_WrapMode wrap, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_over(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, const OIIO_ImageBuf *B,
+                            OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_zover(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A, const OIIO_ImageBuf *B,
+                             bool z_zeroisinf, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_premult(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                               int nthreads);
+bool OIIO_ImageBufAlgo_unpremult(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads);
+bool OIIO_ImageBufAlgo_repremult(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                                 int nthreads);
+bool OIIO_ImageBufAlgo_computePixelStats(const OIIO_ImageBuf *src, int nchannels, float *min,
+                                         float *max, float *avg, float *stddev,
+                                         imagesize_t *nancount, imagesize_t *infcount,
+                                         imagesize_t *finitecount, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_histogram(const OIIO_ImageBuf *src, int channel, imagesize_t *result,
+                                 int bins, float min, float max, bool ignore_outside_range,
+                                 OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_color_range_check(const OIIO_ImageBuf *src, imagesize_t *lowcount,
+                                         imagesize_t *highcount, imagesize_t *inrangecount,
+                                         const float *low, int nlow, const float *high,
+                                         int nhigh, OIIO_ROI roi, int nthreads);
+OIIO_ROI OIIO_ImageBufAlgo_nonzero_region(const OIIO_ImageBuf *src, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_isConstantColor(const OIIO_ImageBuf *src, float threshold, float *color,
+                                       int ncolor, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_isConstantChannel(const OIIO_ImageBuf *src, int channel, float val,
+                                         float threshold, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_isMonochrome(const OIIO_ImageBuf *src, float threshold, OIIO_ROI roi,
+                                    int nthreads);
+const char *OIIO_ImageBufAlgo_computePixelHashSHA1(const OIIO_ImageBuf *src,
+                                                   OIIO_StringRef extrainfo, OIIO_ROI roi,
+                                                   int blocksize, int nthreads);
+bool OIIO_ImageBufAlgo_flatten(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, OIIO_ROI roi,
+                               int nthreads);
+bool OIIO_ImageBufAlgo_deepen(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src, float zvalue,
+                              OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_deep_merge(OIIO_ImageBuf *dst, const OIIO_ImageBuf *A,
+                                  const OIIO_ImageBuf *B, bool occlusion_cull, OIIO_ROI roi,
+                                  int nthreads);
+bool OIIO_ImageBufAlgo_deep_holdout(OIIO_ImageBuf *dst, const OIIO_ImageBuf *src,
+                                    const OIIO_ImageBuf *holdout, OIIO_ROI roi, int nthreads);
+bool OIIO_ImageBufAlgo_compare(const OIIO_ImageBuf *A, const OIIO_ImageBuf *B, float failthresh,
+                               float warnthresh, OIIO_CompareResults *result, OIIO_ROI roi,
+                               int nthreads);
+int OIIO_ImageBufAlgo_compare_Yee(const OIIO_ImageBuf *A, const OIIO_ImageBuf *B,
+                                  OIIO_CompareResults *result, float luminance, float fov,
+                                  OIIO_ROI roi, int nthreads);
 
-bool ROI_defined(ROI* roi);
-int ROI_width(ROI* roi);
-int ROI_height(ROI* roi);
-int ROI_depth(ROI* roi);
-int ROI_nchannels(ROI* roi);
-imagesize_t ROI_npixels(ROI* roi);
+//---------------------------------------------------------------------
+// OIIO_ColorConfig
+//
 
-// Properties
-int ROI_xbegin(const ROI* roi);
-void ROI_set_xbegin(ROI* roi, int val);
-int ROI_xend(const ROI* roi);
-void ROI_set_xend(ROI* roi, int val);
-int ROI_ybegin(const ROI* roi);
-void ROI_set_ybegin(ROI* roi, int val);
-int ROI_yend(const ROI* roi);
-void ROI_set_yend(ROI* roi, int val);
-int ROI_zbegin(const ROI* roi);
-void ROI_set_zbegin(ROI* roi, int val);
-int ROI_zend(const ROI* roi);
-void ROI_set_zend(ROI* roi, int val);
-int ROI_chbegin(const ROI* roi);
-void ROI_set_chbegin(ROI* roi, int val);
-int ROI_chend(const ROI* roi);
-void ROI_set_chend(ROI* roi, int val);
-*/
+OIIO_ColorConfig *OIIO_ColorConfig_new();
+OIIO_ColorConfig *OIIO_ColorConfig_new_file(OIIO_StringRef filename);
+void OIIO_ColorConfig_delete(OIIO_ColorConfig *c);
+bool OIIO_ColorConfig_supportsOpenColorIO();
+bool OIIO_ColorConfig_has_error(const OIIO_ColorConfig *c);
+const char *OIIO_ColorConfig_geterror(OIIO_ColorConfig *c);
+int OIIO_ColorConfig_getNumColorSpaces(const OIIO_ColorConfig *c);
+const char *OIIO_ColorConfig_getColorSpaceNameByIndex(const OIIO_ColorConfig *c, int index);
+const char *OIIO_ColorConfig_getColorSpaceNameByRole(const OIIO_ColorConfig *c,
+                                                     OIIO_StringRef role);
+int OIIO_ColorConfig_getNumRoles(const OIIO_ColorConfig *c);
+const char *OIIO_ColorConfig_getRoleByIndex(const OIIO_ColorConfig *c, int index);
+int OIIO_ColorConfig_getNumLooks(const OIIO_ColorConfig *c);
+const char *OIIO_ColorConfig_getLookNameByIndex(const OIIO_ColorConfig *c, int index);
+int OIIO_ColorConfig_getNumDisplays(const OIIO_ColorConfig *c);
+const char *OIIO_ColorConfig_getDisplayNameByIndex(const OIIO_ColorConfig *c, int index);
+const char *OIIO_ColorConfig_getDefaultDisplayName(const OIIO_ColorConfig *c);
+int OIIO_ColorConfig_getNumViews(const OIIO_ColorConfig *c, OIIO_StringRef display);
+const char *OIIO_ColorConfig_getViewNameByIndex(const OIIO_ColorConfig *c, OIIO_StringRef display,
+                                                int index);
+const char *OIIO_ColorConfig_getDefaultViewName(const OIIO_ColorConfig *c,
+                                                OIIO_StringRef display);
+OIIO_ColorProcessor *OIIO_ColorConfig_createColorProcessor(const OIIO_ColorConfig *c,
+                                                           OIIO_StringRef inputColorSpace,
+                                                           OIIO_StringRef outputColorSpace);
+
+void OIIO_ColorProcessor_delete(OIIO_ColorProcessor *p);
+bool OIIO_ColorProcessor_isNoOp(const OIIO_ColorProcessor *p);
+bool OIIO_ColorProcessor_hasChannelCrosstalk(const OIIO_ColorProcessor *p);
+void OIIO_ColorProcessor_apply(const OIIO_ColorProcessor *p, float *data, int width, int height,
+                               int nchannels, stride_t chanstride, stride_t xstride,
+                               stride_t ystride);
 
 // ImageCache
 //
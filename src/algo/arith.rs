@@ -0,0 +1,389 @@
+//! Per-pixel arithmetic.
+use crate::{
+    algo::{check, into_new},
+    Error, ImageBuf, Roi,
+};
+use openimageio_sys as sys;
+use std::{ptr, slice};
+
+/// Second operand of an arithmetic operation: an image, a single value applied to all channels,
+/// or per-channel values.
+///
+/// If there are fewer per-channel values than channels in the image, the last value is used for
+/// the remaining channels.
+#[derive(Copy, Clone)]
+pub enum Operand<'a> {
+    /// An image, combined pixel by pixel.
+    Image(&'a ImageBuf),
+    /// A constant value for all channels.
+    Value(f32),
+    /// Constant per-channel values.
+    Values(&'a [f32]),
+}
+
+impl<'a> Operand<'a> {
+    /// The returned value borrows from `self`, which must outlive any use of it.
+    fn as_raw(&self) -> sys::OIIO_ImageOrConst {
+        match *self {
+            Operand::Image(img) => sys::OIIO_ImageOrConst {
+                img: img.ptr,
+                values: ptr::null(),
+                nvalues: 0,
+            },
+            Operand::Value(ref value) => sys::OIIO_ImageOrConst {
+                img: ptr::null(),
+                values: value,
+                nvalues: 1,
+            },
+            Operand::Values(values) => sys::OIIO_ImageOrConst {
+                img: ptr::null(),
+                values: values.as_ptr(),
+                nvalues: values.len() as i32,
+            },
+        }
+    }
+
+    /// Returns the constant values of this operand, for the operations that don't accept
+    /// images.
+    fn values(&self) -> Result<&[f32], Error> {
+        match self {
+            Operand::Image(_) => Err(Error::InvalidParameter),
+            Operand::Value(value) => Ok(slice::from_ref(value)),
+            Operand::Values(values) => Ok(values),
+        }
+    }
+}
+
+impl<'a> From<&'a ImageBuf> for Operand<'a> {
+    fn from(img: &'a ImageBuf) -> Operand<'a> {
+        Operand::Image(img)
+    }
+}
+
+impl<'a> From<f32> for Operand<'a> {
+    fn from(value: f32) -> Operand<'a> {
+        Operand::Value(value)
+    }
+}
+
+impl<'a> From<&'a [f32]> for Operand<'a> {
+    fn from(values: &'a [f32]) -> Operand<'a> {
+        Operand::Values(values)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [f32; N]> for Operand<'a> {
+    fn from(values: &'a [f32; N]) -> Operand<'a> {
+        Operand::Values(values)
+    }
+}
+
+/// Computes `a + b` for each pixel and channel.
+pub fn add<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| add_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [add], but writes the result into `dst`.
+pub fn add_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_add(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Computes `a - b` for each pixel and channel.
+pub fn sub<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| sub_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [sub], but writes the result into `dst`.
+pub fn sub_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_sub(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Computes `a * b` for each pixel and channel.
+pub fn mul<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| mul_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [mul], but writes the result into `dst`.
+pub fn mul_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_mul(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Computes `a / b` for each pixel and channel.
+///
+/// Division by zero yields zero.
+pub fn div<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| div_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [div], but writes the result into `dst`.
+pub fn div_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_div(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Computes `abs(a - b)` for each pixel and channel.
+pub fn absdiff<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| absdiff_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [absdiff], but writes the result into `dst`.
+pub fn absdiff_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_absdiff(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32)
+    };
+    check(dst, success)
+}
+
+/// Computes `min(a, b)` for each pixel and channel.
+pub fn min<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| min_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [min], but writes the result into `dst`.
+pub fn min_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_min(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Computes `max(a, b)` for each pixel and channel.
+pub fn max<'b>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    into_new(|dst| max_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [max], but writes the result into `dst`.
+pub fn max_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_max(dst.ptr, a.ptr, b.as_raw(), roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Computes `a * b + c` for each pixel and channel.
+pub fn mad<'b, 'c>(
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    c: impl Into<Operand<'c>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let b = b.into();
+    let c = c.into();
+    into_new(|dst| mad_into(dst, a, b, c, roi, nthreads))
+}
+
+/// Same as [mad], but writes the result into `dst`.
+pub fn mad_into<'b, 'c>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: impl Into<Operand<'b>>,
+    c: impl Into<Operand<'c>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let b = b.into();
+    let c = c.into();
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_mad(
+            dst.ptr,
+            a.ptr,
+            b.as_raw(),
+            c.as_raw(),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Raises each pixel value of `a` to the power `exponent`, a single value or per-channel
+/// values.
+///
+/// Returns [Error::InvalidParameter] if `exponent` is an image.
+pub fn pow<'b>(
+    a: &ImageBuf,
+    exponent: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let exponent = exponent.into();
+    into_new(|dst| pow_into(dst, a, exponent, roi, nthreads))
+}
+
+/// Same as [pow], but writes the result into `dst`.
+pub fn pow_into<'b>(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    exponent: impl Into<Operand<'b>>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let exponent = exponent.into();
+    let exponent = exponent.values()?;
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_pow(
+            dst.ptr,
+            a.ptr,
+            exponent.as_ptr(),
+            exponent.len() as i32,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Computes the absolute value of each pixel value of `a`.
+pub fn abs(a: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| abs_into(dst, a, roi, nthreads))
+}
+
+/// Same as [abs], but writes the result into `dst`.
+pub fn abs_into(dst: &mut ImageBuf, a: &ImageBuf, roi: Roi, nthreads: usize) -> Result<(), Error> {
+    let success = unsafe { sys::OIIO_ImageBufAlgo_abs(dst.ptr, a.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Clamps each pixel value of `src` to the range `min..=max`, whose bounds are single values
+/// or per-channel values.
+///
+/// Use `f32::NEG_INFINITY` or `f32::INFINITY` to leave values unbounded. If `clamp_alpha01` is
+/// true, the alpha channel is additionally clamped to `0.0..=1.0`. Returns
+/// [Error::InvalidParameter] if a bound is an image.
+pub fn clamp<'b, 'c>(
+    src: &ImageBuf,
+    min: impl Into<Operand<'b>>,
+    max: impl Into<Operand<'c>>,
+    clamp_alpha01: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    let min = min.into();
+    let max = max.into();
+    into_new(|dst| clamp_into(dst, src, min, max, clamp_alpha01, roi, nthreads))
+}
+
+/// Same as [clamp], but writes the result into `dst`.
+pub fn clamp_into<'b, 'c>(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    min: impl Into<Operand<'b>>,
+    max: impl Into<Operand<'c>>,
+    clamp_alpha01: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let (min, max) = (min.into(), max.into());
+    let (min, max) = (min.values()?, max.values()?);
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_clamp(
+            dst.ptr,
+            src.ptr,
+            min.as_ptr(),
+            min.len() as i32,
+            max.as_ptr(),
+            max.len() as i32,
+            clamp_alpha01,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
@@ -0,0 +1,289 @@
+//! Color space conversions.
+//!
+//! The functions in this module take an optional [ColorConfig]; if `None` is given, the default
+//! configuration (specified by the `OCIO` environment variable) is used.
+//!
+//! The source color space can be omitted, in which case it is taken from the `oiio:ColorSpace`
+//! attribute of the source image (or assumed to be linear if there is no such attribute).
+//!
+//! If `unpremult` is true, color values are divided by alpha before the conversion and
+//! multiplied by alpha again afterwards.
+use crate::{
+    algo::{check, in_strips, into_new},
+    color::{ColorConfig, ColorProcessor},
+    Error, ImageBuf, Roi,
+};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+use std::{ops::ControlFlow, ptr};
+
+fn config_ptr(config: Option<&ColorConfig>) -> *mut sys::OIIO_ColorConfig {
+    config.map_or(ptr::null_mut(), |config| config.ptr)
+}
+
+/// Converts `src` from the color space `from` to the color space `to`.
+pub fn colorconvert(
+    src: &ImageBuf,
+    from: Option<&str>,
+    to: &str,
+    unpremult: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| colorconvert_into(dst, src, from, to, unpremult, config, roi, nthreads))
+}
+
+/// Same as [colorconvert], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn colorconvert_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    from: Option<&str>,
+    to: &str,
+    unpremult: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_colorconvert(
+            dst.ptr,
+            src.ptr,
+            from.unwrap_or("").as_stringref(),
+            to.as_stringref(),
+            unpremult,
+            config_ptr(config),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Same as [colorconvert], but reports progress.
+///
+/// The image is converted in horizontal strips; `progress` is called after each strip with the
+/// portion of the image that is done, between 0 and 1. If it returns [ControlFlow::Break], the
+/// operation stops and [Error::Cancelled] is returned.
+#[allow(clippy::too_many_arguments)]
+pub fn colorconvert_with_progress(
+    src: &ImageBuf,
+    from: Option<&str>,
+    to: &str,
+    unpremult: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+    progress: impl FnMut(f32) -> ControlFlow<()>,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        colorconvert_into_with_progress(
+            dst, src, from, to, unpremult, config, roi, nthreads, progress,
+        )
+    })
+}
+
+/// Same as [colorconvert_with_progress], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn colorconvert_into_with_progress(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    from: Option<&str>,
+    to: &str,
+    unpremult: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+    mut progress: impl FnMut(f32) -> ControlFlow<()>,
+) -> Result<(), Error> {
+    in_strips(dst, src, roi, false, &mut progress, |dst, strip| {
+        colorconvert_into(dst, src, from, to, unpremult, config, strip, nthreads)
+    })
+}
+
+/// Applies the color transformation of `processor` to `src`.
+pub fn colorconvert_processor(
+    src: &ImageBuf,
+    processor: &ColorProcessor,
+    unpremult: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| colorconvert_processor_into(dst, src, processor, unpremult, roi, nthreads))
+}
+
+/// Same as [colorconvert_processor], but writes the result into `dst`.
+pub fn colorconvert_processor_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    processor: &ColorProcessor,
+    unpremult: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_colorconvert_processor(
+            dst.ptr,
+            src.ptr,
+            processor.ptr,
+            unpremult,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Converts `src` from the color space `from` to the color space of the specified display and
+/// view, applying `looks` (a comma-separated list of look names, or an empty string) on the way.
+///
+/// An empty `view` selects the default view of the display.
+#[allow(clippy::too_many_arguments)]
+pub fn ociodisplay(
+    src: &ImageBuf,
+    display: &str,
+    view: &str,
+    from: Option<&str>,
+    looks: &str,
+    unpremult: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        ociodisplay_into(
+            dst, src, display, view, from, looks, unpremult, config, roi, nthreads,
+        )
+    })
+}
+
+/// Same as [ociodisplay], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn ociodisplay_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    display: &str,
+    view: &str,
+    from: Option<&str>,
+    looks: &str,
+    unpremult: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_ociodisplay(
+            dst.ptr,
+            src.ptr,
+            display.as_stringref(),
+            view.as_stringref(),
+            from.unwrap_or("").as_stringref(),
+            looks.as_stringref(),
+            unpremult,
+            config_ptr(config),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Applies `looks` (a comma-separated list of look names) to `src`, converting it from the color
+/// space `from` to the color space `to`.
+///
+/// If `inverse` is true, the inverse of the looks is applied.
+#[allow(clippy::too_many_arguments)]
+pub fn ociolook(
+    src: &ImageBuf,
+    looks: &str,
+    from: Option<&str>,
+    to: &str,
+    unpremult: bool,
+    inverse: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        ociolook_into(
+            dst, src, looks, from, to, unpremult, inverse, config, roi, nthreads,
+        )
+    })
+}
+
+/// Same as [ociolook], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn ociolook_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    looks: &str,
+    from: Option<&str>,
+    to: &str,
+    unpremult: bool,
+    inverse: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_ociolook(
+            dst.ptr,
+            src.ptr,
+            looks.as_stringref(),
+            from.unwrap_or("").as_stringref(),
+            to.as_stringref(),
+            unpremult,
+            inverse,
+            config_ptr(config),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Applies the transformation stored in the file `name` (e.g. a LUT file), searched for in the
+/// search path of the configuration.
+///
+/// If `inverse` is true, the inverse transformation is applied.
+pub fn ociofiletransform(
+    src: &ImageBuf,
+    name: &str,
+    unpremult: bool,
+    inverse: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        ociofiletransform_into(dst, src, name, unpremult, inverse, config, roi, nthreads)
+    })
+}
+
+/// Same as [ociofiletransform], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn ociofiletransform_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    name: &str,
+    unpremult: bool,
+    inverse: bool,
+    config: Option<&ColorConfig>,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_ociofiletransform(
+            dst.ptr,
+            src.ptr,
+            name.as_stringref(),
+            unpremult,
+            inverse,
+            config_ptr(config),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
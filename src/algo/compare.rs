@@ -0,0 +1,117 @@
+//! Image comparison.
+use crate::{Error, ImageBuf, Roi};
+use openimageio_sys as sys;
+use std::mem::MaybeUninit;
+
+/// Result of an image comparison, returned by [compare] and [compare_yee].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CompareResults {
+    /// Mean absolute difference over all channels of all pixels.
+    pub mean_error: f64,
+    /// Root mean square difference.
+    pub rms_error: f64,
+    /// Peak signal-to-noise ratio, in decibels.
+    pub psnr: f64,
+    /// Largest absolute difference.
+    pub max_error: f64,
+    /// Coordinates (x, y, z) of the pixel with the largest difference.
+    pub max_pixel: (i32, i32, i32),
+    /// Channel with the largest difference.
+    pub max_channel: usize,
+    /// Number of pixels whose difference exceeds the warning threshold.
+    pub warn_count: u64,
+    /// Number of pixels whose difference exceeds the failure threshold.
+    pub fail_count: u64,
+}
+
+impl CompareResults {
+    /// Returns whether no pixel exceeded the failure threshold.
+    pub fn passed(&self) -> bool {
+        self.fail_count == 0
+    }
+
+    fn from_raw(raw: &sys::OIIO_CompareResults) -> CompareResults {
+        CompareResults {
+            mean_error: raw.meanerror,
+            rms_error: raw.rms_error,
+            psnr: raw.PSNR,
+            max_error: raw.maxerror,
+            max_pixel: (raw.maxx, raw.maxy, raw.maxz),
+            max_channel: raw.maxc as usize,
+            warn_count: raw.nwarn,
+            fail_count: raw.nfail,
+        }
+    }
+}
+
+/// Fetches the error message of a failed comparison from either of the compared images.
+fn compare_error(a: &ImageBuf, b: &ImageBuf) -> Error {
+    if a.has_error() {
+        Error::AlgorithmError(a.get_last_error())
+    } else {
+        Error::AlgorithmError(b.get_last_error())
+    }
+}
+
+/// Compares the pixels of `a` and `b` within `roi`.
+///
+/// Pixels that differ by more than `warn_threshold` (resp. `fail_threshold`) in any channel are
+/// counted in [CompareResults::warn_count] (resp. [CompareResults::fail_count]).
+pub fn compare(
+    a: &ImageBuf,
+    b: &ImageBuf,
+    fail_threshold: f32,
+    warn_threshold: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<CompareResults, Error> {
+    let mut raw = MaybeUninit::uninit();
+    let raw = unsafe {
+        if !sys::OIIO_ImageBufAlgo_compare(
+            a.ptr,
+            b.ptr,
+            fail_threshold,
+            warn_threshold,
+            raw.as_mut_ptr(),
+            roi.0,
+            nthreads as i32,
+        ) {
+            return Err(compare_error(a, b));
+        }
+        raw.assume_init()
+    };
+    Ok(CompareResults::from_raw(&raw))
+}
+
+/// Compares `a` and `b` with the perceptual metric of Yee (OpenImageIO's `compare_Yee`).
+///
+/// `luminance` is the adaptation luminance of the viewer in candelas per square meter, and
+/// `fov` the field of view in degrees (OpenImageIO uses 100 and 45 by default). Pixels with a
+/// perceptible difference are counted in [CompareResults::fail_count]; the other error
+/// statistics are not computed.
+pub fn compare_yee(
+    a: &ImageBuf,
+    b: &ImageBuf,
+    luminance: f32,
+    fov: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<CompareResults, Error> {
+    let mut raw = MaybeUninit::uninit();
+    let raw: sys::OIIO_CompareResults = unsafe {
+        sys::OIIO_ImageBufAlgo_compare_Yee(
+            a.ptr,
+            b.ptr,
+            raw.as_mut_ptr(),
+            luminance,
+            fov,
+            roi.0,
+            nthreads as i32,
+        );
+        raw.assume_init()
+    };
+    if raw.error {
+        return Err(compare_error(a, b));
+    }
+    Ok(CompareResults::from_raw(&raw))
+}
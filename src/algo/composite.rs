@@ -0,0 +1,142 @@
+//! Alpha compositing.
+use crate::{
+    algo::{check, into_new},
+    Error, ImageBuf, Roi,
+};
+use openimageio_sys as sys;
+
+/// Composites `a` over `b` with the Porter-Duff "over" operator.
+///
+/// Both images must have an alpha channel and the same number of channels, and their colors
+/// must be premultiplied by alpha.
+pub fn over(a: &ImageBuf, b: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| over_into(dst, a, b, roi, nthreads))
+}
+
+/// Same as [over], but writes the result into `dst`.
+pub fn over_into(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_over(dst.ptr, a.ptr, b.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Composites `a` and `b` according to their depth: at each pixel, the image with the smallest
+/// depth is composited over the other one.
+///
+/// Both images must have a depth channel (the `z_channel` of their spec). If `z_zero_is_inf` is
+/// true, a depth of zero is treated as infinitely far away.
+pub fn zover(
+    a: &ImageBuf,
+    b: &ImageBuf,
+    z_zero_is_inf: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| zover_into(dst, a, b, z_zero_is_inf, roi, nthreads))
+}
+
+/// Same as [zover], but writes the result into `dst`.
+pub fn zover_into(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: &ImageBuf,
+    z_zero_is_inf: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_zover(dst.ptr, a.ptr, b.ptr, z_zero_is_inf, roi.0, nthreads as i32)
+    };
+    check(dst, success)
+}
+
+/// Multiplies the color channels of `src` by its alpha channel.
+///
+/// Images without an alpha channel are copied unchanged.
+pub fn premult(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| premult_into(dst, src, roi, nthreads))
+}
+
+/// Same as [premult], but writes the result into `dst`.
+pub fn premult_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_premult(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Divides the color channels of `src` by its alpha channel, leaving pixels with zero alpha
+/// unchanged.
+///
+/// Images without an alpha channel are copied unchanged.
+pub fn unpremult(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| unpremult_into(dst, src, roi, nthreads))
+}
+
+/// Same as [unpremult], but writes the result into `dst`.
+pub fn unpremult_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_unpremult(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Multiplies the color channels of `src` by its alpha channel, like [premult], except that
+/// pixels with zero alpha are left unchanged. This is the inverse of [unpremult].
+pub fn repremult(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| repremult_into(dst, src, roi, nthreads))
+}
+
+/// Same as [repremult], but writes the result into `dst`.
+pub fn repremult_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_repremult(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Composites a stack of `layers` over `background`, in order: the first layer is
+/// composited over the background, the second layer over the result, and so on.
+///
+/// All images must have an alpha channel (named `"A"`), otherwise
+/// [Error::AlphaChannelNotFound] is returned.
+pub fn comp(
+    background: &ImageBuf,
+    layers: &[&ImageBuf],
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    for img in Some(&background).into_iter().chain(layers) {
+        img.spec()
+            .alpha_channel()
+            .map_err(|_| Error::AlphaChannelNotFound)?;
+    }
+
+    let (first, rest) = match layers.split_first() {
+        Some(split) => split,
+        None => return into_new(|dst| dst.copy_from(background)),
+    };
+    let mut result = over(first, background, roi, nthreads)?;
+    for layer in rest {
+        result = over(layer, &result, roi, nthreads)?;
+    }
+    Ok(result)
+}
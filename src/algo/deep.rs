@@ -0,0 +1,108 @@
+//! Deep image operations.
+use crate::{
+    algo::{check, into_new},
+    Error, ImageBuf, Roi,
+};
+use openimageio_sys as sys;
+
+/// Converts a deep image into a flat image by compositing the samples of each pixel front to
+/// back.
+///
+/// `src` must have a depth channel ("Z") and an alpha channel.
+pub fn flatten(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| flatten_into(dst, src, roi, nthreads))
+}
+
+/// Same as [flatten], but writes the result into `dst`.
+pub fn flatten_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_flatten(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Converts a flat image into a deep image with one sample per pixel.
+///
+/// Pixels whose channels are all zero get no sample. If `src` has no depth channel, samples
+/// are placed at depth `z`.
+pub fn deepen(src: &ImageBuf, z: f32, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| deepen_into(dst, src, z, roi, nthreads))
+}
+
+/// Same as [deepen], but writes the result into `dst`.
+pub fn deepen_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    z: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_deepen(dst.ptr, src.ptr, z, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Merges the samples of two deep images with the same channels.
+///
+/// If `occlusion_cull` is true, samples behind the first fully opaque sample of each pixel are
+/// discarded.
+pub fn deep_merge(
+    a: &ImageBuf,
+    b: &ImageBuf,
+    occlusion_cull: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| deep_merge_into(dst, a, b, occlusion_cull, roi, nthreads))
+}
+
+/// Same as [deep_merge], but writes the result into `dst`.
+pub fn deep_merge_into(
+    dst: &mut ImageBuf,
+    a: &ImageBuf,
+    b: &ImageBuf,
+    occlusion_cull: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_deep_merge(
+            dst.ptr,
+            a.ptr,
+            b.ptr,
+            occlusion_cull,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Removes the samples of the deep image `src` that are behind the first opaque sample of the
+/// deep image `holdout`.
+pub fn deep_holdout(
+    src: &ImageBuf,
+    holdout: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| deep_holdout_into(dst, src, holdout, roi, nthreads))
+}
+
+/// Same as [deep_holdout], but writes the result into `dst`.
+pub fn deep_holdout_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    holdout: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_deep_holdout(dst.ptr, src.ptr, holdout.ptr, roi.0, nthreads as i32)
+    };
+    check(dst, success)
+}
@@ -0,0 +1,82 @@
+//! Reconstruction filters.
+
+/// A 2D reconstruction filter, used by operations that resample images.
+///
+/// Operations taking an `Option<Filter2D>` select a filter appropriate to the operation when
+/// given `None`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Filter2D {
+    Box,
+    Triangle,
+    Gaussian,
+    SharpGaussian,
+    CatmullRom,
+    BlackmanHarris,
+    Sinc,
+    Lanczos3,
+    RadialLanczos3,
+    NukeLanczos6,
+    Mitchell,
+    BSpline,
+    Disk,
+    Cubic,
+    Keys,
+    Simon,
+    Rifman,
+}
+
+impl Filter2D {
+    /// All filters.
+    pub const ALL: [Filter2D; 17] = [
+        Filter2D::Box,
+        Filter2D::Triangle,
+        Filter2D::Gaussian,
+        Filter2D::SharpGaussian,
+        Filter2D::CatmullRom,
+        Filter2D::BlackmanHarris,
+        Filter2D::Sinc,
+        Filter2D::Lanczos3,
+        Filter2D::RadialLanczos3,
+        Filter2D::NukeLanczos6,
+        Filter2D::Mitchell,
+        Filter2D::BSpline,
+        Filter2D::Disk,
+        Filter2D::Cubic,
+        Filter2D::Keys,
+        Filter2D::Simon,
+        Filter2D::Rifman,
+    ];
+
+    /// Returns the OpenImageIO name of this filter.
+    pub fn name(self) -> &'static str {
+        match self {
+            Filter2D::Box => "box",
+            Filter2D::Triangle => "triangle",
+            Filter2D::Gaussian => "gaussian",
+            Filter2D::SharpGaussian => "sharp-gaussian",
+            Filter2D::CatmullRom => "catmull-rom",
+            Filter2D::BlackmanHarris => "blackman-harris",
+            Filter2D::Sinc => "sinc",
+            Filter2D::Lanczos3 => "lanczos3",
+            Filter2D::RadialLanczos3 => "radial-lanczos3",
+            Filter2D::NukeLanczos6 => "nuke-lanczos6",
+            Filter2D::Mitchell => "mitchell",
+            Filter2D::BSpline => "bspline",
+            Filter2D::Disk => "disk",
+            Filter2D::Cubic => "cubic",
+            Filter2D::Keys => "keys",
+            Filter2D::Simon => "simon",
+            Filter2D::Rifman => "rifman",
+        }
+    }
+
+    /// Returns the filter with the specified OpenImageIO name.
+    pub fn from_name(name: &str) -> Option<Filter2D> {
+        Filter2D::ALL.iter().copied().find(|f| f.name() == name)
+    }
+}
+
+/// Returns the name passed to OpenImageIO for an optional filter (empty for the default).
+pub(crate) fn filter_name(filter: Option<Filter2D>) -> &'static str {
+    filter.map_or("", Filter2D::name)
+}
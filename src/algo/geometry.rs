@@ -0,0 +1,326 @@
+//! Geometric transformations: cropping, pasting, flipping and rotating images.
+use crate::{
+    algo::{check, filter::filter_name, into_new, Filter2D},
+    Error, ImageBuf, Roi,
+};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+
+/// Returns the pixels of `src` within `roi`, keeping their original coordinates.
+///
+/// The data window of the result is `roi`; pixels of `roi` outside of the data window of `src`
+/// are black.
+pub fn crop(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| crop_into(dst, src, roi, nthreads))
+}
+
+/// Same as [crop], but writes the result into `dst`.
+pub fn crop_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe { sys::OIIO_ImageBufAlgo_crop(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Returns the pixels of `src` within `roi`, moved so that the data window of the result
+/// starts at the origin.
+pub fn cut(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| cut_into(dst, src, roi, nthreads))
+}
+
+/// Same as [cut], but writes the result into `dst`.
+pub fn cut_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe { sys::OIIO_ImageBufAlgo_cut(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Flips `src` vertically (top to bottom).
+pub fn flip(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| flip_into(dst, src, roi, nthreads))
+}
+
+/// Same as [flip], but writes the result into `dst`.
+pub fn flip_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe { sys::OIIO_ImageBufAlgo_flip(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Flips `src` horizontally (left to right).
+pub fn flop(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| flop_into(dst, src, roi, nthreads))
+}
+
+/// Same as [flop], but writes the result into `dst`.
+pub fn flop_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe { sys::OIIO_ImageBufAlgo_flop(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Transposes `src` (swaps the x and y axes).
+pub fn transpose(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| transpose_into(dst, src, roi, nthreads))
+}
+
+/// Same as [transpose], but writes the result into `dst`.
+pub fn transpose_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_transpose(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Rotates `src` by 90 degrees clockwise.
+pub fn rotate90(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| rotate90_into(dst, src, roi, nthreads))
+}
+
+/// Same as [rotate90], but writes the result into `dst`.
+pub fn rotate90_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_rotate90(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Rotates `src` by 180 degrees.
+///
+/// This is equivalent to flipping the image both vertically and horizontally.
+pub fn rotate180(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| rotate180_into(dst, src, roi, nthreads))
+}
+
+/// Same as [rotate180], but writes the result into `dst`.
+pub fn rotate180_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_rotate180(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Rotates `src` by 270 degrees clockwise (90 degrees counter-clockwise).
+pub fn rotate270(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| rotate270_into(dst, src, roi, nthreads))
+}
+
+/// Same as [rotate270], but writes the result into `dst`.
+pub fn rotate270_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success =
+        unsafe { sys::OIIO_ImageBufAlgo_rotate270(dst.ptr, src.ptr, roi.0, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Copies the pixels of `src` within `src_roi` into `dst`, with the upper-left corner at
+/// `origin` (x, y, z) and the first channel at `chbegin`.
+///
+/// `dst` is resized if necessary to contain the pasted region.
+pub fn paste(
+    dst: &mut ImageBuf,
+    origin: (i32, i32, i32),
+    chbegin: usize,
+    src: &ImageBuf,
+    src_roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let (x, y, z) = origin;
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_paste(
+            dst.ptr,
+            x,
+            y,
+            z,
+            chbegin as i32,
+            src.ptr,
+            src_roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Rotates and/or flips `src` so that it is displayed upright, according to its `Orientation`
+/// metadata (as found in EXIF data). The result has an orientation of 1.
+pub fn reorient(src: &ImageBuf, nthreads: usize) -> Result<ImageBuf, Error> {
+    into_new(|dst| reorient_into(dst, src, nthreads))
+}
+
+/// Same as [reorient], but writes the result into `dst`.
+pub fn reorient_into(dst: &mut ImageBuf, src: &ImageBuf, nthreads: usize) -> Result<(), Error> {
+    let success = unsafe { sys::OIIO_ImageBufAlgo_reorient(dst.ptr, src.ptr, nthreads as i32) };
+    check(dst, success)
+}
+
+/// Shifts the pixels of `src` by `shift` (x, y, z) pixels, wrapping around the edges of the image.
+pub fn circular_shift(
+    src: &ImageBuf,
+    shift: (i32, i32, i32),
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| circular_shift_into(dst, src, shift, roi, nthreads))
+}
+
+/// Same as [circular_shift], but writes the result into `dst`.
+pub fn circular_shift_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    shift: (i32, i32, i32),
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let (x, y, z) = shift;
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_circular_shift(dst.ptr, src.ptr, x, y, z, roi.0, nthreads as i32)
+    };
+    check(dst, success)
+}
+
+/// Rotates `src` by `angle` radians clockwise around the center of its display window.
+///
+/// `filter` is the filter used for resampling, or `None` for a reasonable default. If
+/// `filter_width` is `0.0`, the default width of the filter is used. If `recompute_roi` is true,
+/// the data window of the result is enlarged to contain the whole rotated image; otherwise it is
+/// the same as the data window of `src`.
+pub fn rotate(
+    src: &ImageBuf,
+    angle: f32,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    recompute_roi: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        rotate_into(
+            dst,
+            src,
+            angle,
+            filter,
+            filter_width,
+            recompute_roi,
+            roi,
+            nthreads,
+        )
+    })
+}
+
+/// Same as [rotate], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    angle: f32,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    recompute_roi: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_rotate(
+            dst.ptr,
+            src.ptr,
+            angle,
+            filter_name(filter).as_stringref(),
+            filter_width,
+            recompute_roi,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Rotates `src` by `angle` radians clockwise around the point `center` (x, y).
+///
+/// See [rotate] for the meaning of the other parameters.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate_around(
+    src: &ImageBuf,
+    angle: f32,
+    center: (f32, f32),
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    recompute_roi: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        rotate_around_into(
+            dst,
+            src,
+            angle,
+            center,
+            filter,
+            filter_width,
+            recompute_roi,
+            roi,
+            nthreads,
+        )
+    })
+}
+
+/// Same as [rotate_around], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn rotate_around_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    angle: f32,
+    center: (f32, f32),
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    recompute_roi: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let (center_x, center_y) = center;
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_rotate_around(
+            dst.ptr,
+            src.ptr,
+            angle,
+            center_x,
+            center_y,
+            filter_name(filter).as_stringref(),
+            filter_width,
+            recompute_roi,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
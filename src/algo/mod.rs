@@ -0,0 +1,96 @@
+//! Image processing operations on [ImageBuf](crate::ImageBuf) (OpenImageIO's `ImageBufAlgo`).
+//!
+//! Most operations come in two forms: `op` returns a new image, and `op_into` writes the result
+//! into an existing destination image. If the destination image is uninitialized, it is
+//! allocated with the appropriate size and format by the operation.
+//!
+//! The `roi` parameter restricts the operation to a region of the image; pass [Roi::ALL] to
+//! process the whole image. The `nthreads` parameter is the maximum number of threads to use;
+//! `0` means the global OpenImageIO default.
+//!
+//! [Roi::ALL]: crate::Roi::ALL
+use crate::{Error, ImageBuf, Roi};
+use openimageio_sys as sys;
+use std::ops::ControlFlow;
+
+mod arith;
+mod color;
+mod compare;
+mod composite;
+mod deep;
+mod filter;
+mod geometry;
+mod resize;
+mod stats;
+
+pub use self::arith::*;
+pub use self::color::*;
+pub use self::compare::*;
+pub use self::composite::*;
+pub use self::deep::*;
+pub use self::filter::Filter2D;
+pub use self::geometry::*;
+pub use self::resize::*;
+pub use self::stats::*;
+
+/// Converts the result of an `ImageBufAlgo` function into a `Result`, fetching the error
+/// message from the destination image on failure.
+fn check(dst: &ImageBuf, success: bool) -> Result<(), Error> {
+    if success {
+        Ok(())
+    } else {
+        Err(Error::AlgorithmError(dst.get_last_error()))
+    }
+}
+
+/// Runs an `_into` operation on a fresh destination image and returns it.
+fn into_new(f: impl FnOnce(&mut ImageBuf) -> Result<(), Error>) -> Result<ImageBuf, Error> {
+    let mut dst = ImageBuf::new();
+    f(&mut dst)?;
+    Ok(dst)
+}
+
+/// Number of strips processed by the `_with_progress` operations.
+const PROGRESS_STRIPS: i32 = 64;
+
+/// Runs an operation on successive horizontal strips of `roi`, calling `progress` after each
+/// strip.
+///
+/// `ImageBufAlgo` functions do not report progress, so `dst` is allocated up front (as the
+/// operation itself would do) and filled one strip at a time; the operation can only be
+/// cancelled between strips. `roi_full` must be true for operations that map the full window
+/// of `src` to `roi` (like [resize]).
+fn in_strips(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    roi: Roi,
+    roi_full: bool,
+    progress: &mut dyn FnMut(f32) -> ControlFlow<()>,
+    mut op: impl FnMut(&mut ImageBuf, Roi) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut roi = roi.0;
+    let success = unsafe { sys::OIIO_ImageBufAlgo_prep(dst.ptr, src.ptr, &mut roi, roi_full) };
+    check(dst, success)?;
+    if progress(0.0).is_break() {
+        return Err(Error::Cancelled);
+    }
+    let height = roi.yend - roi.ybegin;
+    let rows = ((height + PROGRESS_STRIPS - 1) / PROGRESS_STRIPS).max(1);
+    let mut y = roi.ybegin;
+    while y < roi.yend {
+        let yend = (y + rows).min(roi.yend);
+        op(
+            dst,
+            Roi(sys::OIIO_ROI {
+                ybegin: y,
+                yend,
+                ..roi
+            }),
+        )?;
+        y = yend;
+        if progress((y - roi.ybegin) as f32 / height as f32).is_break() {
+            return Err(Error::Cancelled);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! Resizing and warping images.
+use crate::{
+    algo::{check, filter::filter_name, in_strips, into_new, Filter2D},
+    Error, ImageBuf, Roi, WrapMode,
+};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+use std::ops::ControlFlow;
+
+/// How [fit] handles a source image whose aspect ratio differs from the target region.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FitMode {
+    /// Fits the whole image in the target region, padding with black on the sides or on the top
+    /// and bottom.
+    Letterbox,
+    /// Fills the width of the target region, cropping or padding vertically.
+    Width,
+    /// Fills the height of the target region, cropping or padding horizontally.
+    Height,
+}
+
+impl FitMode {
+    fn name(self) -> &'static str {
+        match self {
+            FitMode::Letterbox => "letterbox",
+            FitMode::Width => "width",
+            FitMode::Height => "height",
+        }
+    }
+}
+
+/// Resizes `src` so that its full (display) window maps to `roi`, using the specified filter.
+///
+/// `roi` is the data window of the result. If `filter_width` is `0.0`, the default width of the
+/// filter is used.
+pub fn resize(
+    src: &ImageBuf,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| resize_into(dst, src, filter, filter_width, roi, nthreads))
+}
+
+/// Same as [resize], but writes the result into `dst`.
+///
+/// If `dst` is already initialized, `roi` can be [Roi::ALL] to resize to the data window of `dst`.
+pub fn resize_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_resize(
+            dst.ptr,
+            src.ptr,
+            filter_name(filter).as_stringref(),
+            filter_width,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Same as [resize], but reports progress.
+///
+/// The image is resized in horizontal strips; `progress` is called after each strip with the
+/// portion of the image that is done, between 0 and 1. If it returns [ControlFlow::Break], the
+/// operation stops and [Error::Cancelled] is returned.
+pub fn resize_with_progress(
+    src: &ImageBuf,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    roi: Roi,
+    nthreads: usize,
+    progress: impl FnMut(f32) -> ControlFlow<()>,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        resize_into_with_progress(dst, src, filter, filter_width, roi, nthreads, progress)
+    })
+}
+
+/// Same as [resize_with_progress], but writes the result into `dst`.
+pub fn resize_into_with_progress(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    roi: Roi,
+    nthreads: usize,
+    mut progress: impl FnMut(f32) -> ControlFlow<()>,
+) -> Result<(), Error> {
+    in_strips(dst, src, roi, true, &mut progress, |dst, strip| {
+        resize_into(dst, src, filter, filter_width, strip, nthreads)
+    })
+}
+
+/// Resizes `src` so that its full (display) window maps to `roi`, without filtering.
+///
+/// Each result pixel is the source pixel closest to its center, or a bilinear interpolation of
+/// the closest source pixels if `interpolate` is true. This is faster but of lower quality than
+/// [resize].
+pub fn resample(
+    src: &ImageBuf,
+    interpolate: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| resample_into(dst, src, interpolate, roi, nthreads))
+}
+
+/// Same as [resample], but writes the result into `dst`.
+pub fn resample_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    interpolate: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_resample(dst.ptr, src.ptr, interpolate, roi.0, nthreads as i32)
+    };
+    check(dst, success)
+}
+
+/// Resizes `src` to fit in `roi` while preserving its aspect ratio, according to `mode`.
+///
+/// If `exact` is true, the image is positioned with sub-pixel accuracy, which may slightly blur
+/// it; otherwise it is aligned on whole pixels.
+pub fn fit(
+    src: &ImageBuf,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    mode: FitMode,
+    exact: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| fit_into(dst, src, filter, filter_width, mode, exact, roi, nthreads))
+}
+
+/// Same as [fit], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn fit_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    mode: FitMode,
+    exact: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_fit(
+            dst.ptr,
+            src.ptr,
+            filter_name(filter).as_stringref(),
+            filter_width,
+            mode.name().as_stringref(),
+            exact,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
+
+/// Transforms `src` by the 3x3 matrix `matrix` (row-major, transforming row vectors
+/// `[x y 1]`, as in Imath).
+///
+/// If `recompute_roi` is true, the data window of the result is enlarged to contain the whole
+/// transformed image; otherwise it is the same as the data window of `src`. `wrap` determines
+/// the value of source pixels outside of the data window of `src`.
+#[allow(clippy::too_many_arguments)]
+pub fn warp(
+    src: &ImageBuf,
+    matrix: &[f32; 9],
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    recompute_roi: bool,
+    wrap: WrapMode,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<ImageBuf, Error> {
+    into_new(|dst| {
+        warp_into(
+            dst,
+            src,
+            matrix,
+            filter,
+            filter_width,
+            recompute_roi,
+            wrap,
+            roi,
+            nthreads,
+        )
+    })
+}
+
+/// Same as [warp], but writes the result into `dst`.
+#[allow(clippy::too_many_arguments)]
+pub fn warp_into(
+    dst: &mut ImageBuf,
+    src: &ImageBuf,
+    matrix: &[f32; 9],
+    filter: Option<Filter2D>,
+    filter_width: f32,
+    recompute_roi: bool,
+    wrap: WrapMode,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<(), Error> {
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_warp(
+            dst.ptr,
+            src.ptr,
+            matrix.as_ptr(),
+            filter_name(filter).as_stringref(),
+            filter_width,
+            recompute_roi,
+            wrap.to_sys(),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(dst, success)
+}
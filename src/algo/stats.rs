@@ -0,0 +1,231 @@
+//! Image statistics and analysis.
+use crate::{algo::check, cstring_to_owned, Error, ImageBuf, Roi};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+use std::ops::RangeInclusive;
+
+/// Per-channel statistics of the pixels of an image, returned by [compute_pixel_stats].
+///
+/// Each field has one entry per channel of the image. NaN and infinite values are excluded
+/// from `min`, `max`, `avg` and `stddev`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PixelStats {
+    pub min: Vec<f32>,
+    pub max: Vec<f32>,
+    pub avg: Vec<f32>,
+    pub stddev: Vec<f32>,
+    pub nan_count: Vec<u64>,
+    pub inf_count: Vec<u64>,
+    pub finite_count: Vec<u64>,
+}
+
+/// Number of pixels below, above and within a range of values, returned by
+/// [color_range_check].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct RangeCheck {
+    /// Number of pixels with at least one channel below the low bound.
+    pub low: u64,
+    /// Number of pixels with at least one channel above the high bound.
+    pub high: u64,
+    /// Number of pixels with all channels within the bounds.
+    pub in_range: u64,
+}
+
+/// Fails if an analysis function left an error on `src`.
+fn check_src(src: &ImageBuf) -> Result<(), Error> {
+    if src.has_error() {
+        Err(Error::AlgorithmError(src.get_last_error()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Computes statistics about the pixels of `src` within `roi`.
+pub fn compute_pixel_stats(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<PixelStats, Error> {
+    let n = src.num_channels();
+    let mut stats = PixelStats {
+        min: vec![0.0; n],
+        max: vec![0.0; n],
+        avg: vec![0.0; n],
+        stddev: vec![0.0; n],
+        nan_count: vec![0; n],
+        inf_count: vec![0; n],
+        finite_count: vec![0; n],
+    };
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_computePixelStats(
+            src.ptr,
+            n as i32,
+            stats.min.as_mut_ptr(),
+            stats.max.as_mut_ptr(),
+            stats.avg.as_mut_ptr(),
+            stats.stddev.as_mut_ptr(),
+            stats.nan_count.as_mut_ptr(),
+            stats.inf_count.as_mut_ptr(),
+            stats.finite_count.as_mut_ptr(),
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(src, success)?;
+    Ok(stats)
+}
+
+/// Computes the histogram of the values of `channel` of `src`, with `bins` bins evenly dividing
+/// `range`.
+///
+/// Values outside of `range` are counted in the first or last bin, unless
+/// `ignore_outside_range` is true.
+pub fn histogram(
+    src: &ImageBuf,
+    channel: usize,
+    bins: usize,
+    range: RangeInclusive<f32>,
+    ignore_outside_range: bool,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<Vec<u64>, Error> {
+    let mut result = vec![0; bins];
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_histogram(
+            src.ptr,
+            channel as i32,
+            result.as_mut_ptr(),
+            bins as i32,
+            *range.start(),
+            *range.end(),
+            ignore_outside_range,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(src, success)?;
+    Ok(result)
+}
+
+/// Counts the pixels of `src` that have values outside of the range `low..=high` (per channel).
+///
+/// A single bound is used for all channels.
+pub fn color_range_check(
+    src: &ImageBuf,
+    low: &[f32],
+    high: &[f32],
+    roi: Roi,
+    nthreads: usize,
+) -> Result<RangeCheck, Error> {
+    let mut result = RangeCheck::default();
+    let success = unsafe {
+        sys::OIIO_ImageBufAlgo_color_range_check(
+            src.ptr,
+            &mut result.low,
+            &mut result.high,
+            &mut result.in_range,
+            low.as_ptr(),
+            low.len() as i32,
+            high.as_ptr(),
+            high.len() as i32,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check(src, success)?;
+    Ok(result)
+}
+
+/// Returns the smallest region of `src` within `roi` that contains all pixels with a nonzero
+/// value.
+///
+/// If all pixels are zero, the returned region is empty.
+pub fn nonzero_region(src: &ImageBuf, roi: Roi, nthreads: usize) -> Result<Roi, Error> {
+    let region = unsafe { sys::OIIO_ImageBufAlgo_nonzero_region(src.ptr, roi.0, nthreads as i32) };
+    check_src(src)?;
+    Ok(Roi(region))
+}
+
+/// Returns the color of `src` if all its pixels within `roi` have the same value (within
+/// `threshold`), or `None` otherwise.
+///
+/// The returned color has one entry per channel of `src`; channels outside of `roi` are zero.
+pub fn is_constant_color(
+    src: &ImageBuf,
+    threshold: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<Option<Vec<f32>>, Error> {
+    let mut color = vec![0.0; src.num_channels()];
+    let constant = unsafe {
+        sys::OIIO_ImageBufAlgo_isConstantColor(
+            src.ptr,
+            threshold,
+            color.as_mut_ptr(),
+            color.len() as i32,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check_src(src)?;
+    Ok(if constant { Some(color) } else { None })
+}
+
+/// Returns whether `channel` has the value `value` (within `threshold`) for all pixels of `src`
+/// within `roi`.
+pub fn is_constant_channel(
+    src: &ImageBuf,
+    channel: usize,
+    value: f32,
+    threshold: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<bool, Error> {
+    let constant = unsafe {
+        sys::OIIO_ImageBufAlgo_isConstantChannel(
+            src.ptr,
+            channel as i32,
+            value,
+            threshold,
+            roi.0,
+            nthreads as i32,
+        )
+    };
+    check_src(src)?;
+    Ok(constant)
+}
+
+/// Returns whether all pixels of `src` within `roi` have equal values (within `threshold`) in
+/// their channels, i.e. whether the image is gray.
+pub fn is_monochrome(
+    src: &ImageBuf,
+    threshold: f32,
+    roi: Roi,
+    nthreads: usize,
+) -> Result<bool, Error> {
+    let monochrome =
+        unsafe { sys::OIIO_ImageBufAlgo_isMonochrome(src.ptr, threshold, roi.0, nthreads as i32) };
+    check_src(src)?;
+    Ok(monochrome)
+}
+
+/// Computes the SHA-1 hash of the pixels of `src` within `roi`, as a hexadecimal string.
+///
+/// `extra_info` is appended to the hashed data. If `block_size` is not zero, the image is
+/// hashed in blocks of `block_size` scanlines in parallel; the result then depends on the
+/// block size.
+pub fn compute_pixel_hash_sha1(
+    src: &ImageBuf,
+    extra_info: &str,
+    roi: Roi,
+    block_size: usize,
+    nthreads: usize,
+) -> Result<String, Error> {
+    let hash = unsafe {
+        cstring_to_owned(sys::OIIO_ImageBufAlgo_computePixelHashSHA1(
+            src.ptr,
+            extra_info.as_stringref(),
+            roi.0,
+            block_size as i32,
+            nthreads as i32,
+        ))
+    };
+    check_src(src)?;
+    Ok(hash)
+}
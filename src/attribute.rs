@@ -6,8 +6,20 @@ use std::{
     ptr,
 };
 
+/// Types that can be used as the value of an attribute.
+///
+/// # Safety
+///
+/// `TYPEDESC` must describe the memory layout of the type, as OpenImageIO reads and writes
+/// attribute values through untyped pointers.
 pub unsafe trait AttributeType {
     const TYPEDESC: TypeDesc;
+
+    /// Reads a value written by `get` into the pointed-to memory.
+    ///
+    /// # Safety
+    ///
+    /// `get` must write a valid value of type `TYPEDESC` to the pointer, or return an error.
     unsafe fn get(get: impl Fn(*mut c_void) -> Result<(), Error>) -> Result<Self, Error>
     where
         Self: Sized,
@@ -17,6 +29,11 @@ pub unsafe trait AttributeType {
         Ok(v.assume_init())
     }
 
+    /// Passes a pointer to this value to `set`.
+    ///
+    /// # Safety
+    ///
+    /// `set` must only read a value of type `TYPEDESC` from the pointer.
     unsafe fn set(&self, set: impl Fn(*const c_void) -> Result<(), Error>) -> Result<(), Error> {
         set(self as *const Self as *const c_void)
     }
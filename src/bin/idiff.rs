@@ -0,0 +1,169 @@
+//! Compares two images, in the manner of OpenImageIO's `idiff` tool.
+//!
+//! Usage: `idiff [-fail <threshold>] [-warn <threshold>] [-p] <image1> <image2>`
+//!
+//! Exits with status 0 if the images match, 1 if some pixels exceed the warning threshold,
+//! 2 if some pixels exceed the failure threshold, 3 if the images have different sizes,
+//! and 4 if an image could not be read.
+use openimageio::{algo, ImageBuf, Roi, TypeDesc};
+use std::{env, process};
+
+const EXIT_OK: i32 = 0;
+const EXIT_WARN: i32 = 1;
+const EXIT_FAIL: i32 = 2;
+const EXIT_DIFF_SIZE: i32 = 3;
+const EXIT_ERROR: i32 = 4;
+
+struct Options {
+    fail_threshold: f32,
+    warn_threshold: f32,
+    perceptual: bool,
+    files: Vec<String>,
+}
+
+fn usage() -> ! {
+    eprintln!("usage: idiff [-fail <threshold>] [-warn <threshold>] [-p] <image1> <image2>");
+    process::exit(EXIT_ERROR)
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        fail_threshold: 1.0e-6,
+        warn_threshold: 1.0e-6,
+        perceptual: false,
+        files: Vec::new(),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-fail" | "-warn" => {
+                let threshold = args
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or_else(|| usage());
+                if arg == "-fail" {
+                    options.fail_threshold = threshold;
+                } else {
+                    options.warn_threshold = threshold;
+                }
+            }
+            "-p" => options.perceptual = true,
+            _ if arg.starts_with('-') => usage(),
+            _ => options.files.push(arg),
+        }
+    }
+    if options.files.len() != 2 {
+        usage();
+    }
+    options
+}
+
+fn open(path: &str) -> ImageBuf {
+    let mut img = ImageBuf::open(path).unwrap_or_else(|err| {
+        eprintln!("idiff: {}", err);
+        process::exit(EXIT_ERROR)
+    });
+    if let Err(err) = img.read(0, 0, true, TypeDesc::FLOAT) {
+        eprintln!("idiff: could not read {}: {}", path, err);
+        process::exit(EXIT_ERROR)
+    }
+    img
+}
+
+fn main() {
+    let options = parse_args();
+    let a = open(&options.files[0]);
+    let b = open(&options.files[1]);
+
+    println!(
+        "Comparing \"{}\" and \"{}\"",
+        options.files[0], options.files[1]
+    );
+    if (a.width(), a.height(), a.depth(), a.num_channels())
+        != (b.width(), b.height(), b.depth(), b.num_channels())
+    {
+        println!(
+            "Images do not match in size: ({}x{}x{}, {} channels) versus ({}x{}x{}, {} channels)",
+            a.width(),
+            a.height(),
+            a.depth(),
+            a.num_channels(),
+            b.width(),
+            b.height(),
+            b.depth(),
+            b.num_channels()
+        );
+        println!("FAILURE");
+        process::exit(EXIT_DIFF_SIZE);
+    }
+
+    let results = algo::compare(
+        &a,
+        &b,
+        options.fail_threshold,
+        options.warn_threshold,
+        Roi::ALL,
+        0,
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("idiff: {}", err);
+        process::exit(EXIT_ERROR)
+    });
+    let npixels = a.roi().npixels();
+    println!("  Mean error = {}", results.mean_error);
+    println!("  RMS error = {}", results.rms_error);
+    println!("  Peak SNR = {}", results.psnr);
+    println!(
+        "  Max error  = {} @ ({}, {}, {}, channel {})",
+        results.max_error,
+        results.max_pixel.0,
+        results.max_pixel.1,
+        results.max_pixel.2,
+        results.max_channel
+    );
+    println!(
+        "  {} pixels ({:.3}%) over {}",
+        results.warn_count,
+        100.0 * results.warn_count as f64 / npixels as f64,
+        options.warn_threshold
+    );
+    println!(
+        "  {} pixels ({:.3}%) over {}",
+        results.fail_count,
+        100.0 * results.fail_count as f64 / npixels as f64,
+        options.fail_threshold
+    );
+
+    let mut status = if !results.passed() {
+        EXIT_FAIL
+    } else if results.warn_count != 0 {
+        EXIT_WARN
+    } else {
+        EXIT_OK
+    };
+
+    if options.perceptual {
+        let yee = algo::compare_yee(&a, &b, 100.0, 45.0, Roi::ALL, 0).unwrap_or_else(|err| {
+            eprintln!("idiff: {}", err);
+            process::exit(EXIT_ERROR)
+        });
+        println!(
+            "  {} pixels ({:.3}%) failed the perceptual test",
+            yee.fail_count,
+            100.0 * yee.fail_count as f64 / npixels as f64
+        );
+        if !yee.passed() {
+            status = EXIT_FAIL;
+        }
+    }
+
+    println!(
+        "{}",
+        match status {
+            EXIT_OK => "PASS",
+            EXIT_WARN => "WARNING",
+            _ => "FAILURE",
+        }
+    );
+    process::exit(status)
+}
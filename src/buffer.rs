@@ -60,3 +60,59 @@ impl<T: ImageData> ImageBuffer<T> {
         self.data
     }
 }
+
+/// Distances between consecutive pixels, rows and planes of an image stored in a buffer, in
+/// number of elements (not bytes).
+///
+/// Strides describe images stored in a region of a larger buffer, or with padded rows.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Strides {
+    /// Distance between two consecutive pixels of a row.
+    pub x: usize,
+    /// Distance between two consecutive rows.
+    pub y: usize,
+    /// Distance between two consecutive planes (for volume images).
+    pub z: usize,
+}
+
+impl Strides {
+    /// Returns the strides of a buffer where pixels, rows and planes are contiguous.
+    pub fn contiguous(num_channels: usize, width: usize, height: usize) -> Strides {
+        Strides {
+            x: num_channels,
+            y: num_channels * width,
+            z: num_channels * width * height,
+        }
+    }
+
+    /// Returns the minimum length of a buffer with these strides that holds an image of the
+    /// specified size.
+    pub fn buffer_len(
+        &self,
+        num_channels: usize,
+        width: usize,
+        height: usize,
+        depth: usize,
+    ) -> usize {
+        if num_channels == 0 || width == 0 || height == 0 || depth == 0 {
+            return 0;
+        }
+        // saturate so that absurd strides are rejected as too large instead of wrapping around
+        (width - 1)
+            .saturating_mul(self.x)
+            .saturating_add((height - 1).saturating_mul(self.y))
+            .saturating_add((depth - 1).saturating_mul(self.z))
+            .saturating_add(num_channels)
+    }
+
+    /// Returns the strides in bytes for elements of type `T`, as expected by OpenImageIO.
+    pub(crate) fn in_bytes<T>(&self) -> [isize; 3] {
+        // strides of dimensions of size 1 are not used, and may be arbitrarily large
+        let size = mem::size_of::<T>();
+        [
+            self.x.wrapping_mul(size) as isize,
+            self.y.wrapping_mul(size) as isize,
+            self.z.wrapping_mul(size) as isize,
+        ]
+    }
+}
@@ -1,16 +1,17 @@
 use crate::{
     attribute::AttributeType, cstring_to_owned, typedesc::ImageData, Error, ImageBuffer, ImageSpec,
-    ImageSpecOwned, TypeDesc,
+    ImageSpecOwned, Roi, TypeDesc,
 };
 use openimageio_sys as sys;
 use openimageio_sys::AsStringRef;
 use std::{
     ffi::{c_void, CStr},
     mem,
-    ops::{Range, RangeBounds},
+    ops::{Deref, Range, RangeBounds},
     os::raw::c_char,
     path::Path,
     ptr,
+    sync::Arc,
 };
 
 /// A cache of image data that allows an application to read pixels from many image files
@@ -19,6 +20,10 @@ use std::{
 /// See the original documentation for more information.
 pub struct ImageCache(*mut sys::OIIO_ImageCache);
 
+// OpenImageIO's ImageCache is thread-safe: all its methods can be called concurrently.
+unsafe impl Send for ImageCache {}
+unsafe impl Sync for ImageCache {}
+
 impl ImageCache {
     fn get_last_error(&self) -> String {
         unsafe { cstring_to_owned(sys::OIIO_ImageCache_geterror(self.0)) }
@@ -145,11 +150,20 @@ impl ImageCache {
     }
 
     pub fn image<P: AsRef<Path>>(&self, path: P) -> Result<CachedImage, Error> {
-        let path_stringref = path
-            .as_ref()
-            .to_str()
-            .expect("invalid UTF-8")
-            .as_stringref();
+        let (handle, spec) = self.image_handle(path.as_ref())?;
+        Ok(CachedImage {
+            cache: self,
+            spec,
+            handle,
+        })
+    }
+
+    /// Returns the handle of an image, and the spec of its first subimage.
+    fn image_handle(
+        &self,
+        path: &Path,
+    ) -> Result<(*mut sys::OIIO_ImageCache_ImageHandle, ImageSpecOwned), Error> {
+        let path_stringref = path.to_str().expect("invalid UTF-8").as_stringref();
 
         let handle = unsafe { sys::OIIO_ImageCache_get_image_handle(self.0, path_stringref) };
 
@@ -157,13 +171,7 @@ impl ImageCache {
             return Err(Error::OpenError(self.get_last_error()));
         }
 
-        let img = CachedImage {
-            cache: self,
-            spec: self.get_image_spec(handle, 0, 0)?,
-            handle,
-        };
-
-        Ok(img)
+        Ok((handle, self.get_image_spec(handle, 0, 0)?))
     }
 
     /// Returns an `ImageSpec` describing the image specified by `handle`,
@@ -202,6 +210,119 @@ impl Drop for ImageCache {
     }
 }
 
+/// An [ImageCache] that can be shared between threads by cloning it, and whose image handles
+/// do not borrow it.
+///
+/// All methods of [ImageCache] are available through `Deref`.
+#[derive(Clone)]
+pub struct SharedImageCache(Arc<ImageCache>);
+
+impl SharedImageCache {
+    /// Creates a new image cache with the default parameters.
+    pub fn new() -> SharedImageCache {
+        SharedImageCache(Arc::new(ImageCache::new()))
+    }
+
+    /// Returns a handle to an image of the cache.
+    ///
+    /// The handle keeps the cache alive: it can be stored or sent to other threads freely.
+    pub fn image<P: AsRef<Path>>(&self, path: P) -> Result<SharedCachedImage, Error> {
+        let (handle, spec) = self.0.image_handle(path.as_ref())?;
+        Ok(SharedCachedImage {
+            cache: self.0.clone(),
+            spec,
+            handle,
+        })
+    }
+}
+
+impl Default for SharedImageCache {
+    fn default() -> SharedImageCache {
+        SharedImageCache::new()
+    }
+}
+
+impl From<ImageCache> for SharedImageCache {
+    fn from(cache: ImageCache) -> SharedImageCache {
+        SharedImageCache(Arc::new(cache))
+    }
+}
+
+impl Deref for SharedImageCache {
+    type Target = ImageCache;
+
+    fn deref(&self) -> &ImageCache {
+        &self.0
+    }
+}
+
+/// Handle to an image of a [SharedImageCache].
+///
+/// Unlike [CachedImage], it owns a reference to the cache.
+#[derive(Clone)]
+pub struct SharedCachedImage {
+    cache: Arc<ImageCache>,
+    spec: ImageSpecOwned,
+    handle: *mut sys::OIIO_ImageCache_ImageHandle,
+}
+
+// Same as `CachedImage`.
+unsafe impl Send for SharedCachedImage {}
+unsafe impl Sync for SharedCachedImage {}
+
+impl SharedCachedImage {
+    /// Returns a [CachedImage] borrowing this handle, to select subimages and channels and read
+    /// pixels.
+    pub fn as_cached_image(&self) -> CachedImage<'_> {
+        CachedImage {
+            cache: &self.cache,
+            spec: self.spec.clone(),
+            handle: self.handle,
+        }
+    }
+
+    /// Returns the metadata of the first subimage.
+    pub fn spec(&self) -> &ImageSpec {
+        &self.spec
+    }
+
+    /// Returns the width of the first subimage.
+    ///
+    /// Equivalent to `spec().width()`.
+    pub fn width(&self) -> u32 {
+        self.spec().width()
+    }
+
+    /// Returns the height of the first subimage.
+    ///
+    /// Equivalent to `spec().height()`.
+    pub fn height(&self) -> u32 {
+        self.spec().height()
+    }
+
+    /// Returns the depth of the first subimage.
+    ///
+    /// Equivalent to `spec().depth()`.
+    pub fn depth(&self) -> u32 {
+        self.spec().depth()
+    }
+
+    /// Shorthand for `self.as_cached_image().read()`.
+    pub fn read<I: ImageData>(&self) -> Result<ImageBuffer<I>, Error> {
+        self.as_cached_image().read()
+    }
+
+    /// Shorthand for `self.as_cached_image().read_region(roi)`.
+    pub fn read_region<I: ImageData>(&self, roi: Roi) -> Result<ImageBuffer<I>, Error> {
+        self.as_cached_image().read_region(roi)
+    }
+
+    /// Shorthand for `self.as_cached_image().read_region_into(roi, out)`.
+    pub fn read_region_into<T: ImageData>(&self, roi: Roi, out: &mut [T]) -> Result<(), Error> {
+        self.as_cached_image().read_region_into(roi, out)
+    }
+}
+
 /// Handle to a cached image.
 ///
 /// It's safe to clone since the image cache is thread safe.
@@ -213,6 +334,10 @@ pub struct CachedImage<'a> {
     handle: *mut sys::OIIO_ImageCache_ImageHandle,
 }
 
+// Image handles can be used concurrently, like the cache itself.
+unsafe impl Send for CachedImage<'_> {}
+unsafe impl Sync for CachedImage<'_> {}
+
 impl<'a> CachedImage<'a> {
     pub fn subimage(self, subimage: usize) -> Result<CachedSubimageMipmap<'a>, Error> {
         self.subimage_mipmap(subimage, 0)
@@ -296,31 +421,17 @@ impl<'a> CachedImage<'a> {
         self.subimage(0).unwrap().all_channels().read()
     }
 
-    /// Reads channels from a region of an image.
-    pub fn read_region<I: ImageData>(
-        self,
-        xs: impl RangeBounds<i32>,
-        ys: impl RangeBounds<i32>,
-        zs: impl RangeBounds<i32>,
-    ) -> Result<ImageBuffer<I>, Error> {
-        self.subimage(0)
-            .unwrap()
-            .all_channels()
-            .read_region(xs, ys, zs)
+    /// Reads all channels from a region of an image.
+    pub fn read_region<I: ImageData>(self, roi: Roi) -> Result<ImageBuffer<I>, Error> {
+        self.subimage(0).unwrap().all_channels().read_region(roi)
     }
 
     pub fn read_into<T: ImageData>(self, out: &mut [T]) -> Result<(), Error> {
         self.all_channels().read_into(out)
     }
 
-    pub fn read_region_into<T: ImageData>(
-        self,
-        xs: impl RangeBounds<i32>,
-        ys: impl RangeBounds<i32>,
-        zs: impl RangeBounds<i32>,
-        out: &mut [T],
-    ) -> Result<(), Error> {
-        self.all_channels().read_region_into(xs, ys, zs, out)
+    pub fn read_region_into<T: ImageData>(self, roi: Roi, out: &mut [T]) -> Result<(), Error> {
+        self.all_channels().read_region_into(roi, out)
     }
 }
 
@@ -333,6 +444,9 @@ pub struct CachedSubimageMipmap<'a> {
     miplevel: usize,
 }
 
+unsafe impl Send for CachedSubimageMipmap<'_> {}
+unsafe impl Sync for CachedSubimageMipmap<'_> {}
+
 impl<'a> CachedSubimageMipmap<'a> {
     /// Returns the metadata of this subimage.
     pub fn spec(&self) -> &ImageSpec {
@@ -412,14 +526,9 @@ impl<'a> CachedSubimageMipmap<'a> {
         self.all_channels().read()
     }
 
-    /// Reads channels from a region of an image.
-    pub fn read_region<I: ImageData>(
-        self,
-        xs: impl RangeBounds<i32>,
-        ys: impl RangeBounds<i32>,
-        zs: impl RangeBounds<i32>,
-    ) -> Result<ImageBuffer<I>, Error> {
-        self.all_channels().read_region(xs, ys, zs)
+    /// Reads all channels from a region of an image.
+    pub fn read_region<I: ImageData>(self, roi: Roi) -> Result<ImageBuffer<I>, Error> {
+        self.all_channels().read_region(roi)
     }
 
     /// Reads channels into an existing buffer.
@@ -427,14 +536,8 @@ impl<'a> CachedSubimageMipmap<'a> {
         self.all_channels().read_into(out)
     }
 
-    pub fn read_region_into<T: ImageData>(
-        self,
-        xs: impl RangeBounds<i32>,
-        ys: impl RangeBounds<i32>,
-        zs: impl RangeBounds<i32>,
-        out: &mut [T],
-    ) -> Result<(), Error> {
-        self.all_channels().read_region_into(xs, ys, zs, out)
+    pub fn read_region_into<T: ImageData>(self, roi: Roi, out: &mut [T]) -> Result<(), Error> {
+        self.all_channels().read_region_into(roi, out)
     }
 }
 
@@ -447,6 +550,9 @@ pub struct CachedSubimageMipmapChannels<'a> {
     channels: Range<usize>,
 }
 
+unsafe impl Send for CachedSubimageMipmapChannels<'_> {}
+unsafe impl Sync for CachedSubimageMipmapChannels<'_> {}
+
 impl<'a> CachedSubimageMipmapChannels<'a> {
     /// Returns the metadata of this subimage.
     pub fn spec(&self) -> &ImageSpec {
@@ -476,24 +582,36 @@ impl<'a> CachedSubimageMipmapChannels<'a> {
 
     /// Reads channels from the entire image.
     pub fn read<I: ImageData>(&self) -> Result<ImageBuffer<I>, Error> {
-        self.read_region(.., .., ..)
+        self.read_region(Roi::ALL)
+    }
+
+    /// Returns the pixel range of `roi` (or the data window if it is [Roi::ALL]),
+    /// with the selected channels.
+    fn region(&self, roi: Roi) -> Roi {
+        let roi = if roi.is_defined() {
+            roi
+        } else {
+            self.spec().roi()
+        };
+        roi.with_channels(self.channels.clone())
     }
 
     /// Reads channels from a region of an image.
-    pub fn read_region<I: ImageData>(
-        &self,
-        xs: impl RangeBounds<i32>,
-        ys: impl RangeBounds<i32>,
-        zs: impl RangeBounds<i32>,
-    ) -> Result<ImageBuffer<I>, Error> {
-        let spec = self.spec();
-        let (xs, ys, zs) = spec.calculate_bounds(xs, ys, zs);
-        let (width, height, depth) = (xs.len(), ys.len(), zs.len());
+    ///
+    /// Only the pixel range of `roi` is used: the channels read are the selected channels.
+    /// If `roi` is [Roi::ALL], the whole image is read.
+    pub fn read_region<I: ImageData>(&self, roi: Roi) -> Result<ImageBuffer<I>, Error> {
+        let roi = self.region(roi);
+        let (width, height, depth) = (
+            roi.width() as usize,
+            roi.height() as usize,
+            roi.depth() as usize,
+        );
         let n = width * height * depth * self.channels.len();
         let mut data = Vec::with_capacity(n);
 
         unsafe {
-            self.read_region_unchecked(xs, ys, zs, data.as_mut_ptr())?;
+            self.read_region_unchecked(roi, data.as_mut_ptr())?;
             data.set_len(n);
         }
 
@@ -509,34 +627,27 @@ impl<'a> CachedSubimageMipmapChannels<'a> {
 
     /// Reads channels into an existing buffer.
     pub fn read_into<T: ImageData>(&self, out: &mut [T]) -> Result<(), Error> {
-        self.read_region_into(.., .., .., out)
+        self.read_region_into(Roi::ALL, out)
     }
 
-    /// Reads channels into an existing buffer.
-    pub fn read_region_into<T: ImageData>(
-        &self,
-        xs: impl RangeBounds<i32>,
-        ys: impl RangeBounds<i32>,
-        zs: impl RangeBounds<i32>,
-        out: &mut [T],
-    ) -> Result<(), Error> {
-        let spec = self.spec();
-        let (xs, ys, zs) = spec.calculate_bounds(xs, ys, zs);
-        let (width, height, depth) = (xs.len(), ys.len(), zs.len());
-        let n = width * height * depth * self.channels.len();
+    /// Reads channels from a region of an image into an existing buffer.
+    ///
+    /// See [CachedSubimageMipmapChannels::read_region] for the meaning of `roi`.
+    pub fn read_region_into<T: ImageData>(&self, roi: Roi, out: &mut [T]) -> Result<(), Error> {
+        let roi = self.region(roi);
+        let n = roi.npixels() as usize * self.channels.len();
         if out.len() < n {
             return Err(Error::BufferTooSmall);
         }
-        unsafe { self.read_region_unchecked(xs, ys, zs, out.as_mut_ptr()) }
+        unsafe { self.read_region_unchecked(roi, out.as_mut_ptr()) }
     }
 
     unsafe fn read_region_unchecked<I: ImageData>(
         &self,
-        xs: Range<i32>,
-        ys: Range<i32>,
-        zs: Range<i32>,
+        roi: Roi,
         out: *mut I,
     ) -> Result<(), Error> {
+        let (xs, ys, zs) = (roi.xs(), roi.ys(), roi.zs());
         let success = sys::OIIO_ImageCache_get_pixels_stride_by_handle(
             self.cache.0,
             // filename
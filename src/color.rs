@@ -0,0 +1,232 @@
+//! Color management with OpenColorIO configurations.
+use crate::{cstring_to_owned, Error, ImageBuf, Roi};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+use std::{mem, os::raw::c_char, path::Path};
+
+/// Converts a possibly null string returned by the color glue functions.
+unsafe fn name_to_owned(name: *const c_char) -> Option<String> {
+    if name.is_null() {
+        None
+    } else {
+        Some(cstring_to_owned(name))
+    }
+}
+
+/// A color management configuration: color spaces, looks, displays and views.
+///
+/// If OpenImageIO was built with OpenColorIO support, this is an OCIO configuration. Otherwise,
+/// only a few built-in color spaces are available (`linear`, `sRGB`, `Rec709`).
+pub struct ColorConfig {
+    pub(crate) ptr: *mut sys::OIIO_ColorConfig,
+}
+
+// OpenImageIO's ColorConfig is thread-safe (it locks its internal processor cache).
+unsafe impl Send for ColorConfig {}
+unsafe impl Sync for ColorConfig {}
+
+impl ColorConfig {
+    /// Returns whether OpenImageIO was built with OpenColorIO support.
+    pub fn supports_opencolorio() -> bool {
+        unsafe { sys::OIIO_ColorConfig_supportsOpenColorIO() }
+    }
+
+    /// Loads the default configuration, which is the file specified by the `OCIO` environment
+    /// variable if it is set.
+    pub fn new() -> Result<ColorConfig, Error> {
+        let ptr = unsafe { sys::OIIO_ColorConfig_new() };
+        ColorConfig::check(ColorConfig { ptr })
+    }
+
+    /// Loads the OCIO configuration file at the specified path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<ColorConfig, Error> {
+        let path = path.as_ref().to_str().unwrap();
+        let ptr = unsafe { sys::OIIO_ColorConfig_new_file(path.as_stringref()) };
+        ColorConfig::check(ColorConfig { ptr })
+    }
+
+    fn check(config: ColorConfig) -> Result<ColorConfig, Error> {
+        if config.has_error() {
+            Err(Error::ColorError(config.get_last_error()))
+        } else {
+            Ok(config)
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        unsafe { sys::OIIO_ColorConfig_has_error(self.ptr) }
+    }
+
+    fn get_last_error(&self) -> String {
+        unsafe { cstring_to_owned(sys::OIIO_ColorConfig_geterror(self.ptr)) }
+    }
+
+    /// Returns the names of all color spaces.
+    pub fn color_spaces(&self) -> impl ExactSizeIterator<Item = String> + '_ {
+        let n = unsafe { sys::OIIO_ColorConfig_getNumColorSpaces(self.ptr) };
+        (0..n.max(0)).map(move |i| unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getColorSpaceNameByIndex(self.ptr, i))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the names of all roles (e.g. `scene_linear`, `color_picking`).
+    pub fn roles(&self) -> impl ExactSizeIterator<Item = String> + '_ {
+        let n = unsafe { sys::OIIO_ColorConfig_getNumRoles(self.ptr) };
+        (0..n.max(0)).map(move |i| unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getRoleByIndex(self.ptr, i)).unwrap_or_default()
+        })
+    }
+
+    /// Returns the name of the color space assigned to the specified role, if any.
+    pub fn color_space_by_role(&self, role: &str) -> Option<String> {
+        unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getColorSpaceNameByRole(
+                self.ptr,
+                role.as_stringref(),
+            ))
+        }
+    }
+
+    /// Returns the names of all looks.
+    pub fn looks(&self) -> impl ExactSizeIterator<Item = String> + '_ {
+        let n = unsafe { sys::OIIO_ColorConfig_getNumLooks(self.ptr) };
+        (0..n.max(0)).map(move |i| unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getLookNameByIndex(self.ptr, i)).unwrap_or_default()
+        })
+    }
+
+    /// Returns the names of all displays.
+    pub fn displays(&self) -> impl ExactSizeIterator<Item = String> + '_ {
+        let n = unsafe { sys::OIIO_ColorConfig_getNumDisplays(self.ptr) };
+        (0..n.max(0)).map(move |i| unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getDisplayNameByIndex(self.ptr, i))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the name of the default display, if any.
+    pub fn default_display(&self) -> Option<String> {
+        unsafe { name_to_owned(sys::OIIO_ColorConfig_getDefaultDisplayName(self.ptr)) }
+    }
+
+    /// Returns the names of the views of the specified display.
+    pub fn views<'a>(&'a self, display: &'a str) -> impl ExactSizeIterator<Item = String> + 'a {
+        let n = unsafe { sys::OIIO_ColorConfig_getNumViews(self.ptr, display.as_stringref()) };
+        (0..n.max(0)).map(move |i| unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getViewNameByIndex(
+                self.ptr,
+                display.as_stringref(),
+                i,
+            ))
+            .unwrap_or_default()
+        })
+    }
+
+    /// Returns the name of the default view of the specified display, if any.
+    pub fn default_view(&self, display: &str) -> Option<String> {
+        unsafe {
+            name_to_owned(sys::OIIO_ColorConfig_getDefaultViewName(
+                self.ptr,
+                display.as_stringref(),
+            ))
+        }
+    }
+
+    /// Creates a processor that converts colors from the `input` color space to the `output`
+    /// color space. Role names are also accepted.
+    pub fn processor(&self, input: &str, output: &str) -> Result<ColorProcessor, Error> {
+        let ptr = unsafe {
+            sys::OIIO_ColorConfig_createColorProcessor(
+                self.ptr,
+                input.as_stringref(),
+                output.as_stringref(),
+            )
+        };
+        if ptr.is_null() {
+            Err(Error::ColorError(self.get_last_error()))
+        } else {
+            Ok(ColorProcessor { ptr })
+        }
+    }
+}
+
+impl Drop for ColorConfig {
+    fn drop(&mut self) {
+        unsafe { sys::OIIO_ColorConfig_delete(self.ptr) }
+    }
+}
+
+/// A color transformation created from a [ColorConfig].
+pub struct ColorProcessor {
+    pub(crate) ptr: *mut sys::OIIO_ColorProcessor,
+}
+
+// Color processors are immutable once created.
+unsafe impl Send for ColorProcessor {}
+unsafe impl Sync for ColorProcessor {}
+
+impl ColorProcessor {
+    /// Returns whether this processor leaves colors unchanged.
+    pub fn is_noop(&self) -> bool {
+        unsafe { sys::OIIO_ColorProcessor_isNoOp(self.ptr) }
+    }
+
+    /// Returns whether the output value of a channel can depend on the value of other channels.
+    pub fn has_channel_crosstalk(&self) -> bool {
+        unsafe { sys::OIIO_ColorProcessor_hasChannelCrosstalk(self.ptr) }
+    }
+
+    /// Applies the transformation to all pixels of an image, in place.
+    ///
+    /// Color values are unpremultiplied by alpha before the transformation, and premultiplied
+    /// again afterwards.
+    pub fn apply(&self, buf: &mut ImageBuf) -> Result<(), Error> {
+        let success = unsafe {
+            sys::OIIO_ImageBufAlgo_colorconvert_processor(
+                buf.ptr,
+                buf.ptr,
+                self.ptr,
+                true,
+                Roi::ALL.0,
+                0,
+            )
+        };
+        if success {
+            Ok(())
+        } else {
+            Err(Error::AlgorithmError(buf.get_last_error()))
+        }
+    }
+
+    /// Applies the transformation in place to interleaved pixel data with `nchannels` channels.
+    ///
+    /// The first three channels are treated as RGB, and the fourth (if any) as alpha. Values are
+    /// not unpremultiplied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nchannels` is zero or if the length of `data` is not a multiple of `nchannels`.
+    pub fn apply_slice(&self, data: &mut [f32], nchannels: usize) {
+        assert!(nchannels > 0 && data.len().is_multiple_of(nchannels));
+        let float_size = mem::size_of::<f32>() as sys::stride_t;
+        unsafe {
+            sys::OIIO_ColorProcessor_apply(
+                self.ptr,
+                data.as_mut_ptr(),
+                (data.len() / nchannels) as i32,
+                1,
+                nchannels as i32,
+                float_size,
+                float_size * nchannels as sys::stride_t,
+                sys::OIIO_AutoStride,
+            )
+        }
+    }
+}
+
+impl Drop for ColorProcessor {
+    fn drop(&mut self) {
+        unsafe { sys::OIIO_ColorProcessor_delete(self.ptr) }
+    }
+}
@@ -0,0 +1,240 @@
+//! Deep pixel data.
+use crate::{cstring_to_owned, spec::Channel, ChannelDesc, ImageSpec, TypeDesc};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+
+/// Pixel data of a deep image, where each pixel holds a variable number of samples.
+///
+/// Each sample has a value for every channel of the image. Pixels are indexed in scanline
+/// order, relative to the origin of the region that was read.
+///
+/// Values are stored in the format of their channel (see [DeepData::channel]), but are only
+/// accessed as `f32` ([DeepData::value]) or `u32` ([DeepData::value_u32]), and converted from
+/// and to the format of the channel: `f32` is exact for `half` and `float` channels, and `u32`
+/// for unsigned integer channels such as object IDs.
+///
+/// Methods taking pixel, channel or sample indices panic if they are out of bounds.
+pub struct DeepData {
+    pub(crate) ptr: *mut sys::OIIO_DeepData,
+}
+
+// Plain data owned by this object, only mutated through `&mut self`.
+unsafe impl Send for DeepData {}
+unsafe impl Sync for DeepData {}
+
+impl DeepData {
+    /// Creates deep data with the specified number of pixels and channels, and no samples.
+    pub fn new(num_pixels: usize, channels: &[Channel]) -> DeepData {
+        let types: Vec<_> = channels.iter().map(|c| c.format.0).collect();
+        let names: Vec<_> = channels.iter().map(|c| c.name.as_stringref()).collect();
+        let data = DeepData::default();
+        unsafe {
+            sys::OIIO_DeepData_init(
+                data.ptr,
+                num_pixels as i64,
+                channels.len() as i32,
+                types.as_ptr(),
+                names.as_ptr(),
+            );
+        }
+        data
+    }
+
+    /// Creates deep data with the pixels and channels described by a spec, and no samples.
+    pub fn from_spec(spec: &ImageSpec) -> DeepData {
+        let data = DeepData::default();
+        unsafe {
+            sys::OIIO_DeepData_init_spec(data.ptr, &spec.0);
+        }
+        data
+    }
+
+    /// Returns the number of pixels.
+    pub fn num_pixels(&self) -> usize {
+        unsafe { sys::OIIO_DeepData_pixels(self.ptr) as usize }
+    }
+
+    /// Returns the number of channels.
+    pub fn num_channels(&self) -> usize {
+        unsafe { sys::OIIO_DeepData_channels(self.ptr) as usize }
+    }
+
+    /// Returns the name and format of a channel.
+    pub fn channel(&self, channel: usize) -> ChannelDesc {
+        self.check_channel(channel);
+        unsafe {
+            ChannelDesc {
+                format: TypeDesc(sys::OIIO_DeepData_channeltype(self.ptr, channel as i32)),
+                name: cstring_to_owned(sys::OIIO_DeepData_channelname(self.ptr, channel as i32)),
+            }
+        }
+    }
+
+    /// Returns the names and formats of all channels.
+    pub fn channels(&self) -> Vec<ChannelDesc> {
+        (0..self.num_channels()).map(|c| self.channel(c)).collect()
+    }
+
+    /// Returns the index of the depth channel ("Z"), if there is one.
+    pub fn z_channel(&self) -> Option<usize> {
+        channel_index(unsafe { sys::OIIO_DeepData_Z_channel(self.ptr) })
+    }
+
+    /// Returns the index of the back depth channel ("Zback"), if there is one.
+    pub fn zback_channel(&self) -> Option<usize> {
+        channel_index(unsafe { sys::OIIO_DeepData_Zback_channel(self.ptr) })
+    }
+
+    /// Returns the index of the alpha channel ("A"), if there is one.
+    pub fn alpha_channel(&self) -> Option<usize> {
+        channel_index(unsafe { sys::OIIO_DeepData_A_channel(self.ptr) })
+    }
+
+    /// Returns the number of samples of a pixel.
+    pub fn samples(&self, pixel: usize) -> usize {
+        self.check_pixel(pixel);
+        unsafe { sys::OIIO_DeepData_samples(self.ptr, pixel as i64) as usize }
+    }
+
+    /// Sets the number of samples of a pixel, adding or removing samples at the end.
+    pub fn set_samples(&mut self, pixel: usize, samples: usize) {
+        self.check_pixel(pixel);
+        unsafe { sys::OIIO_DeepData_set_samples(self.ptr, pixel as i64, samples as i32) }
+    }
+
+    /// Inserts `count` samples before sample `position` of a pixel.
+    pub fn insert_samples(&mut self, pixel: usize, position: usize, count: usize) {
+        assert!(
+            position <= self.samples(pixel),
+            "sample position out of bounds"
+        );
+        unsafe {
+            sys::OIIO_DeepData_insert_samples(self.ptr, pixel as i64, position as i32, count as i32)
+        }
+    }
+
+    /// Removes `count` samples starting at sample `position` of a pixel.
+    pub fn erase_samples(&mut self, pixel: usize, position: usize, count: usize) {
+        assert!(
+            position + count <= self.samples(pixel),
+            "sample range out of bounds"
+        );
+        unsafe {
+            sys::OIIO_DeepData_erase_samples(self.ptr, pixel as i64, position as i32, count as i32)
+        }
+    }
+
+    /// Returns the value of a sample in a channel, converted to `f32`.
+    pub fn value(&self, pixel: usize, channel: usize, sample: usize) -> f32 {
+        self.check_sample(pixel, channel, sample);
+        unsafe {
+            sys::OIIO_DeepData_deep_value(self.ptr, pixel as i64, channel as i32, sample as i32)
+        }
+    }
+
+    /// Returns the value of a sample in a channel, converted to `u32`.
+    ///
+    /// Use this for integer channels (such as object IDs), which may not be exactly
+    /// representable as `f32`.
+    pub fn value_u32(&self, pixel: usize, channel: usize, sample: usize) -> u32 {
+        self.check_sample(pixel, channel, sample);
+        unsafe {
+            sys::OIIO_DeepData_deep_value_uint(
+                self.ptr,
+                pixel as i64,
+                channel as i32,
+                sample as i32,
+            )
+        }
+    }
+
+    /// Sets the value of a sample in a channel, converted to the format of the channel.
+    pub fn set_value(&mut self, pixel: usize, channel: usize, sample: usize, value: f32) {
+        self.check_sample(pixel, channel, sample);
+        unsafe {
+            sys::OIIO_DeepData_set_deep_value(
+                self.ptr,
+                pixel as i64,
+                channel as i32,
+                sample as i32,
+                value,
+            )
+        }
+    }
+
+    /// Sets the value of a sample in a channel from an integer, converted to the format of the
+    /// channel.
+    pub fn set_value_u32(&mut self, pixel: usize, channel: usize, sample: usize, value: u32) {
+        self.check_sample(pixel, channel, sample);
+        unsafe {
+            sys::OIIO_DeepData_set_deep_value_uint(
+                self.ptr,
+                pixel as i64,
+                channel as i32,
+                sample as i32,
+                value,
+            )
+        }
+    }
+
+    /// Sorts the samples of a pixel by depth.
+    pub fn sort(&mut self, pixel: usize) {
+        self.check_pixel(pixel);
+        unsafe { sys::OIIO_DeepData_sort(self.ptr, pixel as i64) }
+    }
+
+    /// Merges the samples of a pixel that have identical depth ranges.
+    ///
+    /// The samples must be sorted by depth.
+    pub fn merge_overlaps(&mut self, pixel: usize) {
+        self.check_pixel(pixel);
+        unsafe { sys::OIIO_DeepData_merge_overlaps(self.ptr, pixel as i64) }
+    }
+
+    fn check_pixel(&self, pixel: usize) {
+        assert!(pixel < self.num_pixels(), "pixel index out of bounds");
+    }
+
+    fn check_channel(&self, channel: usize) {
+        assert!(channel < self.num_channels(), "channel index out of bounds");
+    }
+
+    fn check_sample(&self, pixel: usize, channel: usize, sample: usize) {
+        self.check_channel(channel);
+        assert!(sample < self.samples(pixel), "sample index out of bounds");
+    }
+}
+
+/// Converts a channel index returned by OpenImageIO (-1 if not found).
+fn channel_index(index: i32) -> Option<usize> {
+    if index < 0 {
+        None
+    } else {
+        Some(index as usize)
+    }
+}
+
+impl Default for DeepData {
+    /// Creates empty deep data, with no pixels and no channels.
+    fn default() -> DeepData {
+        DeepData {
+            ptr: unsafe { sys::OIIO_DeepData_new() },
+        }
+    }
+}
+
+impl Clone for DeepData {
+    fn clone(&self) -> DeepData {
+        DeepData {
+            ptr: unsafe { sys::OIIO_DeepData_clone(self.ptr) },
+        }
+    }
+}
+
+impl Drop for DeepData {
+    fn drop(&mut self) {
+        unsafe {
+            sys::OIIO_DeepData_delete(self.ptr);
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub enum Error {
     WriteError(String),
     ReadError(String),
     SubimageNotFound,
+    MipLevelNotFound,
     ChannelIndexOutOfBounds,
     ChannelNotFound,
     InvalidChannelIndex,
@@ -15,6 +16,14 @@ pub enum Error {
     InvalidParameter,
     BufferTooSmall,
     InvalidForImageType,
+    AlgorithmError(String),
+    ColorError(String),
+    AlphaChannelNotFound,
+    UnsupportedFormat(String),
+    UnknownFormat,
+    Cancelled,
+    MissingSubimages { declared: usize, written: usize },
+    InvalidOption(String),
 }
 
 impl error::Error for Error {}
@@ -26,6 +35,7 @@ impl fmt::Display for Error {
             Error::WriteError(ref msg) => write!(f, "error writing image data: {}", msg),
             Error::ReadError(ref msg) => write!(f, "error reading image data: {}", msg),
             Error::SubimageNotFound => write!(f, "non-existent subimage"),
+            Error::MipLevelNotFound => write!(f, "non-existent MIP level"),
             Error::ChannelNotFound => write!(f, "non-existent channel"),
             Error::InvalidParameter => write!(f, "invalid parameter"),
             Error::ChannelIndexOutOfBounds => write!(f, "channel index out of bounds"),
@@ -39,6 +49,24 @@ impl fmt::Display for Error {
             Error::InvalidChannelIndex => write!(f, "non-existent channel index"),
             Error::BufferTooSmall => write!(f, "buffer was too small"),
             Error::InvalidForImageType => write!(f, "image type did not support operation"),
+            Error::AlgorithmError(ref msg) => {
+                write!(f, "image processing operation failed: {}", msg)
+            }
+            Error::ColorError(ref msg) => write!(f, "color management error: {}", msg),
+            Error::AlphaChannelNotFound => write!(f, "image has no alpha channel"),
+            Error::UnsupportedFormat(ref format) => write!(
+                f,
+                "format `{}` is unknown or cannot be used with memory or streams",
+                format
+            ),
+            Error::UnknownFormat => write!(f, "image data was not recognized by any reader"),
+            Error::Cancelled => write!(f, "operation was cancelled"),
+            Error::MissingSubimages { declared, written } => write!(
+                f,
+                "only {} of the {} declared subimages were written",
+                written, declared
+            ),
+            Error::InvalidOption(ref msg) => write!(f, "invalid writer option: {}", msg),
             //_ => write!(f, "Unknown error."),
         }
     }
@@ -0,0 +1,541 @@
+//! In-memory images with metadata.
+use crate::{
+    cstring_to_owned,
+    progress::{self, Progress},
+    typedesc::ImageData,
+    Error, ImageBuffer, ImageSpec, Roi, TypeDesc,
+};
+use openimageio_sys as sys;
+use openimageio_sys::AsStringRef;
+use std::{ffi::c_void, ops::ControlFlow, path::Path, ptr};
+
+/// Determines the value of pixels outside of the data window of an image, when they are
+/// looked up by an operation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WrapMode {
+    /// The default mode of the operation (usually [WrapMode::Black]).
+    Default,
+    /// Pixels outside of the data window are black.
+    Black,
+    /// Coordinates are clamped to the data window.
+    Clamp,
+    /// The image repeats periodically.
+    Periodic,
+    /// The image repeats, mirrored on every other repetition.
+    Mirror,
+}
+
+impl WrapMode {
+    pub(crate) fn to_sys(self) -> sys::OIIO_ImageBuf_WrapMode {
+        match self {
+            WrapMode::Default => sys::OIIO_ImageBuf_WrapMode_WrapDefault,
+            WrapMode::Black => sys::OIIO_ImageBuf_WrapMode_WrapBlack,
+            WrapMode::Clamp => sys::OIIO_ImageBuf_WrapMode_WrapClamp,
+            WrapMode::Periodic => sys::OIIO_ImageBuf_WrapMode_WrapPeriodic,
+            WrapMode::Mirror => sys::OIIO_ImageBuf_WrapMode_WrapMirror,
+        }
+    }
+}
+
+/// An image in memory, along with its metadata.
+///
+/// Unlike [ImageBuffer], an `ImageBuf` carries a full [ImageSpec] (including all attributes
+/// read from the file), and can be written back to an image file.
+///
+/// An `ImageBuf` opened from a file reads its pixels lazily: they are loaded on first access,
+/// or explicitly with [ImageBuf::read].
+pub struct ImageBuf {
+    pub(crate) ptr: *mut sys::OIIO_ImageBuf,
+}
+
+// An ImageBuf owns its pixels (or reads them through a thread-safe ImageCache), so it can be
+// moved to another thread.
+unsafe impl Send for ImageBuf {}
+
+impl ImageBuf {
+    pub(crate) fn has_error(&self) -> bool {
+        unsafe { sys::OIIO_ImageBuf_has_error(self.ptr) }
+    }
+
+    pub(crate) fn get_last_error(&self) -> String {
+        unsafe { cstring_to_owned(sys::OIIO_ImageBuf_geterror(self.ptr)) }
+    }
+
+    /// Creates an empty, uninitialized image buffer.
+    pub fn new() -> ImageBuf {
+        let ptr = unsafe { sys::OIIO_ImageBuf_new() };
+        ImageBuf { ptr }
+    }
+
+    /// Creates an image buffer with the specified metadata and zero-initialized pixels.
+    pub fn from_spec(spec: &ImageSpec) -> ImageBuf {
+        let ptr = unsafe { sys::OIIO_ImageBuf_new_spec(&spec.0, true) };
+        ImageBuf { ptr }
+    }
+
+    /// Opens the image file at the specified path.
+    ///
+    /// Only the metadata is read at this point.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<ImageBuf, Error> {
+        ImageBuf::open_subimage(path, 0, 0)
+    }
+
+    /// Opens the specified subimage and MIP level of the image file at the specified path.
+    ///
+    /// Only the metadata is read at this point.
+    pub fn open_subimage<P: AsRef<Path>>(
+        path: P,
+        subimage: usize,
+        miplevel: usize,
+    ) -> Result<ImageBuf, Error> {
+        let path = path.as_ref().to_str().unwrap();
+        let ptr = unsafe {
+            sys::OIIO_ImageBuf_new_file(
+                path.as_stringref(),
+                subimage as i32,
+                miplevel as i32,
+                ptr::null_mut(),
+                ptr::null(),
+            )
+        };
+        let buf = ImageBuf { ptr };
+        let success = unsafe {
+            sys::OIIO_ImageBuf_init_spec(
+                buf.ptr,
+                path.as_stringref(),
+                subimage as i32,
+                miplevel as i32,
+            )
+        };
+        if success {
+            Ok(buf)
+        } else {
+            Err(Error::OpenError(buf.get_last_error()))
+        }
+    }
+
+    /// Reads the pixels of the specified subimage and MIP level into memory.
+    ///
+    /// If `force` is false, the pixels may be backed by the image cache instead of
+    /// being read into a local buffer. If `convert` is not `TypeDesc::UNKNOWN`, the pixels
+    /// are converted to this format; this forces a read into a local buffer.
+    pub fn read(
+        &mut self,
+        subimage: usize,
+        miplevel: usize,
+        force: bool,
+        convert: TypeDesc,
+    ) -> Result<(), Error> {
+        self.read_impl(subimage, miplevel, force, convert, None)
+    }
+
+    /// Same as [ImageBuf::read], but reports progress.
+    ///
+    /// `progress` is called periodically with the portion of the image that was read, between
+    /// 0 and 1. If it returns [ControlFlow::Break], reading stops and [Error::Cancelled] is
+    /// returned.
+    pub fn read_with_progress(
+        &mut self,
+        subimage: usize,
+        miplevel: usize,
+        force: bool,
+        convert: TypeDesc,
+        mut progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        self.read_impl(subimage, miplevel, force, convert, Some(&mut progress))
+    }
+
+    fn read_impl(
+        &mut self,
+        subimage: usize,
+        miplevel: usize,
+        force: bool,
+        convert: TypeDesc,
+        progress: Option<&mut dyn FnMut(f32) -> ControlFlow<()>>,
+    ) -> Result<(), Error> {
+        let mut progress = progress.map(Progress::new);
+        let (callback, callback_data) = progress::raw_callback(progress.as_mut());
+        let success = unsafe {
+            sys::OIIO_ImageBuf_read(
+                self.ptr,
+                subimage as i32,
+                miplevel as i32,
+                force,
+                convert.0,
+                callback,
+                callback_data,
+            )
+        };
+        progress::finish(progress, success, || {
+            Error::ReadError(self.get_last_error())
+        })
+    }
+
+    /// Writes this image to a file. The file format is deduced from the extension.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.write_as(path, TypeDesc::UNKNOWN)
+    }
+
+    /// Writes this image to a file, converting the pixels to the specified format.
+    ///
+    /// If `dtype` is `TypeDesc::UNKNOWN`, the format set with [ImageBuf::set_write_format]
+    /// is used, or the format of the buffer if none was set.
+    pub fn write_as<P: AsRef<Path>>(&self, path: P, dtype: TypeDesc) -> Result<(), Error> {
+        self.write_impl(path.as_ref(), dtype, None)
+    }
+
+    /// Same as [ImageBuf::write_as], but reports progress.
+    ///
+    /// `progress` is called periodically with the portion of the image that was written, between
+    /// 0 and 1. If it returns [ControlFlow::Break], writing stops and [Error::Cancelled] is
+    /// returned; the file is left incomplete.
+    pub fn write_with_progress<P: AsRef<Path>>(
+        &self,
+        path: P,
+        dtype: TypeDesc,
+        mut progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        self.write_impl(path.as_ref(), dtype, Some(&mut progress))
+    }
+
+    fn write_impl(
+        &self,
+        path: &Path,
+        dtype: TypeDesc,
+        progress: Option<&mut dyn FnMut(f32) -> ControlFlow<()>>,
+    ) -> Result<(), Error> {
+        let path = path.to_str().unwrap();
+        let mut progress = progress.map(Progress::new);
+        let (callback, callback_data) = progress::raw_callback(progress.as_mut());
+        let success = unsafe {
+            sys::OIIO_ImageBuf_write_file(
+                self.ptr,
+                path.as_stringref(),
+                dtype.0,
+                "".as_stringref(),
+                callback,
+                callback_data,
+            )
+        };
+        progress::finish(progress, success, || {
+            Error::WriteError(self.get_last_error())
+        })
+    }
+
+    /// Sets the pixel format used by subsequent calls to [ImageBuf::write].
+    pub fn set_write_format(&mut self, format: TypeDesc) {
+        unsafe { sys::OIIO_ImageBuf_set_write_format(self.ptr, format.0) }
+    }
+
+    /// Sets the tile size used by subsequent calls to [ImageBuf::write].
+    ///
+    /// A width of zero means that the image is written as scanlines.
+    pub fn set_write_tiles(&mut self, width: u32, height: u32, depth: u32) {
+        unsafe {
+            sys::OIIO_ImageBuf_set_write_tiles(self.ptr, width as i32, height as i32, depth as i32)
+        }
+    }
+
+    /// Returns the metadata of this image.
+    pub fn spec(&self) -> &ImageSpec {
+        unsafe { &*(sys::OIIO_ImageBuf_spec(self.ptr) as *const ImageSpec) }
+    }
+
+    /// Returns the metadata of this image as it is in the file.
+    ///
+    /// This can differ from [ImageBuf::spec] if the pixels were converted to another format
+    /// when read.
+    pub fn nativespec(&self) -> &ImageSpec {
+        unsafe { &*(sys::OIIO_ImageBuf_nativespec(self.ptr) as *const ImageSpec) }
+    }
+
+    /// Returns the width of this image.
+    ///
+    /// Equivalent to `spec().width()`.
+    pub fn width(&self) -> u32 {
+        self.spec().width()
+    }
+
+    /// Returns the height of this image.
+    ///
+    /// Equivalent to `spec().height()`.
+    pub fn height(&self) -> u32 {
+        self.spec().height()
+    }
+
+    /// Returns the depth of this image.
+    ///
+    /// Equivalent to `spec().depth()`.
+    pub fn depth(&self) -> u32 {
+        self.spec().depth()
+    }
+
+    /// Returns the number of channels of this image.
+    pub fn num_channels(&self) -> usize {
+        unsafe { sys::OIIO_ImageBuf_nchannels(self.ptr) as usize }
+    }
+
+    /// Returns the name of the file this image was read from, if any.
+    pub fn name(&self) -> String {
+        unsafe { cstring_to_owned(sys::OIIO_ImageBuf_name(self.ptr)) }
+    }
+
+    /// Returns the name of the format of the file this image was read from, if any.
+    pub fn file_format_name(&self) -> String {
+        unsafe { cstring_to_owned(sys::OIIO_ImageBuf_file_format_name(self.ptr)) }
+    }
+
+    /// Returns the index of the subimage of the file that this buffer holds.
+    pub fn subimage(&self) -> usize {
+        unsafe { sys::OIIO_ImageBuf_subimage(self.ptr) as usize }
+    }
+
+    /// Returns the number of subimages in the file this image was read from.
+    pub fn num_subimages(&self) -> usize {
+        unsafe { sys::OIIO_ImageBuf_nsubimages(self.ptr) as usize }
+    }
+
+    /// Returns the MIP level of the file that this buffer holds.
+    pub fn miplevel(&self) -> usize {
+        unsafe { sys::OIIO_ImageBuf_miplevel(self.ptr) as usize }
+    }
+
+    /// Returns the number of MIP levels of the current subimage in the file.
+    pub fn num_miplevels(&self) -> usize {
+        unsafe { sys::OIIO_ImageBuf_nmiplevels(self.ptr) as usize }
+    }
+
+    /// Returns the format of the pixels in memory.
+    pub fn pixel_type(&self) -> TypeDesc {
+        unsafe { TypeDesc(sys::OIIO_ImageBuf_pixeltype(self.ptr)) }
+    }
+
+    /// Returns whether this buffer holds an image (read from a file or created from a spec).
+    pub fn is_initialized(&self) -> bool {
+        unsafe { sys::OIIO_ImageBuf_initialized(self.ptr) }
+    }
+
+    /// Returns whether the pixels of this buffer are valid (i.e. have been read).
+    pub fn pixels_valid(&self) -> bool {
+        unsafe { sys::OIIO_ImageBuf_pixels_valid(self.ptr) }
+    }
+
+    /// Returns whether the pixels of this buffer are backed by the image cache.
+    pub fn is_cached(&self) -> bool {
+        unsafe { sys::OIIO_ImageBuf_cachedpixels(self.ptr) }
+    }
+
+    /// Returns whether this buffer holds a deep image.
+    pub fn is_deep(&self) -> bool {
+        unsafe { sys::OIIO_ImageBuf_deep(self.ptr) }
+    }
+
+    /// Returns the EXIF orientation of this image (1 is the default, unrotated orientation).
+    pub fn orientation(&self) -> i32 {
+        unsafe { sys::OIIO_ImageBuf_orientation(self.ptr) }
+    }
+
+    /// Sets the EXIF orientation of this image.
+    pub fn set_orientation(&mut self, orientation: i32) {
+        unsafe { sys::OIIO_ImageBuf_set_orientation(self.ptr, orientation) }
+    }
+
+    /// Returns the width of the image once its orientation is applied.
+    pub fn oriented_width(&self) -> u32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_width(self.ptr) as u32 }
+    }
+
+    /// Returns the height of the image once its orientation is applied.
+    pub fn oriented_height(&self) -> u32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_height(self.ptr) as u32 }
+    }
+
+    /// Returns the x origin of the data window once the orientation is applied.
+    pub fn oriented_x(&self) -> i32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_x(self.ptr) }
+    }
+
+    /// Returns the y origin of the data window once the orientation is applied.
+    pub fn oriented_y(&self) -> i32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_y(self.ptr) }
+    }
+
+    /// Returns the width of the display window once the orientation is applied.
+    pub fn oriented_display_width(&self) -> u32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_full_width(self.ptr) as u32 }
+    }
+
+    /// Returns the height of the display window once the orientation is applied.
+    pub fn oriented_display_height(&self) -> u32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_full_height(self.ptr) as u32 }
+    }
+
+    /// Returns the x origin of the display window once the orientation is applied.
+    pub fn oriented_display_x(&self) -> i32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_full_x(self.ptr) }
+    }
+
+    /// Returns the y origin of the display window once the orientation is applied.
+    pub fn oriented_display_y(&self) -> i32 {
+        unsafe { sys::OIIO_ImageBuf_oriented_full_y(self.ptr) }
+    }
+
+    /// Returns the region covering the data window and all channels of this image.
+    pub fn roi(&self) -> Roi {
+        unsafe { Roi(sys::OIIO_ImageBuf_roi(self.ptr)) }
+    }
+
+    /// Returns the region covering the display window and all channels of this image.
+    pub fn roi_full(&self) -> Roi {
+        unsafe { Roi(sys::OIIO_ImageBuf_roi_full(self.ptr)) }
+    }
+
+    /// Sets the display window of this image.
+    pub fn set_roi_full(&mut self, roi: Roi) {
+        unsafe { sys::OIIO_ImageBuf_set_roi_full(self.ptr, roi.0) }
+    }
+
+    /// Moves the origin of the data window of this image, without changing its pixels.
+    pub fn set_origin(&mut self, x: i32, y: i32, z: i32) {
+        unsafe { sys::OIIO_ImageBuf_set_origin(self.ptr, x, y, z) }
+    }
+
+    /// Makes this buffer a copy of `src` (metadata and pixels).
+    pub fn copy_from(&mut self, src: &ImageBuf) -> Result<(), Error> {
+        let success = unsafe { sys::OIIO_ImageBuf_copy(self.ptr, src.ptr, TypeDesc::UNKNOWN.0) };
+        if success {
+            Ok(())
+        } else {
+            Err(Error::ReadError(self.get_last_error()))
+        }
+    }
+
+    /// Copies the metadata of `src` into this buffer, except for the resolution, channels
+    /// and pixel format.
+    pub fn copy_metadata_from(&mut self, src: &ImageBuf) {
+        unsafe { sys::OIIO_ImageBuf_copy_metadata(self.ptr, src.ptr) }
+    }
+
+    /// Copies the pixels of `src` into this buffer, in the region where the two images overlap.
+    pub fn copy_pixels_from(&mut self, src: &ImageBuf) -> Result<(), Error> {
+        let success = unsafe { sys::OIIO_ImageBuf_copy_pixels(self.ptr, src.ptr) };
+        if success {
+            Ok(())
+        } else {
+            Err(Error::ReadError(self.get_last_error()))
+        }
+    }
+
+    /// Reads the pixels of a region of this image into an [ImageBuffer].
+    ///
+    /// Pass [Roi::ALL] to read the whole image.
+    pub fn get_pixels<T: ImageData>(&self, roi: Roi) -> Result<ImageBuffer<T>, Error> {
+        let roi = self.clamp_roi(roi);
+        let (width, height, depth) = (
+            roi.width() as usize,
+            roi.height() as usize,
+            roi.depth() as usize,
+        );
+        let n = width * height * depth * roi.num_channels();
+        let mut data = Vec::with_capacity(n);
+
+        unsafe {
+            self.get_pixels_unchecked(roi, data.as_mut_ptr())?;
+            data.set_len(n);
+        }
+
+        Ok(ImageBuffer {
+            width,
+            height,
+            depth,
+            num_channels: roi.num_channels(),
+            data,
+        })
+    }
+
+    /// Reads the pixels of a region of this image into an existing buffer.
+    pub fn get_pixels_into<T: ImageData>(&self, roi: Roi, out: &mut [T]) -> Result<(), Error> {
+        let roi = self.clamp_roi(roi);
+        let n = roi.width() as usize
+            * roi.height() as usize
+            * roi.depth() as usize
+            * roi.num_channels();
+        if out.len() < n {
+            return Err(Error::BufferTooSmall);
+        }
+        unsafe { self.get_pixels_unchecked(roi, out.as_mut_ptr()) }
+    }
+
+    unsafe fn get_pixels_unchecked<T: ImageData>(
+        &self,
+        roi: Roi,
+        out: *mut T,
+    ) -> Result<(), Error> {
+        let success = sys::OIIO_ImageBuf_get_pixels(
+            self.ptr,
+            roi.0,
+            T::DESC.0,
+            out as *mut c_void,
+            sys::OIIO_AutoStride,
+            sys::OIIO_AutoStride,
+            sys::OIIO_AutoStride,
+        );
+        if success {
+            Ok(())
+        } else {
+            Err(Error::ReadError(self.get_last_error()))
+        }
+    }
+
+    /// Overwrites the pixels of a region of this image with the contents of `data`.
+    ///
+    /// Pass [Roi::ALL] to overwrite the whole image.
+    pub fn set_pixels<T: ImageData>(&mut self, roi: Roi, data: &[T]) -> Result<(), Error> {
+        let roi = self.clamp_roi(roi);
+        let n = roi.width() as usize
+            * roi.height() as usize
+            * roi.depth() as usize
+            * roi.num_channels();
+        if data.len() < n {
+            return Err(Error::BufferTooSmall);
+        }
+        let success = unsafe {
+            sys::OIIO_ImageBuf_set_pixels(
+                self.ptr,
+                roi.0,
+                T::DESC.0,
+                data.as_ptr() as *const c_void,
+                sys::OIIO_AutoStride,
+                sys::OIIO_AutoStride,
+                sys::OIIO_AutoStride,
+            )
+        };
+        if success {
+            Ok(())
+        } else {
+            Err(Error::WriteError(self.get_last_error()))
+        }
+    }
+
+    /// Replaces `Roi::ALL` by the data window of the image, and clamps the channel range
+    /// to the channels of the image.
+    fn clamp_roi(&self, roi: Roi) -> Roi {
+        let mut roi = if roi.is_defined() { roi } else { self.roi() };
+        roi.0.chend = roi.0.chend.min(self.num_channels() as i32);
+        roi
+    }
+}
+
+impl Default for ImageBuf {
+    fn default() -> Self {
+        ImageBuf::new()
+    }
+}
+
+impl Drop for ImageBuf {
+    fn drop(&mut self) {
+        unsafe {
+            sys::OIIO_ImageBuf_delete(self.ptr);
+        }
+    }
+}
@@ -1,42 +1,230 @@
 use crate::{
-    cstring_to_owned, error::get_last_error, typedesc::ImageData, Error, ImageBuffer, ImageSpec,
-    TypeDesc,
+    attribute::AttributeType,
+    cstring_to_owned,
+    error::get_last_error,
+    ioproxy::{IoProxy, ReadSeek},
+    progress::{self, Progress},
+    strips::Strips,
+    tiles::{self, Tile, Tiles},
+    typedesc::ImageData,
+    DeepData, Error, ImageBuffer, ImageSpec, ImageSpecOwned, Roi, Strides, TypeDesc,
 };
 use openimageio_sys as sys;
 use openimageio_sys::AsStringRef;
-use std::{ffi::c_void, mem, ops::Range, path::Path, ptr};
+use std::{
+    ffi::{c_void, CStr},
+    io::{Read, Seek},
+    ops::{ControlFlow, Range},
+    path::{Path, PathBuf},
+    ptr,
+};
 
 /// Image file opened for input.
 ///
-/// Use [ImageInput::open] to open an image file.
+/// Use [ImageInput::open] to open an image file, or [ImageInput::open_from_memory] and
+/// [ImageInput::open_reader] to decode an image from memory or from a stream.
 ///
 /// Images may contain multiple _subimages_ (e.g. the faces of a cube map)
-/// and/or _mip maps_. You must select which subimage to read from with the
-/// [ImageInput::subimage_0] or [ImageInput::subimage] methods, and use
+/// and/or _mip maps_. You can select which subimage and MIP level to read from with the
+/// [ImageInput::subimage_0], [ImageInput::subimage] or [ImageInput::subimage_miplevel] methods
+/// (or iterate over them with [ImageInput::subimages] and [SubimageInput::miplevels]), and use
 /// the returned [SubimageInput] object to read image data.
-/// These methods exclusively borrow the `ImageInput` object, so it's impossible to read multiple
-/// subimages at once.
 pub struct ImageInput {
-    ptr: *mut sys::OIIO_ImageInput,
+    pub(crate) ptr: *mut sys::OIIO_ImageInput,
+    // must outlive the reader: dropped after `ptr` is deleted
+    proxy: Option<IoProxy>,
+    // file and configuration this input was opened with, to open it again in other threads
+    path: Option<PathBuf>,
+    config: Option<ImageSpecOwned>,
+    // metadata of the first subimage: reads seek and replace the spec held by OpenImageIO
+    spec: ImageSpecOwned,
 }
 
+// An ImageInput can be moved to another thread. It is not `Sync`: reads seek to a subimage and
+// MIP level, which is state shared by all users of the input.
+unsafe impl Send for ImageInput {}
+
 impl ImageInput {
     /// Opens the image file at the specified path.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<ImageInput, Error> {
-        let path = path.as_ref().to_str().unwrap();
-        let ptr = unsafe { sys::OIIO_ImageInput_open(path.as_stringref(), ptr::null()) };
+        ImageInput::open_internal(path.as_ref(), None)
+    }
+
+    /// Opens the image file at the specified path, passing configuration hints to the reader.
+    pub fn open_with<P: AsRef<Path>>(path: P, options: &OpenOptions) -> Result<ImageInput, Error> {
+        ImageInput::open_internal(path.as_ref(), Some(&options.config))
+    }
+
+    fn open_internal(path: &Path, config: Option<&ImageSpecOwned>) -> Result<ImageInput, Error> {
+        let path_str = path.to_str().unwrap();
+        let config_ptr = config.map_or(ptr::null(), |config| config.0 as *const _);
+        let ptr = unsafe { sys::OIIO_ImageInput_open(path_str.as_stringref(), config_ptr) };
         if ptr.is_null() {
             Err(Error::OpenError(get_last_error()))
         } else {
-            let input = ImageInput { ptr };
-            //input.seek_subimage_mipmap(0, 0)?;
-            Ok(input)
+            let input = ImageInput {
+                ptr,
+                proxy: None,
+                path: Some(path.to_owned()),
+                config: config.cloned(),
+                spec: ImageSpecOwned::new(),
+            };
+            Ok(input.with_first_spec())
+        }
+    }
+
+    /// Opens the file of this input again, with the same configuration.
+    ///
+    /// Returns [Error::InvalidForImageType] if this input does not read from a file.
+    pub(crate) fn reopen(&self) -> Result<ImageInput, Error> {
+        match self.path {
+            Some(ref path) => ImageInput::open_internal(path, self.config.as_ref()),
+            None => Err(Error::InvalidForImageType),
+        }
+    }
+
+    /// Decodes an image stored in memory.
+    ///
+    /// `format_hint` is the name of the format (e.g. `"png"`) or a file extension. If it is
+    /// `None`, all readers that support in-memory input are tried. The data is copied, so it
+    /// doesn't need to outlive the returned object.
+    ///
+    /// Returns [Error::UnsupportedFormat] if the format is unknown or its reader cannot read
+    /// from memory, and [Error::UnknownFormat] if no reader recognized the data.
+    pub fn open_from_memory(data: &[u8], format_hint: Option<&str>) -> Result<ImageInput, Error> {
+        ImageInput::open_proxy(IoProxy::from_memory(data), format_hint)
+    }
+
+    /// Decodes an image from a stream.
+    ///
+    /// The format is detected from the contents of the stream, as with
+    /// [ImageInput::open_from_memory] without a format hint. The stream may be read from
+    /// multiple threads (one at a time), and is dropped with the returned object.
+    pub fn open_reader<R: Read + Seek + Send + 'static>(reader: R) -> Result<ImageInput, Error> {
+        let reader: Box<dyn ReadSeek> = Box::new(reader);
+        ImageInput::open_proxy(IoProxy::from_reader(reader)?, None)
+    }
+
+    fn open_proxy(proxy: IoProxy, format_hint: Option<&str>) -> Result<ImageInput, Error> {
+        let candidates = match format_hint {
+            Some(format) => {
+                let reader = ImageInput::create_reader(format)
+                    .filter(|reader| reader.supports(InputCapability::IoProxy))
+                    .ok_or_else(|| Error::UnsupportedFormat(format.to_owned()))?;
+                vec![reader]
+            }
+            None => {
+                let formats = unsafe { cstring_to_owned(sys::OIIO_input_format_list()) };
+                formats
+                    .split(',')
+                    .filter_map(ImageInput::create_reader)
+                    .filter(|reader| reader.supports(InputCapability::IoProxy))
+                    .collect()
+            }
+        };
+
+        let mut last_error = None;
+        for mut reader in candidates {
+            if unsafe { sys::OIIO_ImageInput_open_ioproxy(reader.ptr, proxy.ptr, ptr::null()) } {
+                reader.proxy = Some(proxy);
+                return Ok(reader.with_first_spec());
+            }
+            last_error = Some(reader.get_last_error());
+        }
+        match (format_hint, last_error) {
+            // the format was known: report why its reader failed
+            (Some(_), Some(msg)) => Err(Error::OpenError(msg)),
+            _ => Err(Error::UnknownFormat),
         }
     }
 
-    /// Returns the metadata of this image.
+    /// Creates a reader for the format with the specified name or file extension, without
+    /// opening any file.
+    fn create_reader(name: &str) -> Option<ImageInput> {
+        let ptr = unsafe { sys::OIIO_ImageInput_create(name.as_stringref(), "".as_stringref()) };
+        if ptr.is_null() {
+            // clear the error message left by OpenImageIO
+            get_last_error();
+            None
+        } else {
+            Some(ImageInput {
+                ptr,
+                proxy: None,
+                path: None,
+                config: None,
+                spec: ImageSpecOwned::new(),
+            })
+        }
+    }
+
+    /// Stores the metadata of the first subimage, once the reader is open.
+    fn with_first_spec(mut self) -> ImageInput {
+        if let Some(spec) = self.spec_level(0, 0) {
+            self.spec = spec;
+        }
+        self
+    }
+
+    /// Returns whether this reader recognizes the contents of the specified file.
+    fn valid_file(&self, path: &str) -> bool {
+        unsafe { sys::OIIO_ImageInput_valid_file(self.ptr, path.as_stringref()) }
+    }
+
+    /// Determines the format of the image file at the specified path, or returns `None` if no
+    /// reader recognizes it.
+    ///
+    /// Only the header of the file is inspected: no pixels are decoded. The reader matching the
+    /// file extension is tried first, then all other readers, so that files with a wrong
+    /// extension are still recognized.
+    pub fn probe<P: AsRef<Path>>(path: P) -> Option<FormatInfo> {
+        let path = path.as_ref().to_str().unwrap();
+        let info = |reader: ImageInput, extension_matches| FormatInfo {
+            format_name: reader.format_name().to_owned(),
+            capabilities: InputCapability::ALL
+                .iter()
+                .copied()
+                .filter(|&cap| reader.supports(cap))
+                .collect(),
+            extension_matches,
+        };
+
+        let by_extension = ImageInput::create_reader(path);
+        let extension_format = by_extension.as_ref().map(|r| r.format_name().to_owned());
+        if let Some(reader) = by_extension {
+            if reader.valid_file(path) {
+                return Some(info(reader, true));
+            }
+        }
+
+        let formats = unsafe { cstring_to_owned(sys::OIIO_input_format_list()) };
+        formats
+            .split(',')
+            .filter(|&format| Some(format) != extension_format.as_deref())
+            .filter_map(ImageInput::create_reader)
+            .find(|reader| reader.valid_file(path))
+            .map(|reader| info(reader, false))
+    }
+
+    /// Returns the name of the format of this image (e.g. `"openexr"` or `"png"`).
+    pub fn format_name(&self) -> &str {
+        unsafe {
+            CStr::from_ptr(sys::OIIO_ImageInput_format_name(self.ptr))
+                .to_str()
+                .unwrap()
+        }
+    }
+
+    /// Returns whether the reader of this image supports the specified capability.
+    pub fn supports(&self, capability: InputCapability) -> bool {
+        unsafe { sys::OIIO_ImageInput_supports(self.ptr, capability.name().as_stringref()) }
+    }
+
+    /// Returns the metadata of the first subimage (at the highest-resolution MIP level) of this
+    /// image.
+    ///
+    /// Use [SubimageInput::spec] to get the metadata of another subimage or MIP level.
     pub fn spec(&self) -> &ImageSpec {
-        unsafe { &*(sys::OIIO_ImageInput_spec(self.ptr) as *const ImageSpec) }
+        &self.spec
     }
 
     /// Returns the width of this image.
@@ -82,38 +270,66 @@ impl ImageInput {
         self.spec().channels_by_name(channel_names)
     }
 
+    /// Returns a copy of the metadata of the specified subimage and MIP level,
+    /// or `None` if it does not exist.
+    fn spec_level(&self, subimage: usize, miplevel: usize) -> Option<ImageSpecOwned> {
+        let ptr =
+            unsafe { sys::OIIO_ImageInput_spec_level(self.ptr, subimage as i32, miplevel as i32) };
+        if ptr.is_null() {
+            None
+        } else {
+            Some(ImageSpecOwned(ptr))
+        }
+    }
+
+    /// Selects the first subimage (at the highest-resolution MIP level) of this image.
+    pub fn subimage_0(&self) -> Result<SubimageInput<'_>, Error> {
+        self.subimage(0)
+    }
+
+    /// Selects the specified subimage (at the highest-resolution MIP level) of this image.
+    pub fn subimage(&self, subimage: usize) -> Result<SubimageInput<'_>, Error> {
+        let spec = self
+            .spec_level(subimage, 0)
+            .ok_or(Error::SubimageNotFound)?;
+        Ok(SubimageInput {
+            input: self,
+            subimage,
+            miplevel: 0,
+            spec,
+        })
+    }
+
+    /// Selects the specified subimage and MIP level of this image.
+    pub fn subimage_miplevel(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+    ) -> Result<SubimageInput<'_>, Error> {
+        self.subimage(subimage)?.miplevel(miplevel)
+    }
+
+    /// Returns an iterator over the subimages of this image (at the highest-resolution MIP
+    /// level).
+    pub fn subimages(&self) -> impl Iterator<Item = SubimageInput<'_>> + '_ {
+        (0..).map_while(move |subimage| self.subimage(subimage).ok())
+    }
+
+    /// Reads the specified channels of a subimage and MIP level.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.read(channels)`.
     pub fn read<T: ImageData>(
         &self,
         subimage: usize,
         miplevel: usize,
         channels: Range<usize>,
     ) -> Result<ImageBuffer<T>, Error> {
-        let spec = self.spec();
-        let n = (spec.width() * spec.height() * spec.depth()) as usize * self.spec().num_channels();
-
-        let mut data: Vec<T> = Vec::with_capacity(n);
-
-        unsafe {
-            self.read_unchecked(
-                subimage,
-                miplevel,
-                channels.clone(),
-                T::DESC,
-                data.as_mut_ptr() as *mut u8,
-                channels.len() * mem::size_of::<T>(),
-            )?;
-            data.set_len(n);
-
-            Ok(ImageBuffer {
-                width: self.width() as usize,
-                height: self.height() as usize,
-                depth: self.depth() as usize,
-                data,
-                num_channels: channels.len(),
-            })
-        }
+        self.subimage_miplevel(subimage, miplevel)?.read(channels)
     }
 
+    /// Reads the specified channels of a subimage and MIP level into an existing buffer.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.read_into(channels, out)`.
     pub fn read_into<T: ImageData>(
         &self,
         subimage: usize,
@@ -121,24 +337,23 @@ impl ImageInput {
         channels: Range<usize>,
         out: &mut [T],
     ) -> Result<(), Error> {
-        if self.spec().width() as usize
-            * self.spec().height() as usize
-            * self.spec().depth() as usize
-            > out.len()
-        {
-            return Err(Error::BufferTooSmall);
-        }
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_into(channels, out)
+    }
 
-        unsafe {
-            self.read_unchecked(
-                subimage,
-                miplevel,
-                channels.clone(),
-                T::DESC,
-                out.as_mut_ptr() as *mut u8,
-                channels.len() * mem::size_of::<T>(),
-            )
-        }
+    /// Reads the specified channels of a subimage and MIP level, reporting progress.
+    ///
+    /// Shorthand for
+    /// `self.subimage_miplevel(subimage, miplevel)?.read_with_progress(channels, progress)`.
+    pub fn read_with_progress<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        channels: Range<usize>,
+        progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<ImageBuffer<T>, Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_with_progress(channels, progress)
     }
 
     /// xstride: number of elements (T) to skip between pixels in a row
@@ -151,9 +366,25 @@ impl ImageInput {
         out: *mut u8,
         xstride: usize,
     ) -> Result<(), Error> {
-        let mut success = true;
+        let strides = [xstride as isize, sys::OIIO_AutoStride, sys::OIIO_AutoStride];
+        self.read_image_raw(subimage, miplevel, channels, typedesc, out, strides, None)
+    }
 
-        success &= sys::OIIO_ImageInput_read_image(
+    /// Same as [ImageInput::read_unchecked], with an optional progress callback.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn read_image_raw(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        channels: Range<usize>,
+        typedesc: TypeDesc,
+        out: *mut u8,
+        strides: [isize; 3],
+        progress: Option<&mut dyn FnMut(f32) -> ControlFlow<()>>,
+    ) -> Result<(), Error> {
+        let mut progress = progress.map(Progress::new);
+        let (callback, callback_data) = progress::raw_callback(progress.as_mut());
+        let success = sys::OIIO_ImageInput_read_image(
             self.ptr,
             subimage as i32,
             miplevel as i32,
@@ -161,118 +392,843 @@ impl ImageInput {
             channels.end as i32,
             typedesc.0,
             out as *mut c_void,
-            xstride as isize,
-            sys::OIIO_AutoStride,
-            sys::OIIO_AutoStride,
+            strides[0],
+            strides[1],
+            strides[2],
+            callback,
+            callback_data,
         );
+        progress::finish(progress, success, || {
+            Error::ReadError(self.get_last_error())
+        })
+    }
 
-        if success {
-            Ok(())
-        } else {
-            Err(Error::ReadError(self.get_last_error()))
-        }
+    /// Reads the specified channels of a subimage and MIP level into an existing buffer with
+    /// the specified strides.
+    ///
+    /// Shorthand for
+    /// `self.subimage_miplevel(subimage, miplevel)?.read_into_strided(channels, out, strides)`.
+    pub fn read_into_strided<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        channels: Range<usize>,
+        out: &mut [T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_into_strided(channels, out, strides)
     }
 
+    /// Reads the tiles covering the specified region of a subimage and MIP level into an
+    /// existing buffer.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.read_tiles_into(roi, out)`.
     pub fn read_tiles_into<T: ImageData>(
         &self,
         subimage: usize,
         miplevel: usize,
-        x: Range<u32>,
+        roi: Roi,
+        out: &mut [T],
+    ) -> Result<(), Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_tiles_into(roi, out)
+    }
+
+    /// Reads the tiles covering the specified region of a subimage and MIP level into an
+    /// existing buffer with the specified strides.
+    ///
+    /// Shorthand for
+    /// `self.subimage_miplevel(subimage, miplevel)?.read_tiles_into_strided(roi, out, strides)`.
+    pub fn read_tiles_into_strided<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        roi: Roi,
+        out: &mut [T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_tiles_into_strided(roi, out, strides)
+    }
+
+    /// Reads a range of scanlines of a subimage and MIP level into an existing buffer.
+    ///
+    /// Shorthand for
+    /// `self.subimage_miplevel(subimage, miplevel)?.read_scanlines_into(y, z, channels, out)`.
+    pub fn read_scanlines_into<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
         y: Range<u32>,
-        z: Range<u32>,
+        z: u32,
         channels: Range<usize>,
         out: &mut [T],
     ) -> Result<(), Error> {
-        let tile_size = {
-            let spec = self.spec();
-            spec.tile_width() as usize * spec.tile_height() as usize * spec.num_channels()
-        };
-        if tile_size == 0 {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_scanlines_into(y, z, channels, out)
+    }
+
+    /// Reads a range of scanlines of a subimage and MIP level into an existing buffer with the
+    /// specified strides.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?
+    /// .read_scanlines_into_strided(y, z, channels, out, strides)`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_scanlines_into_strided<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        y: Range<u32>,
+        z: u32,
+        channels: Range<usize>,
+        out: &mut [T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_scanlines_into_strided(y, z, channels, out, strides)
+    }
+
+    /// Reads a range of scanlines of a deep subimage and MIP level.
+    ///
+    /// Shorthand for
+    /// `self.subimage_miplevel(subimage, miplevel)?.read_native_deep_scanlines(y, z, channels)`.
+    pub fn read_native_deep_scanlines(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        y: Range<i32>,
+        z: i32,
+        channels: Range<usize>,
+    ) -> Result<DeepData, Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_native_deep_scanlines(y, z, channels)
+    }
+
+    /// Reads the tiles covering the specified region of a deep subimage and MIP level.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.read_native_deep_tiles(roi)`.
+    pub fn read_native_deep_tiles(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        roi: Roi,
+    ) -> Result<DeepData, Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_native_deep_tiles(roi)
+    }
+
+    /// Reads all pixels of a deep subimage and MIP level.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.read_native_deep_image()`.
+    pub fn read_native_deep_image(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+    ) -> Result<DeepData, Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .read_native_deep_image()
+    }
+
+    /// Returns an iterator over the tiles of a subimage and MIP level, in file order.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.tiles(channels)`.
+    pub fn tiles<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        channels: Range<usize>,
+    ) -> Result<Tiles<'_, T>, Error> {
+        self.subimage_miplevel(subimage, miplevel)?.tiles(channels)
+    }
+
+    /// Returns a reader of successive strips of scanlines of a subimage and MIP level.
+    ///
+    /// Shorthand for `self.subimage_miplevel(subimage, miplevel)?.strips(rows_per_strip, channels)`.
+    pub fn strips<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        rows_per_strip: usize,
+        channels: Range<usize>,
+    ) -> Result<Strips<'_, T>, Error> {
+        self.subimage_miplevel(subimage, miplevel)?
+            .strips(rows_per_strip, channels)
+    }
+
+    /// Reads all tiles of a subimage and MIP level in parallel, and calls `f` with each tile.
+    ///
+    /// Each of the `nthreads` worker threads (`0` means one per CPU) opens the file again and
+    /// decodes its own tiles, so only a few tiles are in memory at any time. Tiles are passed
+    /// to `f` in no particular order, possibly concurrently. Reading stops at the first error.
+    ///
+    /// Returns [Error::InvalidForImageType] if the image is not tiled, or if this input does
+    /// not read from a file, and the errors of [ImageSpec::channel_range] if `channels` is
+    /// empty or out of bounds.
+    pub fn par_read_tiles<T: ImageData>(
+        &self,
+        subimage: usize,
+        miplevel: usize,
+        channels: Range<usize>,
+        nthreads: usize,
+        f: impl Fn(Tile<T>) + Sync,
+    ) -> Result<(), Error> {
+        let input = self.subimage_miplevel(subimage, miplevel)?;
+        tiles::par_read_tiles(self, &input, channels, nthreads, f)
+    }
+
+    pub(crate) fn get_last_error(&self) -> String {
+        unsafe { cstring_to_owned(sys::OIIO_ImageInput_geterror(self.ptr)) }
+    }
+}
+
+impl Drop for ImageInput {
+    fn drop(&mut self) {
+        unsafe {
+            sys::OIIO_ImageInput_delete(self.ptr);
+        }
+    }
+}
+
+/// An optional capability of an image reader, queried with [ImageInput::supports].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum InputCapability {
+    /// The reader can read from memory or custom streams instead of files.
+    IoProxy,
+    /// The reader can read EXIF metadata.
+    Exif,
+    /// The reader can read IPTC metadata.
+    Iptc,
+    /// The reader can read thumbnails embedded in the file.
+    Thumbnail,
+    /// The format stores metadata only, without pixels.
+    NoImage,
+    /// The format can store arbitrarily named metadata.
+    ArbitraryMetadata,
+    /// The reader generates images procedurally instead of reading a file.
+    Procedural,
+}
+
+impl InputCapability {
+    /// All capabilities.
+    pub const ALL: [InputCapability; 7] = [
+        InputCapability::IoProxy,
+        InputCapability::Exif,
+        InputCapability::Iptc,
+        InputCapability::Thumbnail,
+        InputCapability::NoImage,
+        InputCapability::ArbitraryMetadata,
+        InputCapability::Procedural,
+    ];
+
+    /// Returns the OpenImageIO name of this capability.
+    pub fn name(self) -> &'static str {
+        match self {
+            InputCapability::IoProxy => "ioproxy",
+            InputCapability::Exif => "exif",
+            InputCapability::Iptc => "iptc",
+            InputCapability::Thumbnail => "thumbnail",
+            InputCapability::NoImage => "noimage",
+            InputCapability::ArbitraryMetadata => "arbitrary_metadata",
+            InputCapability::Procedural => "procedural",
+        }
+    }
+}
+
+/// Format of an image file, returned by [ImageInput::probe].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FormatInfo {
+    /// Name of the format (e.g. `"openexr"` or `"png"`).
+    pub format_name: String,
+    /// Capabilities of the reader for this format.
+    pub capabilities: Vec<InputCapability>,
+    /// Whether the extension of the file matches its format.
+    pub extension_matches: bool,
+}
+
+/// Configuration hints passed to the image reader by [ImageInput::open_with].
+///
+/// Readers ignore the hints that they don't understand. Hints that have no dedicated method
+/// (such as format-specific `exr:` or `tiff:` hints) can be set with [OpenOptions::hint].
+///
+/// # Example
+///
+/// ```no_run
+/// use openimageio::{ImageInput, OpenOptions};
+///
+/// let input = ImageInput::open_with(
+///     "photo.cr2",
+///     OpenOptions::new()
+///         .unassociated_alpha(true)
+///         .raw_demosaic("AHD")
+///         .raw_exposure(1.5),
+/// )
+/// .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct OpenOptions {
+    config: ImageSpecOwned,
+}
+
+impl OpenOptions {
+    /// Creates a set of options with no hints.
+    pub fn new() -> OpenOptions {
+        OpenOptions {
+            config: ImageSpecOwned::new(),
+        }
+    }
+
+    /// Sets an arbitrary hint.
+    pub fn hint<A: AttributeType>(&mut self, name: &str, value: A) -> &mut OpenOptions {
+        self.config.set_attribute(name, value);
+        self
+    }
+
+    /// Sets whether images with unassociated alpha should be left unassociated instead of
+    /// being premultiplied on read (`oiio:UnassociatedAlpha`).
+    pub fn unassociated_alpha(&mut self, unassociated: bool) -> &mut OpenOptions {
+        self.hint("oiio:UnassociatedAlpha", unassociated as i32)
+    }
+
+    /// Sets whether color values should be returned as stored in the file, without any color
+    /// transformation by the reader (`oiio:RawColor`).
+    pub fn raw_color(&mut self, raw: bool) -> &mut OpenOptions {
+        self.hint("oiio:RawColor", raw as i32)
+    }
+
+    /// Sets whether the reader should reorient the image according to its orientation metadata
+    /// (`oiio:reorient`).
+    pub fn reorient(&mut self, reorient: bool) -> &mut OpenOptions {
+        self.hint("oiio:reorient", reorient as i32)
+    }
+
+    /// Sets the demosaicing algorithm used for camera RAW files (`raw:Demosaic`), e.g.
+    /// `"linear"`, `"VNG"`, `"PPG"`, `"AHD"` or `"none"`.
+    pub fn raw_demosaic(&mut self, algorithm: &str) -> &mut OpenOptions {
+        self.hint("raw:Demosaic", algorithm)
+    }
+
+    /// Sets the output color space of camera RAW files (`raw:ColorSpace`), e.g. `"raw"`,
+    /// `"sRGB"`, `"Adobe"`, `"Wide"`, `"ProPhoto"`, `"XYZ"` or `"ACES"`.
+    pub fn raw_color_space(&mut self, color_space: &str) -> &mut OpenOptions {
+        self.hint("raw:ColorSpace", color_space)
+    }
+
+    /// Sets the exposure correction applied to camera RAW files before demosaicing
+    /// (`raw:Exposure`), as a linear scale factor.
+    pub fn raw_exposure(&mut self, exposure: f32) -> &mut OpenOptions {
+        self.hint("raw:Exposure", exposure)
+    }
+
+    /// Sets whether PNG images should be premultiplied in linear space rather than in their
+    /// stored (gamma-encoded) space (`png:linear_premult`).
+    pub fn png_linear_premult(&mut self, linear: bool) -> &mut OpenOptions {
+        self.hint("png:linear_premult", linear as i32)
+    }
+
+    /// Returns the configuration spec holding the hints.
+    pub fn config(&self) -> &ImageSpec {
+        &self.config
+    }
+}
+
+impl Default for OpenOptions {
+    fn default() -> OpenOptions {
+        OpenOptions::new()
+    }
+}
+
+/// A subimage and MIP level of an [ImageInput], selected with [ImageInput::subimage] or
+/// [SubimageInput::miplevel].
+///
+/// Unlike [ImageInput::spec], which describes the first subimage, the metadata of this object
+/// describes the selected subimage and MIP level, and read methods size their output from it.
+#[derive(Clone)]
+pub struct SubimageInput<'a> {
+    pub(crate) input: &'a ImageInput,
+    subimage: usize,
+    miplevel: usize,
+    spec: ImageSpecOwned,
+}
+
+impl<'a> SubimageInput<'a> {
+    /// Returns the index of this subimage.
+    pub fn subimage_index(&self) -> usize {
+        self.subimage
+    }
+
+    /// Returns the index of this MIP level.
+    pub fn miplevel_index(&self) -> usize {
+        self.miplevel
+    }
+
+    /// Selects another MIP level of this subimage.
+    pub fn miplevel(&self, miplevel: usize) -> Result<SubimageInput<'a>, Error> {
+        let spec = self
+            .input
+            .spec_level(self.subimage, miplevel)
+            .ok_or(Error::MipLevelNotFound)?;
+        Ok(SubimageInput {
+            input: self.input,
+            subimage: self.subimage,
+            miplevel,
+            spec,
+        })
+    }
+
+    /// Returns an iterator over the MIP levels of this subimage, starting from the
+    /// highest-resolution level.
+    pub fn miplevels(&self) -> impl Iterator<Item = SubimageInput<'a>> + 'a {
+        let input = self.input;
+        let subimage = self.subimage;
+        (0..).map_while(move |miplevel| input.subimage_miplevel(subimage, miplevel).ok())
+    }
+
+    /// Returns the metadata of this subimage and MIP level.
+    pub fn spec(&self) -> &ImageSpec {
+        &self.spec
+    }
+
+    /// Returns the width of this subimage and MIP level.
+    ///
+    /// Equivalent to `spec().width()`.
+    pub fn width(&self) -> u32 {
+        self.spec().width()
+    }
+
+    /// Returns the height of this subimage and MIP level.
+    ///
+    /// Equivalent to `spec().height()`.
+    pub fn height(&self) -> u32 {
+        self.spec().height()
+    }
+
+    /// Returns the depth of this subimage and MIP level.
+    ///
+    /// Equivalent to `spec().depth()`.
+    pub fn depth(&self) -> u32 {
+        self.spec().depth()
+    }
+
+    /// Returns the range of all channels of this subimage.
+    /// Shorthand for `self.spec().all_channels()`.
+    pub fn all_channels(&self) -> Range<usize> {
+        self.spec().all_channels()
+    }
+
+    /// Returns the range of channel indices corresponding to the RGBA channels (in this order).
+    /// Shorthand for `self.spec().rgba_channels()`.
+    pub fn rgba_channels(&self) -> Result<Range<usize>, Error> {
+        self.spec().rgba_channels()
+    }
+
+    /// Returns the range of channels corresponding to the given channel names.
+    ///
+    /// Shorthand for `self.spec().channels_by_name(channel_names)`.
+    pub fn channels_by_name(&self, channel_names: &[&str]) -> Result<Range<usize>, Error> {
+        self.spec().channels_by_name(channel_names)
+    }
+
+    /// Returns the number of pixels of this subimage and MIP level.
+    fn num_pixels(&self) -> usize {
+        self.width() as usize * self.height() as usize * self.depth() as usize
+    }
+
+    fn contiguous_strides(&self, num_channels: usize) -> Strides {
+        Strides::contiguous(num_channels, self.width() as usize, self.height() as usize)
+    }
+
+    /// Reads the specified channels of this subimage and MIP level.
+    ///
+    /// Returns the errors of [ImageSpec::channel_range] if `channels` is empty or out of
+    /// bounds. The same applies to the other read methods.
+    pub fn read<T: ImageData>(&self, channels: Range<usize>) -> Result<ImageBuffer<T>, Error> {
+        self.read_impl(channels, None)
+    }
+
+    /// Reads the specified channels of this subimage and MIP level, reporting progress.
+    ///
+    /// `progress` is called periodically with the portion of the image that was read, between
+    /// 0 and 1. If it returns [ControlFlow::Break], reading stops and [Error::Cancelled] is
+    /// returned.
+    pub fn read_with_progress<T: ImageData>(
+        &self,
+        channels: Range<usize>,
+        mut progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<ImageBuffer<T>, Error> {
+        self.read_impl(channels, Some(&mut progress))
+    }
+
+    fn read_impl<T: ImageData>(
+        &self,
+        channels: Range<usize>,
+        progress: Option<&mut dyn FnMut(f32) -> ControlFlow<()>>,
+    ) -> Result<ImageBuffer<T>, Error> {
+        // OIIO clamps the channels to those of the image: the buffer would not match
+        let channels = self.spec().channel_range(channels)?;
+        let n = self.num_pixels() * channels.len();
+        let mut data: Vec<T> = Vec::with_capacity(n);
+
+        unsafe {
+            self.input.read_image_raw(
+                self.subimage,
+                self.miplevel,
+                channels.clone(),
+                T::DESC,
+                data.as_mut_ptr() as *mut u8,
+                self.contiguous_strides(channels.len()).in_bytes::<T>(),
+                progress,
+            )?;
+            data.set_len(n);
+        }
+
+        Ok(ImageBuffer {
+            width: self.width() as usize,
+            height: self.height() as usize,
+            depth: self.depth() as usize,
+            data,
+            num_channels: channels.len(),
+        })
+    }
+
+    /// Reads the specified channels of this subimage and MIP level into an existing buffer.
+    pub fn read_into<T: ImageData>(
+        &self,
+        channels: Range<usize>,
+        out: &mut [T],
+    ) -> Result<(), Error> {
+        let strides = self.contiguous_strides(channels.len());
+        self.read_into_impl(channels, out, strides, None)
+    }
+
+    /// Reads the specified channels of this subimage and MIP level into an existing buffer
+    /// with the specified strides.
+    ///
+    /// This can read into a region of a larger image, or into a buffer with padded rows.
+    /// Returns [Error::BufferTooSmall] if pixels would be written outside of `out`.
+    pub fn read_into_strided<T: ImageData>(
+        &self,
+        channels: Range<usize>,
+        out: &mut [T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        self.read_into_impl(channels, out, strides, None)
+    }
+
+    /// Same as [SubimageInput::read_into], but reports progress like
+    /// [SubimageInput::read_with_progress].
+    pub fn read_into_with_progress<T: ImageData>(
+        &self,
+        channels: Range<usize>,
+        out: &mut [T],
+        mut progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        let strides = self.contiguous_strides(channels.len());
+        self.read_into_impl(channels, out, strides, Some(&mut progress))
+    }
+
+    fn read_into_impl<T: ImageData>(
+        &self,
+        channels: Range<usize>,
+        out: &mut [T],
+        strides: Strides,
+        progress: Option<&mut dyn FnMut(f32) -> ControlFlow<()>>,
+    ) -> Result<(), Error> {
+        let channels = self.spec().channel_range(channels)?;
+        let (width, height, depth) = self.spec().size();
+        let len = strides.buffer_len(
+            channels.len(),
+            width as usize,
+            height as usize,
+            depth as usize,
+        );
+        if len > out.len() {
+            return Err(Error::BufferTooSmall);
+        }
+
+        unsafe {
+            self.input.read_image_raw(
+                self.subimage,
+                self.miplevel,
+                channels.clone(),
+                T::DESC,
+                out.as_mut_ptr() as *mut u8,
+                strides.in_bytes::<T>(),
+                progress,
+            )
+        }
+    }
+
+    /// Reads the tiles covering the specified region into an existing buffer.
+    ///
+    /// The region must be aligned on tile boundaries (or on the edges of the image).
+    /// If `roi` is [Roi::ALL], all tiles and all channels are read. Channels of `roi` past
+    /// those of the image (as with [Roi::new_2d]) are ignored.
+    pub fn read_tiles_into<T: ImageData>(&self, roi: Roi, out: &mut [T]) -> Result<(), Error> {
+        let roi = self.tile_region(roi)?;
+        let strides = Strides::contiguous(
+            roi.num_channels(),
+            roi.width() as usize,
+            roi.height() as usize,
+        );
+        self.read_tiles_into_strided(roi, out, strides)
+    }
+
+    /// Reads the tiles covering the specified region into an existing buffer with the
+    /// specified strides.
+    ///
+    /// Same as [SubimageInput::read_tiles_into], but returns [Error::BufferTooSmall] if pixels
+    /// would be written outside of `out`.
+    pub fn read_tiles_into_strided<T: ImageData>(
+        &self,
+        roi: Roi,
+        out: &mut [T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        if self.spec().tile_width() == 0 {
             // scanline image
             return Err(Error::InvalidForImageType);
         }
-        if x.len() * y.len() * z.len() * channels.len() > out.len() {
+        let roi = self.tile_region(roi)?;
+        let len = strides.buffer_len(
+            roi.num_channels(),
+            roi.width() as usize,
+            roi.height() as usize,
+            roi.depth() as usize,
+        );
+        if len > out.len() {
             return Err(Error::BufferTooSmall);
         }
+        unsafe { self.read_tiles_unchecked(roi, out.as_mut_ptr(), strides) }
+    }
 
-        let mut success = true;
-        success &= unsafe {
-            openimageio_sys::OIIO_ImageInput_read_tiles(
-                self.ptr,
-                subimage as i32,
-                miplevel as i32,
-                x.start as i32,
-                x.end as i32,
-                y.start as i32,
-                y.end as i32,
-                z.start as i32,
-                z.end as i32,
-                channels.start as i32,
-                channels.end as i32,
-                T::DESC.0,
-                out.as_mut_ptr() as *mut c_void,
-                sys::OIIO_AutoStride,
-                sys::OIIO_AutoStride,
-                sys::OIIO_AutoStride,
-            )
-        };
+    /// Returns `roi`, or the data window if it is [Roi::ALL], with its channels clamped to
+    /// those of this image.
+    fn tile_region(&self, roi: Roi) -> Result<Roi, Error> {
+        let spec = self.spec();
+        let roi = if roi.is_defined() { roi } else { spec.roi() };
+        let channels = roi.channels();
+        let channels = spec.channel_range(channels.start..channels.end.min(spec.num_channels()))?;
+        Ok(roi.with_channels(channels))
+    }
+
+    /// Reads the tiles covering `roi` (which must be defined) without checking the size of the
+    /// output buffer.
+    pub(crate) unsafe fn read_tiles_unchecked<T: ImageData>(
+        &self,
+        roi: Roi,
+        out: *mut T,
+        strides: Strides,
+    ) -> Result<(), Error> {
+        let [xstride, ystride, zstride] = strides.in_bytes::<T>();
+        let success = sys::OIIO_ImageInput_read_tiles(
+            self.input.ptr,
+            self.subimage as i32,
+            self.miplevel as i32,
+            roi.0.xbegin,
+            roi.0.xend,
+            roi.0.ybegin,
+            roi.0.yend,
+            roi.0.zbegin,
+            roi.0.zend,
+            roi.0.chbegin,
+            roi.0.chend,
+            T::DESC.0,
+            out as *mut c_void,
+            xstride,
+            ystride,
+            zstride,
+        );
         if success {
             Ok(())
         } else {
-            Err(Error::ReadError(self.get_last_error()))
+            Err(Error::ReadError(self.input.get_last_error()))
         }
     }
 
+    /// Returns an iterator over the tiles of this subimage and MIP level, in file order.
+    ///
+    /// Tiles on the right, bottom and back edges are clipped to the data window, so their
+    /// data may be smaller than a full tile. Each tile is read when the iterator is advanced.
+    ///
+    /// Returns [Error::InvalidForImageType] if the image is not tiled, and the errors of
+    /// [ImageSpec::channel_range] if `channels` is empty or out of bounds.
+    pub fn tiles<T: ImageData>(&self, channels: Range<usize>) -> Result<Tiles<'a, T>, Error> {
+        Tiles::new(self.clone(), channels)
+    }
+
+    /// Returns a reader of successive strips of `rows_per_strip` scanlines of this subimage and
+    /// MIP level, from top to bottom.
+    ///
+    /// This works for both scanline and tiled images, and reads one strip at a time into a
+    /// single buffer. Returns [Error::InvalidParameter] if `rows_per_strip` is zero,
+    /// [Error::InvalidForImageType] for volume images, and the errors of
+    /// [ImageSpec::channel_range] if `channels` is empty or out of bounds.
+    pub fn strips<T: ImageData>(
+        &self,
+        rows_per_strip: usize,
+        channels: Range<usize>,
+    ) -> Result<Strips<'a, T>, Error> {
+        Strips::new(self.clone(), rows_per_strip, channels)
+    }
+
+    /// Reads a range of scanlines into an existing buffer.
     pub fn read_scanlines_into<T: ImageData>(
         &self,
-        subimage: usize,
-        miplevel: usize,
         y: Range<u32>,
         z: u32,
         channels: Range<usize>,
         out: &mut [T],
+    ) -> Result<(), Error> {
+        let strides = self.contiguous_strides(channels.len());
+        self.read_scanlines_into_strided(y, z, channels, out, strides)
+    }
+
+    /// Reads a range of scanlines into an existing buffer with the specified strides.
+    ///
+    /// The z stride is not used. Returns [Error::BufferTooSmall] if pixels would be written
+    /// outside of `out`.
+    pub fn read_scanlines_into_strided<T: ImageData>(
+        &self,
+        y: Range<u32>,
+        z: u32,
+        channels: Range<usize>,
+        out: &mut [T],
+        strides: Strides,
     ) -> Result<(), Error> {
         if self.spec().tile_width() != 0 {
             // tile image
             return Err(Error::InvalidForImageType);
         }
-        let scanline_size = self.spec().width() * channels.len() as u32;
-        if scanline_size > out.len() as u32 {
+        let channels = self.spec().channel_range(channels)?;
+        let len = strides.buffer_len(channels.len(), self.width() as usize, y.len(), 1);
+        if len > out.len() {
             return Err(Error::BufferTooSmall);
         }
-        let mut success = true;
-        success &= unsafe {
-            openimageio_sys::OIIO_ImageInput_read_scanlines(
-                self.ptr,
-                subimage as i32,
-                miplevel as i32,
-                y.start as i32,
-                y.end as i32,
+        unsafe {
+            self.read_scanlines_unchecked(
+                y.start as i32..y.end as i32,
                 z as i32,
-                channels.start as i32,
-                channels.end as i32,
-                T::DESC.0,
-                out.as_mut_ptr() as *mut c_void,
-                sys::OIIO_AutoStride,
-                sys::OIIO_AutoStride,
+                channels,
+                out.as_mut_ptr(),
+                strides,
             )
-        };
+        }
+    }
+
+    /// Reads a range of scanlines without checking the size of the output buffer.
+    ///
+    /// This also works on tiled images.
+    pub(crate) unsafe fn read_scanlines_unchecked<T: ImageData>(
+        &self,
+        y: Range<i32>,
+        z: i32,
+        channels: Range<usize>,
+        out: *mut T,
+        strides: Strides,
+    ) -> Result<(), Error> {
+        let [xstride, ystride, _] = strides.in_bytes::<T>();
+        let success = sys::OIIO_ImageInput_read_scanlines(
+            self.input.ptr,
+            self.subimage as i32,
+            self.miplevel as i32,
+            y.start,
+            y.end,
+            z,
+            channels.start as i32,
+            channels.end as i32,
+            T::DESC.0,
+            out as *mut c_void,
+            xstride,
+            ystride,
+        );
         if success {
             Ok(())
         } else {
-            Err(Error::ReadError(self.get_last_error()))
+            Err(Error::ReadError(self.input.get_last_error()))
         }
     }
 
-    fn get_last_error(&self) -> String {
-        unsafe { cstring_to_owned(sys::OIIO_ImageInput_geterror(self.ptr)) }
+    /// Reads a range of scanlines of a deep image.
+    ///
+    /// `y` and `z` are coordinates in the data window, which may be negative. Returns
+    /// [Error::InvalidForImageType] if the image is not deep.
+    pub fn read_native_deep_scanlines(
+        &self,
+        y: Range<i32>,
+        z: i32,
+        channels: Range<usize>,
+    ) -> Result<DeepData, Error> {
+        self.read_deep(|ptr| unsafe {
+            sys::OIIO_ImageInput_read_native_deep_scanlines(
+                self.input.ptr,
+                self.subimage as i32,
+                self.miplevel as i32,
+                y.start,
+                y.end,
+                z,
+                channels.start as i32,
+                channels.end as i32,
+                ptr,
+            )
+        })
     }
-}
 
-impl Drop for ImageInput {
-    fn drop(&mut self) {
-        unsafe {
-            sys::OIIO_ImageInput_delete(self.ptr);
+    /// Reads the tiles of a deep image covering the specified region.
+    ///
+    /// The region must be aligned on tile boundaries (or on the edges of the image).
+    /// If `roi` is [Roi::ALL], all tiles and all channels are read.
+    /// Returns [Error::InvalidForImageType] if the image is not deep or not tiled.
+    pub fn read_native_deep_tiles(&self, roi: Roi) -> Result<DeepData, Error> {
+        if self.spec().tile_width() == 0 {
+            // scanline image
+            return Err(Error::InvalidForImageType);
+        }
+        let roi = self.tile_region(roi)?;
+        self.read_deep(|ptr| unsafe {
+            sys::OIIO_ImageInput_read_native_deep_tiles(
+                self.input.ptr,
+                self.subimage as i32,
+                self.miplevel as i32,
+                roi.0.xbegin,
+                roi.0.xend,
+                roi.0.ybegin,
+                roi.0.yend,
+                roi.0.zbegin,
+                roi.0.zend,
+                roi.0.chbegin,
+                roi.0.chend,
+                ptr,
+            )
+        })
+    }
+
+    /// Reads all pixels of a deep image.
+    ///
+    /// Returns [Error::InvalidForImageType] if the image is not deep.
+    pub fn read_native_deep_image(&self) -> Result<DeepData, Error> {
+        self.read_deep(|ptr| unsafe {
+            sys::OIIO_ImageInput_read_native_deep_image(
+                self.input.ptr,
+                self.subimage as i32,
+                self.miplevel as i32,
+                ptr,
+            )
+        })
+    }
+
+    fn read_deep(
+        &self,
+        f: impl FnOnce(*mut sys::OIIO_DeepData) -> bool,
+    ) -> Result<DeepData, Error> {
+        if !self.spec().is_deep() {
+            return Err(Error::InvalidForImageType);
+        }
+        let data = DeepData::default();
+        if f(data.ptr) {
+            Ok(data)
+        } else {
+            Err(Error::ReadError(self.input.get_last_error()))
         }
     }
 }
@@ -0,0 +1,225 @@
+//! Custom I/O for image readers and writers, on top of OpenImageIO's `IOProxy`.
+use crate::Error;
+use openimageio_sys as sys;
+use std::{
+    ffi::c_void,
+    io::{self, Read, Seek, SeekFrom, Write},
+    panic::{self, AssertUnwindSafe},
+    slice,
+    sync::Mutex,
+};
+
+/// Streams that can be read by an image reader.
+pub(crate) trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// Streams that can be written by an image writer.
+pub(crate) trait WriteSeek: Write + Seek + Send {}
+
+impl<T: Write + Seek + Send> WriteSeek for T {}
+
+/// Owned `IOProxy`, with the data it reads from.
+pub(crate) struct IoProxy {
+    pub(crate) ptr: *mut sys::OIIO_IOProxy,
+    _data: Option<Box<[u8]>>,
+    // double boxed because the proxy needs a thin pointer
+    _reader: Option<Box<Mutex<Box<dyn ReadSeek>>>>,
+    writer: Option<Box<Mutex<ProxyWriter>>>,
+    vec_output: bool,
+}
+
+/// Stream written by a proxy, with the first error it returned: the proxy only reports short
+/// writes to the writer.
+struct ProxyWriter {
+    stream: Box<dyn WriteSeek>,
+    error: Option<io::Error>,
+}
+
+// The readers and writers are `Send` and behind a mutex.
+unsafe impl Send for IoProxy {}
+
+impl IoProxy {
+    /// Creates a proxy reading from a copy of the specified data.
+    pub(crate) fn from_memory(data: &[u8]) -> IoProxy {
+        let data: Box<[u8]> = data.into();
+        let ptr =
+            unsafe { sys::OIIO_IOProxy_new_memreader(data.as_ptr() as *const c_void, data.len()) };
+        IoProxy {
+            ptr,
+            _data: Some(data),
+            _reader: None,
+            writer: None,
+            vec_output: false,
+        }
+    }
+
+    /// Creates a proxy reading from the specified stream.
+    pub(crate) fn from_reader(mut reader: Box<dyn ReadSeek>) -> Result<IoProxy, Error> {
+        let size = reader
+            .seek(SeekFrom::End(0))
+            .and_then(|size| reader.seek(SeekFrom::Start(0)).map(|_| size))
+            .map_err(|err| Error::ReadError(err.to_string()))?;
+        let reader = Box::new(Mutex::new(reader));
+        let ptr = unsafe {
+            sys::OIIO_IOProxy_new_reader(
+                &*reader as *const Mutex<Box<dyn ReadSeek>> as *mut c_void,
+                Some(read_at),
+                size as i64,
+            )
+        };
+        Ok(IoProxy {
+            ptr,
+            _data: None,
+            _reader: Some(reader),
+            writer: None,
+            vec_output: false,
+        })
+    }
+
+    /// Creates a proxy writing into a growable memory buffer, retrieved with
+    /// [IoProxy::vec_output_data].
+    pub(crate) fn vec_output() -> IoProxy {
+        IoProxy {
+            ptr: unsafe { sys::OIIO_IOProxy_new_vecoutput() },
+            _data: None,
+            _reader: None,
+            writer: None,
+            vec_output: true,
+        }
+    }
+
+    /// Returns the data written into a proxy created with [IoProxy::vec_output], or `None`
+    /// for other proxies.
+    pub(crate) fn vec_output_data(&self) -> Option<&[u8]> {
+        if !self.vec_output {
+            return None;
+        }
+        let data = unsafe {
+            let mut size = 0;
+            let data = sys::OIIO_IOProxy_vecoutput_data(self.ptr, &mut size);
+            if size == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(data, size)
+            }
+        };
+        Some(data)
+    }
+
+    /// Creates a proxy writing to the specified stream.
+    pub(crate) fn from_writer(writer: Box<dyn WriteSeek>) -> IoProxy {
+        let writer = Box::new(Mutex::new(ProxyWriter {
+            stream: writer,
+            error: None,
+        }));
+        let ptr = unsafe {
+            sys::OIIO_IOProxy_new_writer(
+                &*writer as *const Mutex<ProxyWriter> as *mut c_void,
+                Some(write_at),
+            )
+        };
+        IoProxy {
+            ptr,
+            _data: None,
+            _reader: None,
+            writer: Some(writer),
+            vec_output: false,
+        }
+    }
+
+    /// Flushes the stream of a proxy created with [IoProxy::from_writer], or returns the
+    /// first error of the stream if a write failed.
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        match self.writer {
+            Some(ref writer) => {
+                let mut writer = writer.lock().unwrap();
+                match writer.error.take() {
+                    Some(err) => Err(err),
+                    None => writer.stream.flush(),
+                }
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for IoProxy {
+    fn drop(&mut self) {
+        unsafe {
+            sys::OIIO_IOProxy_delete(self.ptr);
+        }
+    }
+}
+
+/// `IOProxy` read callback: reads as many bytes as possible at the specified offset.
+///
+/// I/O errors are reported as short reads.
+unsafe extern "C" fn read_at(
+    opaque: *mut c_void,
+    buf: *mut c_void,
+    size: usize,
+    offset: i64,
+) -> usize {
+    if offset < 0 || size == 0 {
+        return 0;
+    }
+    let reader = &*(opaque as *const Mutex<Box<dyn ReadSeek>>);
+    let buf = slice::from_raw_parts_mut(buf as *mut u8, size);
+    // panics must not unwind into C++
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut reader = match reader.lock() {
+            Ok(reader) => reader,
+            Err(_) => return 0,
+        };
+        if reader.seek(SeekFrom::Start(offset as u64)).is_err() {
+            return 0;
+        }
+        let mut total = 0;
+        while total < size {
+            match reader.read(&mut buf[total..]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+        total
+    }))
+    .unwrap_or(0)
+}
+
+/// `IOProxy` write callback: writes all bytes at the specified offset.
+///
+/// I/O errors are reported as short writes, and the first one is kept for [IoProxy::flush].
+unsafe extern "C" fn write_at(
+    opaque: *mut c_void,
+    buf: *const c_void,
+    size: usize,
+    offset: i64,
+) -> usize {
+    if offset < 0 || size == 0 {
+        return 0;
+    }
+    let writer = &*(opaque as *const Mutex<ProxyWriter>);
+    let buf = slice::from_raw_parts(buf as *const u8, size);
+    // panics must not unwind into C++
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut writer = match writer.lock() {
+            Ok(writer) => writer,
+            Err(_) => return 0,
+        };
+        let stream = &mut writer.stream;
+        match stream
+            .seek(SeekFrom::Start(offset as u64))
+            .and_then(|_| stream.write_all(buf))
+        {
+            Ok(()) => size,
+            Err(err) => {
+                writer.error.get_or_insert(err);
+                0
+            }
+        }
+    }))
+    .unwrap_or(0)
+}
@@ -1,23 +1,48 @@
 use std::{ffi::CStr, os::raw::c_char};
 
+pub mod algo;
 mod attribute;
 mod buffer;
 mod cache;
+pub mod color;
+mod deepdata;
 mod error;
+mod imagebuf;
 mod input;
+mod ioproxy;
 mod output;
+mod progress;
 mod roi;
 mod spec;
+mod strips;
+mod tiles;
+mod transcode;
 mod typedesc;
+mod writeoptions;
 
-pub use buffer::ImageBuffer;
+pub use attribute::AttributeType;
+pub use buffer::{ImageBuffer, Strides};
+pub use deepdata::DeepData;
 pub use error::Error;
-pub use input::ImageInput;
-pub use output::{ImageOutput, MultiImageOutput, SingleImageOutput};
-pub use spec::{Channel, ChannelDesc, ImageSpec, ImageSpecOwned};
+pub use imagebuf::{ImageBuf, WrapMode};
+pub use input::{FormatInfo, ImageInput, InputCapability, OpenOptions, SubimageInput};
+pub use output::{ImageOutput, MultiImageOutput, OutputCapability, SingleImageOutput};
+pub use roi::Roi;
+pub use spec::{Channel, ChannelDesc, ImageSpec, ImageSpecOwned, Window};
+pub use strips::{Strip, Strips};
+pub use tiles::{Tile, Tiles};
+pub use transcode::{transcode, TranscodeOptions};
 pub use typedesc::{Aggregate, BaseType, ImageData, TypeDesc, VecSemantics};
+pub use writeoptions::{
+    DpxOptions, DpxTransfer, ExrCompression, ExrLineOrder, ExrOptions, JpegOptions,
+    JpegSubsampling, PngFilter, PngOptions, TiffCompression, TiffOptions, TiffPlanarConfig,
+    TiffPredictor, WebpOptions, WriteOptions,
+};
 
-pub use cache::ImageCache;
+pub use cache::{
+    CachedImage, CachedSubimageMipmap, CachedSubimageMipmapChannels, ImageCache, SharedCachedImage,
+    SharedImageCache,
+};
 
 unsafe fn cstring_to_owned(cstr: *const c_char) -> String {
     // assume utf8 input
@@ -71,6 +96,109 @@ mod tests {
         assert_eq!(all_scanlines, whole_img.data);
     }
 
+    #[test]
+    fn subimage_navigation() {
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        assert_eq!(img.subimages().count(), 1);
+        assert_eq!(img.subimage(1).err(), Some(Error::SubimageNotFound));
+
+        let subimage = img.subimage_0().unwrap();
+        assert_eq!(subimage.miplevels().count(), 1);
+        assert_eq!(subimage.miplevel(1).err(), Some(Error::MipLevelNotFound));
+        assert_eq!(subimage.spec().size(), img.spec().size());
+
+        let channels = subimage.rgba_channels().unwrap();
+        let data: ImageBuffer<u8> = subimage.read(channels.clone()).unwrap();
+        assert_eq!(
+            data.data().len(),
+            subimage.width() as usize * subimage.height() as usize * channels.len()
+        );
+        let whole_img: ImageBuffer<u8> = img.read(0, 0, channels).unwrap();
+        assert_eq!(data.data(), whole_img.data());
+
+        // channels outside of the image are rejected before reading
+        let nch = subimage.spec().num_channels();
+        assert_eq!(
+            subimage.read::<u8>(0..0).err(),
+            Some(Error::InvalidParameter)
+        );
+        assert_eq!(
+            subimage.read::<u8>(0..nch + 1).err(),
+            Some(Error::ChannelIndexOutOfBounds)
+        );
+        assert_eq!(
+            subimage.read_into::<u8>(0..0, &mut []),
+            Err(Error::InvalidParameter)
+        );
+    }
+
+    #[test]
+    fn open_with_options() {
+        let img = ImageInput::open_with(
+            "test_images/kazeharu.png",
+            OpenOptions::new()
+                .unassociated_alpha(true)
+                .png_linear_premult(false)
+                .hint("oiio:missingcolor", "0,0,0,0"),
+        )
+        .unwrap();
+        let reference = ImageInput::open("test_images/kazeharu.png").unwrap();
+        assert_eq!(img.spec().size(), reference.spec().size());
+        assert_eq!(img.spec().num_channels(), reference.spec().num_channels());
+        img.read::<u8>(0, 0, img.all_channels()).unwrap();
+    }
+
+    #[test]
+    fn probe_format() {
+        let info = ImageInput::probe("test_images/kazeharu.png").unwrap();
+        assert_eq!(info.format_name, "png");
+        assert!(info.extension_matches);
+        assert_eq!(
+            ImageInput::open("test_images/kazeharu.png")
+                .unwrap()
+                .format_name(),
+            "png"
+        );
+
+        std::fs::copy("test_images/tonberry.jpg", "tonberry_misnamed.png").unwrap();
+        let info = ImageInput::probe("tonberry_misnamed.png").unwrap();
+        assert_eq!(info.format_name, "jpeg");
+        assert!(!info.extension_matches);
+        assert!(info.capabilities.contains(&InputCapability::Exif));
+
+        assert_eq!(ImageInput::probe("Cargo.toml"), None);
+    }
+
+    #[test]
+    fn open_from_memory() {
+        let bytes = std::fs::read("test_images/kazeharu.png").unwrap();
+        let reference: ImageBuffer<u8> = ImageInput::open("test_images/kazeharu.png")
+            .unwrap()
+            .subimage_0()
+            .and_then(|s| s.read(s.all_channels()))
+            .unwrap();
+
+        for format_hint in &[Some("png"), None] {
+            let img = ImageInput::open_from_memory(&bytes, *format_hint).unwrap();
+            assert_eq!(img.format_name(), "png");
+            let data: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+            assert_eq!(data.data(), reference.data());
+        }
+
+        let img = ImageInput::open_reader(std::io::Cursor::new(bytes)).unwrap();
+        let data: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        assert_eq!(data.data(), reference.data());
+
+        assert_eq!(
+            ImageInput::open_from_memory(b"not an image", None).err(),
+            Some(Error::UnknownFormat)
+        );
+        assert_eq!(
+            ImageInput::open_from_memory(b"", Some("nosuchformat")).err(),
+            Some(Error::UnsupportedFormat("nosuchformat".to_owned()))
+        );
+    }
+
     #[test]
     fn write_image_scanline() {
         // scanline based png
@@ -79,11 +207,21 @@ mod tests {
 
         let mut out = ImageOutput::create("kazeharu.png").unwrap();
         let mut out = out.open(&img.spec()).unwrap();
+        let short = &data.data()[1..];
+        assert_eq!(out.write_image(short), Err(Error::BufferTooSmall));
+        assert_eq!(
+            out.write_image_with_progress(short, |_| std::ops::ControlFlow::Continue(())),
+            Err(Error::BufferTooSmall)
+        );
         out.write_image(data.data()).unwrap();
 
         let mut out2 = ImageOutput::create("kazeharu_scanline.png").unwrap();
         let mut out2 = out2.open(&img.spec()).unwrap();
         let row_width: usize = data.width * data.num_channels;
+        assert_eq!(
+            out2.write_scanline(0, 0, &data.data[..row_width - 1]),
+            Err(Error::BufferTooSmall)
+        );
         for y in 0..data.height() as usize {
             out2.write_scanline(
                 y as i32,
@@ -109,6 +247,612 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_tiled() {
+        // edge tiles are partially outside the image
+        let (width, height, tile_size) = (40, 24, 16);
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, width, height, &["R", "G", "B"])
+            .with_tile_size(tile_size, tile_size, 1);
+        let value = |x: u32, y: u32, c: u32| (y * width + x) as f32 + c as f32 / 4.0;
+        let expected = (0..height)
+            .flat_map(|y| (0..width).flat_map(move |x| (0..3).map(move |c| value(x, y, c))))
+            .collect::<Vec<_>>();
+
+        let mut output = ImageOutput::create("tiled_by_tile.exr").unwrap();
+        assert!(output.supports(OutputCapability::Tiles));
+        let mut out = output.open(&spec).unwrap();
+        for ty in (0..height).step_by(tile_size as usize) {
+            for tx in (0..width).step_by(tile_size as usize) {
+                let tile = (ty..ty + tile_size)
+                    .flat_map(|y| {
+                        (tx..tx + tile_size).flat_map(move |x| (0..3).map(move |c| value(x, y, c)))
+                    })
+                    .collect::<Vec<_>>();
+                out.write_tile(tx as i32, ty as i32, 0, &tile).unwrap();
+            }
+        }
+        assert_eq!(
+            out.write_tile(0, 0, 0, &[0.0f32; 3]),
+            Err(Error::BufferTooSmall)
+        );
+        out.close();
+        output.close().unwrap();
+
+        let mut output = ImageOutput::create("tiled_region.exr").unwrap();
+        let mut out = output.open(&spec).unwrap();
+        assert_eq!(
+            out.write_tiles(Roi::ALL, &expected[..3]),
+            Err(Error::BufferTooSmall)
+        );
+        out.write_tiles(Roi::ALL, &expected).unwrap();
+        out.close();
+        output.close().unwrap();
+
+        for path in &["tiled_by_tile.exr", "tiled_region.exr"] {
+            let img = ImageInput::open(path).unwrap();
+            assert_eq!(img.spec().tile_width(), tile_size);
+            let data: ImageBuffer<f32> = img.read(0, 0, img.all_channels()).unwrap();
+            assert_eq!(data.data(), &expected[..]);
+        }
+
+        // PNG has no tiles
+        let mut out = ImageOutput::create("tiled.png").unwrap();
+        assert!(!out.supports(OutputCapability::Tiles));
+        let mut out = out.open(&spec).unwrap();
+        assert_eq!(
+            out.write_tile(0, 0, 0, &expected),
+            Err(Error::InvalidForImageType)
+        );
+    }
+
+    #[test]
+    fn write_multi_image() {
+        let beauty = ImageSpecOwned::new_2d(TypeDesc::HALF, 8, 4, &["R", "G", "B", "A"]);
+        let depth = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 8, 4, &["Z"]);
+        let beauty_pixels = vec![0.5f32; 8 * 4 * 4];
+        let depth_pixels = (0..8 * 4).map(|i| i as f32).collect::<Vec<_>>();
+
+        let mut output = ImageOutput::create("multipart.exr").unwrap();
+        let mut out = output.open_multi(&[&beauty, &depth]).unwrap();
+        assert_eq!(out.num_subimages(), 2);
+        out.write_image(&beauty_pixels).unwrap();
+        out.append_subimage(&depth).unwrap();
+        assert_eq!(out.subimage_index(), 1);
+        out.write_image(&depth_pixels).unwrap();
+        assert_eq!(out.append_subimage(&depth), Err(Error::SubimageNotFound));
+        out.close();
+        output.close().unwrap();
+
+        let img = ImageInput::open("multipart.exr").unwrap();
+        // the spec of the image stays the one of the first subimage when others are read
+        let name = img.spec().channel_by_index(0).unwrap().name;
+        assert_eq!(img.subimages().count(), 2);
+        let data: ImageBuffer<f32> = img.read(0, 0, 0..4).unwrap();
+        assert_eq!(data.data(), &beauty_pixels[..]);
+        let data: ImageBuffer<f32> = img.read(1, 0, 0..1).unwrap();
+        assert_eq!(data.data(), &depth_pixels[..]);
+        assert_eq!(name, "R");
+        assert_eq!(img.spec().num_channels(), 4);
+
+        let mut output = ImageOutput::create("multipart_incomplete.exr").unwrap();
+        let mut out = output.open_multi(&[&beauty, &depth]).unwrap();
+        out.write_image(&beauty_pixels).unwrap();
+        out.close();
+        assert_eq!(
+            output.close(),
+            Err(Error::MissingSubimages {
+                declared: 2,
+                written: 1
+            })
+        );
+        // a subimage that is appended but gets no pixels is not written
+        let mut output = ImageOutput::create("multipart_empty.exr").unwrap();
+        let mut out = output.open_multi(&[&beauty, &depth]).unwrap();
+        out.write_image(&beauty_pixels).unwrap();
+        out.append_subimage(&depth).unwrap();
+        out.close();
+        assert_eq!(
+            output.close(),
+            Err(Error::MissingSubimages {
+                declared: 2,
+                written: 1
+            })
+        );
+
+        // MIP chain 16x16, 8x8, ..., 1x1
+        let mut output = ImageOutput::create("mipmapped.exr").unwrap();
+        assert!(output.supports(OutputCapability::MipMap));
+        let level_spec = |size: u32| {
+            let mut spec =
+                ImageSpecOwned::new_2d(TypeDesc::FLOAT, size, size, &["Y"]).with_tile_size(4, 4, 1);
+            spec.set_attribute("textureformat", "Plain Texture");
+            spec
+        };
+        let mut out = output.open(&level_spec(16)).unwrap();
+        let mut size = 16;
+        loop {
+            out.write_image(&vec![size as f32; (size * size) as usize])
+                .unwrap();
+            if size == 1 {
+                break;
+            }
+            size /= 2;
+            out.append_miplevel(&level_spec(size)).unwrap();
+        }
+        out.close();
+        output.close().unwrap();
+
+        let img = ImageInput::open("mipmapped.exr").unwrap();
+        let levels = img.subimage_0().unwrap().miplevels().collect::<Vec<_>>();
+        assert_eq!(levels.len(), 5);
+        for level in &levels {
+            let data: ImageBuffer<f32> = level.read(0..1).unwrap();
+            assert!(data.data().iter().all(|&v| v == level.width() as f32));
+        }
+    }
+
+    #[test]
+    fn write_options() {
+        let img = ImageInput::open("test_images/tonberry.jpg").unwrap();
+        let data: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+
+        let jpeg_size = |quality: u32| {
+            let mut output = ImageOutput::create_in_memory("jpeg").unwrap();
+            let mut out = output
+                .open_with(img.spec(), JpegOptions::new().quality(quality))
+                .unwrap();
+            out.write_image(data.data()).unwrap();
+            out.close();
+            output.into_bytes().unwrap().len()
+        };
+        assert!(jpeg_size(10) < jpeg_size(95));
+
+        let mut output = ImageOutput::create("tonberry_options.exr").unwrap();
+        let mut out = output
+            .open_with(
+                img.spec(),
+                ExrOptions::new()
+                    .compression(ExrCompression::Piz)
+                    .tile_size(32, 32),
+            )
+            .unwrap();
+        out.write_image(data.data()).unwrap();
+        out.close();
+        output.close().unwrap();
+        let written = ImageInput::open("tonberry_options.exr").unwrap();
+        assert_eq!(written.spec().tile_width(), 32);
+        let pixels: ImageBuffer<u8> = written.read(0, 0, written.all_channels()).unwrap();
+        assert_eq!(pixels.data(), data.data());
+
+        // invalid values and combinations
+        let mut spec = img.spec().to_owned();
+        let invalid = |result: Result<(), Error>| match result {
+            Err(Error::InvalidOption(_)) => {}
+            other => panic!("expected an invalid option error, got {:?}", other),
+        };
+        invalid(JpegOptions::new().quality(0).apply(&mut spec));
+        invalid(PngOptions::new().compression_level(12).apply(&mut spec));
+        invalid(
+            ExrOptions::new()
+                .line_order(ExrLineOrder::RandomY)
+                .apply(&mut spec),
+        );
+        invalid(
+            ExrOptions::new()
+                .compression(ExrCompression::Dwaa(Some(-1.0)))
+                .apply(&mut spec),
+        );
+        invalid(
+            TiffOptions::new()
+                .compression(TiffCompression::PackBits)
+                .predictor(TiffPredictor::Horizontal)
+                .apply(&mut spec),
+        );
+        invalid(
+            TiffOptions::new()
+                .predictor(TiffPredictor::FloatingPoint)
+                .apply(&mut spec),
+        );
+        invalid(DpxOptions::new().bit_depth(9).apply(&mut spec));
+        invalid(WebpOptions::new().quality(101.0).apply(&mut spec));
+        TiffOptions::new()
+            .compression(TiffCompression::Lzw)
+            .predictor(TiffPredictor::Horizontal)
+            .planar_config(TiffPlanarConfig::Separate)
+            .apply(&mut spec)
+            .unwrap();
+
+        // options of another format
+        let mut output = ImageOutput::create("tonberry_options.png").unwrap();
+        assert!(matches!(
+            output.open_with(img.spec(), &JpegOptions::new()),
+            Err(Error::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn transcode_images() {
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 16, 8, &["R", "G", "B"]);
+        let pixels = (0..16 * 8 * 3).map(|i| i as f32 / 7.0).collect::<Vec<_>>();
+        let mut output = ImageOutput::create("transcode_src.tif").unwrap();
+        let mut out = output.open(&spec).unwrap();
+        out.write_image(&pixels).unwrap();
+        out.close();
+        output.close().unwrap();
+
+        // float pixels must survive the conversion unchanged
+        transcode(
+            "transcode_src.tif",
+            "transcode_dst.exr",
+            TranscodeOptions::new()
+                .write_options(ExrOptions::new().compression(ExrCompression::Zip)),
+        )
+        .unwrap();
+        let img = ImageInput::open("transcode_dst.exr").unwrap();
+        assert_eq!(img.spec().format(), TypeDesc::FLOAT);
+        let data: ImageBuffer<f32> = img.read(0, 0, img.all_channels()).unwrap();
+        assert_eq!(data.data(), &pixels[..]);
+
+        // every subimage is copied in its own format
+        let beauty = ImageSpecOwned::new_2d(TypeDesc::HALF, 8, 4, &["R", "G", "B", "A"]);
+        let depth = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 8, 4, &["Z"]);
+        let depth_pixels = (0..8 * 4).map(|i| i as f32 / 3.0).collect::<Vec<_>>();
+        let mut output = ImageOutput::create("transcode_multi_src.exr").unwrap();
+        let mut out = output.open_multi(&[&beauty, &depth]).unwrap();
+        out.write_image(&vec![0.25f32; 8 * 4 * 4]).unwrap();
+        out.append_subimage(&depth).unwrap();
+        out.write_image(&depth_pixels).unwrap();
+        out.close();
+        output.close().unwrap();
+
+        transcode(
+            "transcode_multi_src.exr",
+            "transcode_multi_dst.exr",
+            &TranscodeOptions::new(),
+        )
+        .unwrap();
+        let img = ImageInput::open("transcode_multi_dst.exr").unwrap();
+        let formats = img
+            .subimages()
+            .map(|subimage| subimage.spec().format())
+            .collect::<Vec<_>>();
+        assert_eq!(formats, [TypeDesc::HALF, TypeDesc::FLOAT]);
+        let data: ImageBuffer<f32> = img.read(1, 0, 0..1).unwrap();
+        assert_eq!(data.data(), &depth_pixels[..]);
+
+        // the copied level does not depend on the levels read before
+        let img = ImageInput::open("transcode_multi_src.exr").unwrap();
+        img.read::<f32>(0, 0, 0..4).unwrap();
+        let mut output = ImageOutput::create("transcode_depth.exr").unwrap();
+        let mut out = output.open(&depth).unwrap();
+        out.copy_from(&img.subimage(1).unwrap()).unwrap();
+        out.close();
+        output.close().unwrap();
+        let copy = ImageInput::open("transcode_depth.exr").unwrap();
+        let data: ImageBuffer<f32> = copy.read(0, 0, 0..1).unwrap();
+        assert_eq!(data.data(), &depth_pixels[..]);
+
+        // destination unable to hold all subimages, or options of another format
+        assert_eq!(
+            transcode(
+                "transcode_multi_src.exr",
+                "transcode_multi_dst.png",
+                &TranscodeOptions::new()
+            ),
+            Err(Error::InvalidForImageType)
+        );
+        assert!(matches!(
+            transcode(
+                "transcode_src.tif",
+                "transcode_options.exr",
+                TranscodeOptions::new().write_options(&PngOptions::new()),
+            ),
+            Err(Error::InvalidOption(_))
+        ));
+
+        // single image copy
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let reference: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        let mut output = ImageOutput::create("kazeharu_copy.tif").unwrap();
+        let mut out = output.open(img.spec()).unwrap();
+        out.copy_from(&img.subimage_0().unwrap()).unwrap();
+        out.close();
+        output.close().unwrap();
+        let copy = ImageInput::open("kazeharu_copy.tif").unwrap();
+        let data: ImageBuffer<u8> = copy.read(0, 0, copy.all_channels()).unwrap();
+        assert_eq!(data.data(), reference.data());
+    }
+
+    #[test]
+    fn strided_io() {
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let data: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        let (width, height, nch) = (data.width(), data.height(), data.num_channels());
+
+        // the image is stored at column 5 of an atlas with 8 columns of padding on each row
+        let pitch = (width + 8) * nch;
+        let strides = Strides {
+            x: nch,
+            y: pitch,
+            z: pitch * height,
+        };
+        let mut atlas = vec![0u8; pitch * height];
+        for (y, row) in data.data().chunks(width * nch).enumerate() {
+            atlas[y * pitch + 5 * nch..][..width * nch].copy_from_slice(row);
+        }
+        let rows_equal = |atlas: &[u8]| {
+            data.data()
+                .chunks(width * nch)
+                .enumerate()
+                .all(|(y, row)| &atlas[y * pitch + 5 * nch..][..width * nch] == row)
+        };
+
+        let mut output = ImageOutput::create("kazeharu_strided.png").unwrap();
+        let mut out = output.open(img.spec()).unwrap();
+        assert_eq!(
+            out.write_scanlines(0..height as i32, 0, &atlas[5 * nch..][..width], strides),
+            Err(Error::BufferTooSmall)
+        );
+        out.write_scanlines(0..height as i32, 0, &atlas[5 * nch..], strides)
+            .unwrap();
+        out.close();
+        output.close().unwrap();
+        let written: ImageBuffer<u8> = ImageInput::open("kazeharu_strided.png")
+            .unwrap()
+            .read(0, 0, 0..nch)
+            .unwrap();
+        assert_eq!(written.data(), data.data());
+
+        let mut read_back = vec![0u8; atlas.len()];
+        img.read_into_strided(0, 0, img.all_channels(), &mut read_back[5 * nch..], strides)
+            .unwrap();
+        assert!(rows_equal(&read_back));
+
+        let mut read_back = vec![0u8; atlas.len()];
+        let subimage = img.subimage_0().unwrap();
+        for y in 0..height {
+            let out = &mut read_back[y * pitch + 5 * nch..];
+            subimage
+                .read_scanlines_into_strided(y as u32..y as u32 + 1, 0, 0..nch, out, strides)
+                .unwrap();
+        }
+        assert!(rows_equal(&read_back));
+        assert_eq!(
+            subimage.read_into_strided(0..nch, &mut read_back[pitch..], strides),
+            Err(Error::BufferTooSmall)
+        );
+
+        let tiled = ImageInput::open("test_images/tiled.tif").unwrap();
+        let whole: ImageBuffer<u8> = tiled.read(0, 0, tiled.all_channels()).unwrap();
+        let roi = tiled.spec().roi();
+        let row_len = whole.width() * whole.num_channels();
+        let mut padded = vec![0u8; (row_len + 3) * whole.height()];
+        let strides = Strides {
+            x: whole.num_channels(),
+            y: row_len + 3,
+            z: padded.len(),
+        };
+        tiled
+            .read_tiles_into_strided(0, 0, roi, &mut padded, strides)
+            .unwrap();
+        for (y, row) in whole.data().chunks(row_len).enumerate() {
+            assert_eq!(&padded[y * (row_len + 3)..][..row_len], row);
+        }
+    }
+
+    #[test]
+    fn write_to_memory() {
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let data: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+
+        let mut out = ImageOutput::create_in_memory("png").unwrap();
+        out.open(img.spec())
+            .unwrap()
+            .write_image(data.data())
+            .unwrap();
+        let bytes = out.into_bytes().unwrap();
+        let decoded = ImageInput::open_from_memory(&bytes, Some("png")).unwrap();
+        let decoded_data: ImageBuffer<u8> = decoded.read(0, 0, decoded.all_channels()).unwrap();
+        assert_eq!(decoded_data.data(), data.data());
+
+        let file = std::fs::File::create("kazeharu_writer.png").unwrap();
+        let mut out = ImageOutput::create_writer("png", file).unwrap();
+        out.open(img.spec())
+            .unwrap()
+            .write_image(data.data())
+            .unwrap();
+        out.close().unwrap();
+        assert_eq!(std::fs::read("kazeharu_writer.png").unwrap(), bytes);
+
+        // errors of the stream are reported by close
+        struct FullDisk;
+        impl std::io::Write for FullDisk {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk full"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        impl std::io::Seek for FullDisk {
+            fn seek(&mut self, _: std::io::SeekFrom) -> std::io::Result<u64> {
+                Ok(0)
+            }
+        }
+        let mut out = ImageOutput::create_writer("png", FullDisk).unwrap();
+        let _ = out.open(img.spec()).unwrap().write_image(data.data());
+        assert_eq!(out.close(), Err(Error::WriteError("disk full".to_owned())));
+
+        let out = ImageOutput::create("kazeharu_not_in_memory.png").unwrap();
+        assert_eq!(out.into_bytes(), Err(Error::InvalidForImageType));
+
+        assert_eq!(
+            ImageOutput::create_in_memory("nosuchformat").err(),
+            Some(Error::UnsupportedFormat("nosuchformat".to_owned()))
+        );
+    }
+
+    #[test]
+    fn deep_data() {
+        let mut spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 2, 1, &["R", "G", "B", "A", "Z"]);
+        spec.set_deep(true);
+        assert!(spec.is_deep());
+
+        let mut data = DeepData::from_spec(&spec);
+        assert_eq!(data.num_pixels(), 2);
+        assert_eq!(data.num_channels(), 5);
+        assert_eq!(data.alpha_channel(), Some(3));
+        assert_eq!(data.z_channel(), Some(4));
+        assert_eq!(data.zback_channel(), None);
+        data.set_samples(0, 2);
+        data.set_value(0, 4, 0, 10.0);
+        data.set_value(0, 4, 1, 5.0);
+        data.insert_samples(1, 0, 3);
+        data.erase_samples(1, 1, 2);
+        assert_eq!(data.samples(1), 1);
+        data.sort(0);
+        assert_eq!(data.value(0, 4, 0), 5.0);
+        assert_eq!(data.clone().value(0, 4, 1), 10.0);
+
+        let mut out = ImageOutput::create("deep.exr").unwrap();
+        out.open(&spec).unwrap().write_deep_image(&data).unwrap();
+        out.close().unwrap();
+
+        let input = ImageInput::open("deep.exr").unwrap();
+        assert!(input.spec().is_deep());
+        let read = input.read_native_deep_image(0, 0).unwrap();
+        assert_eq!(read.samples(0), 2);
+        assert_eq!(read.samples(1), 1);
+        assert_eq!(read.value(0, 4, 1), 10.0);
+        assert_eq!(
+            input.read_native_deep_image(0, 0).unwrap().channel(4).name,
+            "Z"
+        );
+        // same scanline coordinates as write_deep_scanlines
+        let row = input
+            .read_native_deep_scanlines(0, 0, 0..1, 0, input.all_channels())
+            .unwrap();
+        assert_eq!(row.samples(0), 2);
+
+        let tiled_spec = spec.clone().with_tile_size(2, 1, 1);
+        let mut out = ImageOutput::create("deep_tiled.exr").unwrap();
+        out.open(&tiled_spec)
+            .unwrap()
+            .write_deep_tiles(Roi::ALL, &data)
+            .unwrap();
+        out.close().unwrap();
+        let read = ImageInput::open("deep_tiled.exr")
+            .unwrap()
+            .read_native_deep_image(0, 0)
+            .unwrap();
+        assert_eq!(read.samples(0), 2);
+        assert_eq!(read.value(0, 4, 1), 10.0);
+
+        let png = ImageInput::open("test_images/kazeharu.png").unwrap();
+        assert_eq!(
+            png.read_native_deep_image(0, 0).err(),
+            Some(Error::InvalidForImageType)
+        );
+
+        let flat_spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 2, 2, &["R", "G", "B", "A", "Z"]);
+        let flat = algo::add(
+            &ImageBuf::from_spec(&flat_spec),
+            &[0.5, 0.0, 0.0, 0.5, 1.0],
+            Roi::ALL,
+            0,
+        )
+        .unwrap();
+        let deep = algo::deepen(&flat, 0.0, Roi::ALL, 0).unwrap();
+        assert!(deep.is_deep());
+        let merged = algo::deep_merge(&deep, &deep, false, Roi::ALL, 0).unwrap();
+        let flattened = algo::flatten(&merged, Roi::ALL, 0).unwrap();
+        let pixels: ImageBuffer<f32> = flattened.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&pixels.data()[0..4], &[0.75, 0.0, 0.0, 0.75]);
+    }
+
+    #[test]
+    fn progress_callbacks() {
+        use std::ops::ControlFlow;
+
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let mut reported = Vec::new();
+        let data: ImageBuffer<u8> = img
+            .read_with_progress(0, 0, img.all_channels(), |done| {
+                reported.push(done);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+        assert!(!reported.is_empty());
+        assert!(reported.windows(2).all(|w| w[0] <= w[1]));
+        let expected: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        assert_eq!(data.data(), expected.data());
+        assert_eq!(
+            img.read_with_progress::<u8>(0, 0, img.all_channels(), |_| ControlFlow::Break(()))
+                .err(),
+            Some(Error::Cancelled)
+        );
+
+        let buf = ImageBuf::open("test_images/kazeharu.png").unwrap();
+        let roi = Roi::new_2d(0..64, 0..32);
+        let mut last = 0.0;
+        let small = algo::resize_with_progress(&buf, None, 0.0, roi, 0, |done| {
+            last = done;
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(last, 1.0);
+        let expected = algo::resize(&buf, None, 0.0, roi, 0).unwrap();
+        let small_pixels: ImageBuffer<f32> = small.get_pixels(Roi::ALL).unwrap();
+        let expected_pixels: ImageBuffer<f32> = expected.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(small_pixels.data(), expected_pixels.data());
+        assert_eq!(
+            algo::resize_with_progress(&buf, None, 0.0, roi, 0, |done| {
+                if done > 0.5 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .err(),
+            Some(Error::Cancelled)
+        );
+    }
+
+    #[test]
+    fn thread_safety() {
+        fn assert_send<T: Send>() {}
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send::<ImageInput>();
+        assert_send::<ImageOutput>();
+        assert_send::<ImageBuf>();
+        assert_send_sync::<ImageSpecOwned>();
+        assert_send_sync::<ImageCache>();
+        assert_send_sync::<CachedImage>();
+        assert_send_sync::<SharedCachedImage>();
+
+        let cache = SharedImageCache::new();
+        let img = cache.image("test_images/tiled.tif").unwrap();
+        let expected: ImageBuffer<u8> = img.read().unwrap();
+        let (width, rows) = (img.width() as i32, img.height() as i32 / 4);
+        let strips: Vec<_> = (0..4)
+            .map(|i| {
+                let img = img.clone();
+                std::thread::spawn(move || {
+                    img.read_region::<u8>(Roi::new_2d(0..width, i * rows..(i + 1) * rows))
+                        .unwrap()
+                })
+            })
+            .collect();
+        let strip_len = (width * rows) as usize * expected.num_channels();
+        for (i, strip) in strips.into_iter().enumerate() {
+            assert_eq!(
+                strip.join().unwrap().data(),
+                &expected.data()[i * strip_len..(i + 1) * strip_len]
+            );
+        }
+        assert_eq!(cache.total_files(), 1);
+    }
+
     #[test]
     fn read_tiled() {
         let mut img = ImageInput::open("test_images/tiled.tif").unwrap();
@@ -121,16 +865,14 @@ mod tests {
                 * img.spec().num_channels(),
             0,
         );
-        img.read_tiles_into(
-            0,
-            0,
-            0..img.spec().tile_width(),
-            0..img.spec().tile_height(),
-            0..img.spec().tile_depth(),
+        let tile_roi = Roi::new(
+            0..img.spec().tile_width() as i32,
+            0..img.spec().tile_height() as i32,
+            0..img.spec().tile_depth() as i32,
             img.all_channels(),
-            &mut tiled_data,
-        )
-        .unwrap();
+        );
+        img.read_tiles_into(0, 0, tile_roi, &mut tiled_data)
+            .unwrap();
 
         let mut whole_img: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
 
@@ -148,6 +890,124 @@ mod tests {
             .collect();
 
         assert_eq!(tile_from_whole_img, tiled_data);
+
+        // regions without a channel range cover the channels of the image
+        let mut all_channels_data = vec![0u8; tiled_data.len()];
+        let tile_roi = Roi::new_2d(
+            0..img.spec().tile_width() as i32,
+            0..img.spec().tile_height() as i32,
+        );
+        img.read_tiles_into(0, 0, tile_roi, &mut all_channels_data)
+            .unwrap();
+        assert_eq!(all_channels_data, tiled_data);
+    }
+
+    #[test]
+    fn tile_iteration() {
+        let img = ImageInput::open("test_images/tiled.tif").unwrap();
+        let whole: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        let (x0, y0) = (img.spec().x(), img.spec().y());
+        let row_len = img.width() as usize * whole.num_channels();
+
+        // copies a tile into an image buffer, returning the number of pixels copied
+        let paste = |tile: &Tile<u8>, out: &mut [u8]| {
+            let tile_row_len = tile.data.width() * tile.data.num_channels();
+            for (row, tile_row) in tile.data.data().chunks(tile_row_len).enumerate() {
+                let y = (tile.roi.ys().start - y0) as usize + row;
+                let x = (tile.roi.xs().start - x0) as usize * whole.num_channels();
+                out[y * row_len + x..][..tile_row_len].copy_from_slice(tile_row);
+            }
+            tile.data.width() * tile.data.height()
+        };
+
+        let mut assembled = vec![0u8; whole.data().len()];
+        let mut pixels = 0;
+        let tiles = img.tiles::<u8>(0, 0, img.all_channels()).unwrap();
+        let tiles_x = img.width().div_ceil(img.spec().tile_width());
+        let tiles_y = img.height().div_ceil(img.spec().tile_height());
+        assert_eq!(tiles.size_hint().0, (tiles_x * tiles_y) as usize);
+        for tile in tiles {
+            pixels += paste(&tile.unwrap(), &mut assembled);
+        }
+        assert_eq!(pixels, (img.width() * img.height()) as usize);
+        assert_eq!(&assembled[..], whole.data());
+
+        let assembled = std::sync::Mutex::new(vec![0u8; whole.data().len()]);
+        img.par_read_tiles::<u8>(0, 0, img.all_channels(), 4, |tile| {
+            paste(&tile, &mut assembled.lock().unwrap());
+        })
+        .unwrap();
+        assert_eq!(&assembled.into_inner().unwrap()[..], whole.data());
+
+        // channels are checked before any tile is read
+        let nch = img.spec().num_channels();
+        assert_eq!(
+            img.tiles::<u8>(0, 0, 0..nch + 1).err(),
+            Some(Error::ChannelIndexOutOfBounds)
+        );
+        assert_eq!(
+            img.par_read_tiles::<u8>(0, 0, 0..0, 4, |_| {}),
+            Err(Error::InvalidParameter)
+        );
+
+        let png = ImageInput::open("test_images/kazeharu.png").unwrap();
+        assert_eq!(
+            png.tiles::<u8>(0, 0, png.all_channels()).err(),
+            Some(Error::InvalidForImageType)
+        );
+    }
+
+    #[test]
+    fn strip_reading() {
+        for path in &["test_images/kazeharu.png", "test_images/tiled.tif"] {
+            let img = ImageInput::open(path).unwrap();
+            let whole: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+            let row_len = img.width() as usize * whole.num_channels();
+
+            let mut strips = img.strips::<u8>(0, 0, 7, img.all_channels()).unwrap();
+            let mut next_row = img.spec().y();
+            let mut buffer_ptr = None;
+            while let Some(strip) = strips.next_strip() {
+                let strip = strip.unwrap();
+                assert_eq!(strip.rows.start, next_row);
+                assert!(strip.rows.len() <= 7);
+                let offset = (strip.rows.start - img.spec().y()) as usize * row_len;
+                assert_eq!(
+                    strip.data.data(),
+                    &whole.data()[offset..offset + strip.rows.len() * row_len]
+                );
+                // the same allocation is reused for every strip
+                let ptr = strip.data.data().as_ptr();
+                assert_eq!(*buffer_ptr.get_or_insert(ptr), ptr);
+                next_row = strip.rows.end;
+            }
+            assert_eq!(next_row, img.spec().y() + img.height() as i32);
+        }
+
+        // one strip for the whole image
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let whole: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        let mut strips = img
+            .strips::<u8>(0, 0, usize::MAX, img.all_channels())
+            .unwrap();
+        let strip = strips.next_strip().unwrap().unwrap();
+        assert_eq!(strip.rows.len(), img.height() as usize);
+        assert_eq!(strip.data.data(), whole.data());
+        assert!(strips.next_strip().is_none());
+
+        assert_eq!(
+            img.strips::<u8>(0, 0, 0, img.all_channels()).err(),
+            Some(Error::InvalidParameter)
+        );
+        assert_eq!(
+            img.strips::<u8>(0, 0, 7, 0..0).err(),
+            Some(Error::InvalidParameter)
+        );
+        assert_eq!(
+            img.strips::<u8>(0, 0, 7, 0..img.spec().num_channels() + 1)
+                .err(),
+            Some(Error::ChannelIndexOutOfBounds)
+        );
     }
 
     #[test]
@@ -439,6 +1299,366 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn image_buf_read_write() {
+        let mut buf = ImageBuf::open("test_images/kazeharu.png").unwrap();
+        buf.read(0, 0, true, TypeDesc::UNKNOWN).unwrap();
+        assert_eq!(buf.file_format_name(), "png");
+        assert_eq!(buf.num_channels(), buf.spec().num_channels());
+        assert_eq!(buf.roi().width(), buf.width());
+
+        // pixels should match those read through ImageInput
+        let mut img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let data: ImageBuffer<u8> = img.read(0, 0, img.all_channels()).unwrap();
+        let buf_data: ImageBuffer<u8> = buf.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(data.data(), buf_data.data());
+
+        buf.write("kazeharu_imagebuf.png").unwrap();
+        let written = ImageBuf::open("kazeharu_imagebuf.png").unwrap();
+        assert_eq!(written.spec().size(), buf.spec().size());
+    }
+
+    #[test]
+    fn image_buf_open_nonexistent() {
+        assert!(ImageBuf::open("test_images/nonexistent.png").is_err());
+    }
+
+    #[test]
+    fn roi() {
+        let a = Roi::new_2d(0..10, 0..10);
+        let b = Roi::new_2d(5..20, 5..8);
+        assert_eq!(a.npixels(), 100);
+        assert_eq!(a.union(&b), Roi::new_2d(0..20, 0..10));
+        assert_eq!(a.intersection(&b), Roi::new_2d(5..10, 5..8));
+        assert!(a.intersection(&Roi::new_2d(20..30, 0..10)).is_empty());
+        assert_eq!(a.union(&Roi::ALL), a);
+        assert!(a.contains(&Roi::new_2d(2..4, 2..4)));
+        assert!(!a.contains(&b));
+        assert!(a.contains_pixel(9, 9, 0));
+        assert!(!a.contains_pixel(10, 9, 0));
+        assert!(Roi::ALL.contains(&a));
+        assert_eq!(Roi::ALL.npixels(), 0);
+        assert_eq!(Roi::ALL.width(), 0);
+        assert_eq!(
+            Roi::new_2d(i32::MIN + 1..i32::MAX, 0..1).width(),
+            u32::MAX - 1
+        );
+
+        let img = ImageInput::open("test_images/kazeharu.png").unwrap();
+        let spec = img.spec();
+        let roi = spec.roi();
+        assert_eq!(roi.width(), spec.width());
+        assert_eq!(roi.channels(), spec.all_channels());
+        assert_eq!(
+            Roi::from(spec.calculate_bounds(.., .., ..)),
+            Roi::from(spec.data_window())
+        );
+    }
+
+    #[test]
+    fn image_spec_channel_names() {
+        // the given names replace the default ones (R, G, B, A) instead of following them
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 4, 4, &["X", "Y", "Z", "W"]);
+        let names = spec.channels().map(|ch| ch.name).collect::<Vec<_>>();
+        assert_eq!(names, ["X", "Y", "Z", "W"]);
+        assert_eq!(spec.alpha_channel(), Err(Error::ChannelNotFound));
+
+        let mut spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 4, 4, &["A", "B", "G", "R"]);
+        assert_eq!(spec.alpha_channel(), Ok(0));
+
+        // attribute names are passed with their length, so they can contain a NUL
+        spec.set_attribute("oiio:\0name", 1i32);
+    }
+
+    #[test]
+    fn algo_geometry() {
+        let buf = ImageBuf::open("test_images/kazeharu.png").unwrap();
+        let pixels: ImageBuffer<u8> = buf.get_pixels(Roi::ALL).unwrap();
+
+        // flipping twice gives back the original image
+        let flipped = algo::flip(&buf, Roi::ALL, 0).unwrap();
+        let flipped = algo::flip(&flipped, Roi::ALL, 0).unwrap();
+        let flipped_pixels: ImageBuffer<u8> = flipped.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(pixels.data(), flipped_pixels.data());
+
+        let rotated = algo::rotate90(&buf, Roi::ALL, 0).unwrap();
+        assert_eq!(rotated.width(), buf.height());
+        assert_eq!(rotated.height(), buf.width());
+
+        let cut = algo::cut(&buf, Roi::new_2d(10..42, 20..36), 0).unwrap();
+        assert_eq!(cut.width(), 32);
+        assert_eq!(cut.height(), 16);
+        assert_eq!(cut.roi().xs(), 0..32);
+    }
+
+    #[test]
+    fn algo_resize() {
+        let buf = ImageBuf::open("test_images/kazeharu.png").unwrap();
+
+        let small = algo::resize(
+            &buf,
+            Some(algo::Filter2D::Lanczos3),
+            0.0,
+            Roi::new_2d(0..64, 0..32),
+            0,
+        )
+        .unwrap();
+        assert_eq!((small.width(), small.height()), (64, 32));
+        assert_eq!(small.num_channels(), buf.num_channels());
+
+        let resampled = algo::resample(&buf, true, Roi::new_2d(0..64, 0..32), 0).unwrap();
+        assert_eq!((resampled.width(), resampled.height()), (64, 32));
+
+        let fitted = algo::fit(
+            &buf,
+            None,
+            0.0,
+            algo::FitMode::Letterbox,
+            false,
+            Roi::new_2d(0..100, 0..100),
+            0,
+        )
+        .unwrap();
+        assert_eq!((fitted.width(), fitted.height()), (100, 100));
+
+        let identity = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        let warped = algo::warp(
+            &small,
+            &identity,
+            Some(algo::Filter2D::Box),
+            0.0,
+            false,
+            WrapMode::Black,
+            Roi::ALL,
+            0,
+        )
+        .unwrap();
+        assert_eq!(warped.roi(), small.roi());
+
+        assert_eq!(
+            algo::Filter2D::from_name("catmull-rom"),
+            Some(algo::Filter2D::CatmullRom)
+        );
+    }
+
+    #[test]
+    fn algo_arith() {
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 4, 4, &["R", "G", "B"]);
+        let zero = ImageBuf::from_spec(&spec);
+
+        let a = algo::add(&zero, &[1.0, 2.0, 3.0], Roi::ALL, 0).unwrap();
+        let b = algo::mad(&a, 2.0, &a, Roi::ALL, 0).unwrap();
+        let b: ImageBuffer<f32> = b.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&b.data()[0..3], &[3.0, 6.0, 9.0]);
+
+        let c = algo::clamp(&a, f32::NEG_INFINITY, 2.5, false, Roi::ALL, 0).unwrap();
+        let c: ImageBuffer<f32> = c.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&c.data()[0..3], &[1.0, 2.0, 2.5]);
+        let c = algo::clamp(&a, &[0.0, 2.5, 0.0], &[0.5, 3.0, 2.0], false, Roi::ALL, 0).unwrap();
+        let c: ImageBuffer<f32> = c.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&c.data()[0..3], &[0.5, 2.5, 2.0]);
+
+        let e = algo::pow(&a, 2.0, Roi::ALL, 0).unwrap();
+        let e: ImageBuffer<f32> = e.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&e.data()[0..3], &[1.0, 4.0, 9.0]);
+        let e = algo::pow(&a, &[1.0, 0.0, 2.0], Roi::ALL, 0).unwrap();
+        let e: ImageBuffer<f32> = e.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&e.data()[0..3], &[1.0, 1.0, 9.0]);
+        assert_eq!(
+            algo::pow(&a, &a, Roi::ALL, 0).err(),
+            Some(Error::InvalidParameter)
+        );
+
+        let d = algo::sub(&zero, &a, Roi::ALL, 0).unwrap();
+        let d = algo::abs(&d, Roi::ALL, 0).unwrap();
+        let d: ImageBuffer<f32> = d.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&d.data()[0..3], &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn algo_composite() {
+        let rgba = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 2, 2, &["R", "G", "B", "A"]);
+        let zero = ImageBuf::from_spec(&rgba);
+        let background = algo::add(&zero, &[0.0, 0.0, 1.0, 1.0], Roi::ALL, 0).unwrap();
+        let layer = algo::add(&zero, &[0.5, 0.0, 0.0, 0.5], Roi::ALL, 0).unwrap();
+
+        let result = algo::comp(&background, &[&layer, &layer], Roi::ALL, 0).unwrap();
+        let pixels: ImageBuffer<f32> = result.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&pixels.data()[0..4], &[0.75, 0.0, 0.25, 1.0]);
+
+        let unpremultiplied = algo::unpremult(&layer, Roi::ALL, 0).unwrap();
+        let pixels: ImageBuffer<f32> = unpremultiplied.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&pixels.data()[0..4], &[1.0, 0.0, 0.0, 0.5]);
+        let repremultiplied = algo::repremult(&unpremultiplied, Roi::ALL, 0).unwrap();
+        let pixels: ImageBuffer<f32> = repremultiplied.get_pixels(Roi::ALL).unwrap();
+        assert_eq!(&pixels.data()[0..4], &[0.5, 0.0, 0.0, 0.5]);
+
+        let rgb = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 2, 2, &["R", "G", "B"]);
+        assert_eq!(
+            algo::comp(&ImageBuf::from_spec(&rgb), &[&layer], Roi::ALL, 0).err(),
+            Some(Error::AlphaChannelNotFound)
+        );
+    }
+
+    #[test]
+    fn algo_stats() {
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 4, 4, &["R", "G", "B"]);
+        let zero = ImageBuf::from_spec(&spec);
+        let gray = algo::add(&zero, &[0.5, 0.5, 0.5], Roi::ALL, 0).unwrap();
+
+        let stats = algo::compute_pixel_stats(&gray, Roi::ALL, 0).unwrap();
+        assert_eq!(stats.min, [0.5, 0.5, 0.5]);
+        assert_eq!(stats.max, [0.5, 0.5, 0.5]);
+        assert_eq!(stats.avg, [0.5, 0.5, 0.5]);
+        assert_eq!(stats.finite_count, [16, 16, 16]);
+        assert_eq!(stats.nan_count, [0, 0, 0]);
+
+        let histogram = algo::histogram(&gray, 0, 4, 0.0..=1.0, false, Roi::ALL, 0).unwrap();
+        assert_eq!(histogram.iter().sum::<u64>(), 16);
+        assert_eq!(histogram[2], 16);
+
+        let range = algo::color_range_check(&gray, &[0.0], &[0.25], Roi::ALL, 0).unwrap();
+        assert_eq!(range.high, 16);
+        assert_eq!(range.in_range, 0);
+
+        assert_eq!(
+            algo::is_constant_color(&gray, 0.0, Roi::ALL, 0).unwrap(),
+            Some(vec![0.5, 0.5, 0.5])
+        );
+        assert!(algo::is_constant_channel(&gray, 1, 0.5, 0.0, Roi::ALL, 0).unwrap());
+        assert!(algo::is_monochrome(&gray, 0.0, Roi::ALL, 0).unwrap());
+        assert!(algo::nonzero_region(&zero, Roi::ALL, 0).unwrap().is_empty());
+        let region = algo::nonzero_region(&gray, Roi::ALL, 0).unwrap();
+        assert_eq!((region.width(), region.height()), (4, 4));
+
+        let hash = algo::compute_pixel_hash_sha1(&gray, "", Roi::ALL, 0, 0).unwrap();
+        assert_eq!(hash.len(), 40);
+        let zero_hash = algo::compute_pixel_hash_sha1(&zero, "", Roi::ALL, 0, 0).unwrap();
+        assert_ne!(hash, zero_hash);
+    }
+
+    #[test]
+    fn algo_compare() {
+        let mut original = ImageBuf::open("test_images/kazeharu.png").unwrap();
+        original.read(0, 0, true, TypeDesc::UNKNOWN).unwrap();
+        original.write("kazeharu_reencoded.tif").unwrap();
+        let mut reencoded = ImageBuf::open("kazeharu_reencoded.tif").unwrap();
+        reencoded.read(0, 0, true, TypeDesc::UNKNOWN).unwrap();
+
+        let results = algo::compare(&original, &reencoded, 1.0e-6, 1.0e-6, Roi::ALL, 0).unwrap();
+        assert!(results.passed());
+        assert_eq!(results.max_error, 0.0);
+        assert_eq!(results.warn_count, 0);
+        let yee = algo::compare_yee(&original, &reencoded, 100.0, 45.0, Roi::ALL, 0).unwrap();
+        assert!(yee.passed());
+
+        let brighter = algo::add(&original, 0.25, Roi::ALL, 0).unwrap();
+        let results = algo::compare(&original, &brighter, 0.1, 0.01, Roi::ALL, 0).unwrap();
+        assert!(!results.passed());
+        assert!(results.mean_error > 0.0);
+        assert!(results.max_error > 0.1);
+    }
+
+    #[test]
+    fn color_config() {
+        use crate::color::ColorConfig;
+        if !ColorConfig::supports_opencolorio() {
+            return;
+        }
+
+        let config = ColorConfig::from_file("test_images/ocio/config.ocio").unwrap();
+        let color_spaces: Vec<_> = config.color_spaces().collect();
+        assert_eq!(color_spaces, ["linear", "srgb", "raw"]);
+        assert!(config.roles().any(|r| r == "scene_linear"));
+        assert_eq!(config.color_space_by_role("data").unwrap(), "raw");
+        assert_eq!(config.looks().collect::<Vec<_>>(), ["contrast"]);
+        assert_eq!(config.displays().collect::<Vec<_>>(), ["sRGB"]);
+        assert_eq!(config.views("sRGB").collect::<Vec<_>>(), ["Film", "Raw"]);
+
+        let processor = config.processor("linear", "srgb").unwrap();
+        let mut pixel = [0.5, 0.5, 0.5, 1.0];
+        processor.apply_slice(&mut pixel, 4);
+        assert!((pixel[0] - 0.5f32.powf(1.0 / 2.2)).abs() < 1e-4);
+        assert_eq!(pixel[3], 1.0);
+
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 2, 2, &["R", "G", "B"]);
+        let mut buf = algo::add(&ImageBuf::from_spec(&spec), 0.5, Roi::ALL, 0).unwrap();
+        processor.apply(&mut buf).unwrap();
+        let pixels: ImageBuffer<f32> = buf.get_pixels(Roi::ALL).unwrap();
+        assert!((pixels.data()[0] - pixel[0]).abs() < 1e-4);
+
+        assert!(ColorConfig::from_file("test_images/ocio/nonexistent.ocio").is_err());
+        assert!(config.processor("linear", "nonexistent").is_err());
+    }
+
+    #[test]
+    fn algo_color() {
+        use crate::color::ColorConfig;
+        if !ColorConfig::supports_opencolorio() {
+            return;
+        }
+
+        let config = ColorConfig::from_file("test_images/ocio/config.ocio").unwrap();
+        let spec = ImageSpecOwned::new_2d(TypeDesc::FLOAT, 2, 2, &["R", "G", "B"]);
+        let buf = algo::add(&ImageBuf::from_spec(&spec), 0.5, Roi::ALL, 0).unwrap();
+        let expected = 0.5f32.powf(1.0 / 2.2);
+
+        let converted = algo::colorconvert(
+            &buf,
+            Some("linear"),
+            "srgb",
+            false,
+            Some(&config),
+            Roi::ALL,
+            0,
+        )
+        .unwrap();
+        let pixels: ImageBuffer<f32> = converted.get_pixels(Roi::ALL).unwrap();
+        assert!((pixels.data()[0] - expected).abs() < 1e-4);
+
+        // source color space taken from the image metadata
+        let display = algo::ociodisplay(
+            &converted,
+            "sRGB",
+            "Raw",
+            None,
+            "",
+            false,
+            Some(&config),
+            Roi::ALL,
+            0,
+        )
+        .unwrap();
+        let pixels: ImageBuffer<f32> = display.get_pixels(Roi::ALL).unwrap();
+        assert!((pixels.data()[0] - expected).abs() < 1e-4);
+
+        let look = algo::ociolook(
+            &buf,
+            "contrast",
+            Some("linear"),
+            "linear",
+            false,
+            false,
+            Some(&config),
+            Roi::ALL,
+            0,
+        )
+        .unwrap();
+        let pixels: ImageBuffer<f32> = look.get_pixels(Roi::ALL).unwrap();
+        assert!((pixels.data()[0] - 0.6).abs() < 1e-4);
+
+        assert!(algo::ociofiletransform(
+            &buf,
+            "nonexistent.cube",
+            false,
+            false,
+            Some(&config),
+            Roi::ALL,
+            0
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_api_overhead() {
         /*let mut img = ImageInput::open("../test_images/kazeharu.png").unwrap();
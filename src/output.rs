@@ -1,15 +1,39 @@
 //! Image output
-use crate::{cstring_to_owned, error::get_last_error, typedesc::ImageData, Error, ImageSpec};
+use crate::{
+    cstring_to_owned,
+    error::get_last_error,
+    ioproxy::{IoProxy, WriteSeek},
+    progress::{self, Progress},
+    typedesc::ImageData,
+    writeoptions, DeepData, Error, ImageBuf, ImageSpec, Roi, Strides, SubimageInput, WriteOptions,
+};
 use openimageio_sys as sys;
 use openimageio_sys::AsStringRef;
-use std::{ffi::c_void, io, path::Path};
+use std::{
+    ffi::{c_void, CStr},
+    io::{Seek, Write},
+    ops::{ControlFlow, Deref, DerefMut, Range},
+    path::Path,
+    ptr,
+};
 
 ///
 pub struct ImageOutput {
     ptr: *mut sys::OIIO_ImageOutput,
     path: String,
+    // must outlive the writer: dropped after `ptr` is deleted
+    proxy: Option<IoProxy>,
+    // number of subimages declared when opening, number of subimages started so far, and
+    // number of subimages that received pixels (including the current one if `current_written`)
+    subimages: usize,
+    started_subimages: usize,
+    written_subimages: usize,
+    current_written: bool,
 }
 
+// An ImageOutput can be used from any thread, but writes must be sequential.
+unsafe impl Send for ImageOutput {}
+
 pub struct SingleImageOutput<'a>(&'a mut ImageOutput);
 pub struct MultiImageOutput<'a>(SingleImageOutput<'a>);
 
@@ -31,9 +55,130 @@ impl ImageOutput {
         Ok(ImageOutput {
             ptr,
             path: path.to_string(),
+            proxy: None,
+            subimages: 0,
+            started_subimages: 0,
+            written_subimages: 0,
+            current_written: false,
         })
     }
 
+    /// Creates an imageoutput that encodes the image in memory, in the specified format (a
+    /// format name such as `"png"`, or a file extension).
+    ///
+    /// Retrieve the encoded bytes with [ImageOutput::into_bytes] once the image is written.
+    /// Returns [Error::UnsupportedFormat] if the format is unknown or its writer cannot write to
+    /// memory.
+    pub fn create_in_memory(format: &str) -> Result<ImageOutput, Error> {
+        ImageOutput::create_with_proxy(format, IoProxy::vec_output())
+    }
+
+    /// Creates an imageoutput that encodes the image into a stream, in the specified format
+    /// (a format name such as `"png"`, or a file extension).
+    ///
+    /// The stream is dropped with the imageoutput; call [ImageOutput::close] to flush it and
+    /// get errors. Returns [Error::UnsupportedFormat] if the format is unknown or its writer
+    /// cannot write to streams.
+    pub fn create_writer<W: Write + Seek + Send + 'static>(
+        format: &str,
+        writer: W,
+    ) -> Result<ImageOutput, Error> {
+        let writer: Box<dyn WriteSeek> = Box::new(writer);
+        ImageOutput::create_with_proxy(format, IoProxy::from_writer(writer))
+    }
+
+    fn create_with_proxy(format: &str, proxy: IoProxy) -> Result<ImageOutput, Error> {
+        let ptr = unsafe { sys::OIIO_ImageOutput_create(format.as_stringref(), "".as_stringref()) };
+        if ptr.is_null() {
+            // clear the error message left by OpenImageIO
+            get_last_error();
+            return Err(Error::UnsupportedFormat(format.to_owned()));
+        }
+        let mut output = ImageOutput {
+            ptr,
+            path: format.to_string(),
+            proxy: None,
+            subimages: 0,
+            started_subimages: 0,
+            written_subimages: 0,
+            current_written: false,
+        };
+        if !output.supports(OutputCapability::IoProxy) {
+            return Err(Error::UnsupportedFormat(format.to_owned()));
+        }
+        if !unsafe { sys::OIIO_ImageOutput_set_ioproxy(ptr, proxy.ptr) } {
+            return Err(Error::OpenError(output.get_last_error()));
+        }
+        output.proxy = Some(proxy);
+        Ok(output)
+    }
+
+    /// Returns the name of the format of the writer (e.g. `"openexr"` or `"png"`).
+    pub fn format_name(&self) -> &str {
+        unsafe {
+            CStr::from_ptr(sys::OIIO_ImageOutput_format_name(self.ptr))
+                .to_str()
+                .unwrap()
+        }
+    }
+
+    /// Returns whether the writer of this imageoutput supports the specified capability.
+    pub fn supports(&self, capability: OutputCapability) -> bool {
+        unsafe { sys::OIIO_ImageOutput_supports(self.ptr, capability.name().as_stringref()) }
+    }
+
+    /// Finishes writing the image and closes the file or stream.
+    ///
+    /// If writing to a stream failed, returns [Error::WriteError] with the first error of the
+    /// stream. Returns [Error::MissingSubimages] if it was opened with [ImageOutput::open_multi] and
+    /// pixels were written to fewer subimages than declared (the file is closed anyway).
+    /// Dropping an imageoutput also closes it, but ignores errors.
+    pub fn close(mut self) -> Result<(), Error> {
+        self.close_checked()
+    }
+
+    /// Finishes writing an image created with [ImageOutput::create_in_memory], and returns the
+    /// encoded bytes.
+    ///
+    /// Returns [Error::InvalidForImageType] if the imageoutput doesn't write to memory.
+    pub fn into_bytes(mut self) -> Result<Vec<u8>, Error> {
+        // check before finishing the image, which is not needed by other outputs
+        if self
+            .proxy
+            .as_ref()
+            .and_then(|proxy| proxy.vec_output_data())
+            .is_none()
+        {
+            return Err(Error::InvalidForImageType);
+        }
+        self.close_checked()?;
+        self.proxy
+            .as_ref()
+            .and_then(|proxy| proxy.vec_output_data())
+            .map(|data| data.to_vec())
+            .ok_or(Error::InvalidForImageType)
+    }
+
+    fn close_checked(&mut self) -> Result<(), Error> {
+        let closed = unsafe { sys::OIIO_ImageOutput_close(self.ptr) };
+        // errors of the stream explain the failures of the writer better than its message
+        if let Some(ref proxy) = self.proxy {
+            proxy
+                .flush()
+                .map_err(|err| Error::WriteError(err.to_string()))?;
+        }
+        if !closed {
+            return Err(Error::WriteError(self.get_last_error()));
+        }
+        if self.written_subimages < self.subimages {
+            return Err(Error::MissingSubimages {
+                declared: self.subimages,
+                written: self.written_subimages,
+            });
+        }
+        Ok(())
+    }
+
     /// Opens an imageoutput
     pub fn open(&mut self, spec: &ImageSpec) -> Result<SingleImageOutput, Error> {
         // init headers
@@ -50,12 +195,34 @@ impl ImageOutput {
         if !success {
             return Err(Error::OpenError(self.get_last_error()));
         }
+        self.start(1);
 
         Ok(SingleImageOutput(self))
     }
 
-    /// Opens an imageoutput
+    /// Opens an imageoutput, with options specific to the format of the writer.
+    ///
+    /// The options are applied to a copy of `spec`. Returns [Error::InvalidOption] if the
+    /// options are for another format, or are invalid for this image.
+    pub fn open_with<O: WriteOptions>(
+        &mut self,
+        spec: &ImageSpec,
+        options: &O,
+    ) -> Result<SingleImageOutput<'_>, Error> {
+        writeoptions::check_format(O::FORMAT_NAME, self.format_name())?;
+        let mut spec = spec.to_owned();
+        options.apply(&mut spec)?;
+        self.open(&spec)
+    }
+
+    /// Opens an imageoutput for writing several subimages, with the specified specs.
+    ///
+    /// Pixels are written to the first subimage; use [MultiImageOutput::append_subimage] to
+    /// move to the next ones. Returns [Error::InvalidParameter] if `subimages` is empty.
     pub fn open_multi(&mut self, subimages: &[&ImageSpec]) -> Result<MultiImageOutput, Error> {
+        if subimages.is_empty() {
+            return Err(Error::InvalidParameter);
+        }
         // init headers
         let success = unsafe {
             sys::OIIO_ImageOutput_open2(
@@ -69,10 +236,42 @@ impl ImageOutput {
         if !success {
             return Err(Error::OpenError(self.get_last_error()));
         }
+        self.start(subimages.len());
 
         Ok(MultiImageOutput(SingleImageOutput(self)))
     }
 
+    /// Starts writing the first of the specified number of subimages.
+    fn start(&mut self, subimages: usize) {
+        self.subimages = subimages;
+        self.started_subimages = 1;
+        self.written_subimages = 0;
+        self.current_written = false;
+    }
+
+    /// Counts the current subimage as written, once pixels were written to it.
+    fn pixels_written(&mut self) {
+        if !self.current_written {
+            self.current_written = true;
+            self.written_subimages += 1;
+        }
+    }
+
+    /// Reopens the imageoutput to write a new subimage or MIP level.
+    fn reopen(
+        &mut self,
+        spec: &ImageSpec,
+        mode: sys::OIIO_ImageOutput_OpenMode,
+    ) -> Result<(), Error> {
+        let success = unsafe {
+            sys::OIIO_ImageOutput_open(self.ptr, self.path.as_stringref(), &spec.0, mode)
+        };
+        if !success {
+            return Err(Error::OpenError(self.get_last_error()));
+        }
+        Ok(())
+    }
+
     fn close_internal(&mut self) {
         unsafe {
             sys::OIIO_ImageOutput_close(self.ptr);
@@ -94,11 +293,54 @@ impl<'a> SingleImageOutput<'a> {
         unsafe { &*(sys::OIIO_ImageOutput_spec(self.0.ptr) as *const ImageSpec) }
     }
 
+    /// Finishes the current MIP level, and starts writing the next (smaller) one, with the
+    /// specified spec.
+    ///
+    /// Returns [Error::InvalidForImageType] if the writer does not support MIP maps.
+    pub fn append_miplevel(&mut self, spec: &ImageSpec) -> Result<(), Error> {
+        if !self.0.supports(OutputCapability::MipMap) {
+            return Err(Error::InvalidForImageType);
+        }
+        self.0
+            .reopen(spec, sys::OIIO_ImageOutput_OpenMode_AppendMIPLevel)
+    }
+
+    /// Writes all pixels of this subimage.
+    ///
+    /// Returns [Error::BufferTooSmall] if `pixels` does not contain the whole image.
     pub fn write_image<T: ImageData>(&mut self, pixels: &[T]) -> Result<(), Error> {
+        self.write_image_impl(pixels, None)
+    }
+
+    /// Same as [SingleImageOutput::write_image], but reports progress.
+    ///
+    /// `progress` is called periodically with the portion of the image that was written, between
+    /// 0 and 1. If it returns [ControlFlow::Break], writing stops and [Error::Cancelled] is
+    /// returned; the file is left incomplete.
+    pub fn write_image_with_progress<T: ImageData>(
+        &mut self,
+        pixels: &[T],
+        mut progress: impl FnMut(f32) -> ControlFlow<()>,
+    ) -> Result<(), Error> {
+        self.write_image_impl(pixels, Some(&mut progress))
+    }
+
+    fn write_image_impl<T: ImageData>(
+        &mut self,
+        pixels: &[T],
+        progress: Option<&mut dyn FnMut(f32) -> ControlFlow<()>>,
+    ) -> Result<(), Error> {
         let spec = self.spec();
-        let nch = spec.num_channels();
-        let _n = (spec.width() * spec.height() * spec.depth()) as usize * nch;
+        let n = spec.width() as usize
+            * spec.height() as usize
+            * spec.depth() as usize
+            * spec.num_channels();
+        if pixels.len() < n {
+            return Err(Error::BufferTooSmall);
+        }
 
+        let mut progress = progress.map(Progress::new);
+        let (callback, callback_data) = progress::raw_callback(progress.as_mut());
         let write_result = unsafe {
             sys::OIIO_ImageOutput_write_image(
                 self.0.ptr,
@@ -107,19 +349,34 @@ impl<'a> SingleImageOutput<'a> {
                 sys::OIIO_AutoStride,
                 sys::OIIO_AutoStride,
                 sys::OIIO_AutoStride,
+                callback,
+                callback_data,
             )
         };
-        if !write_result {
-            Err(Error::WriteError(self.0.get_last_error()))
-        } else {
-            Ok(())
+        progress::finish(progress, write_result, || {
+            Error::WriteError(self.0.get_last_error())
+        })?;
+        self.0.pixels_written();
+        Ok(())
+    }
+
+    fn check_write(&mut self, success: bool) -> Result<(), Error> {
+        if !success {
+            return Err(Error::WriteError(self.0.get_last_error()));
         }
+        self.0.pixels_written();
+        Ok(())
     }
 
+    /// Writes one scanline.
+    ///
+    /// Returns [Error::BufferTooSmall] if `pixels` does not contain a whole row of pixels.
     pub fn write_scanline<T: ImageData>(&mut self, y: i32, z: i32, pixels: &[T]) -> Result<(), Error> {
         let spec = self.spec();
-        let nch = spec.num_channels();
-        let _n = (spec.width() * spec.height() * spec.depth()) as usize * nch;
+        let n = spec.width() as usize * spec.num_channels();
+        if pixels.len() < n {
+            return Err(Error::BufferTooSmall);
+        }
 
         let write_result = unsafe {
             sys::OIIO_ImageOutput_write_scanline(
@@ -131,13 +388,374 @@ impl<'a> SingleImageOutput<'a> {
                 sys::OIIO_AutoStride,
             )
         };
-        if !write_result {
-            Err(Error::WriteError(self.0.get_last_error()))
+        self.check_write(write_result)
+    }
+
+    /// Writes a range of scanlines from a buffer with the specified strides.
+    ///
+    /// `pixels` contains `y.len()` rows of pixels, for all channels of the image. The z stride
+    /// is not used. Returns [Error::BufferTooSmall] if pixels would be read outside of
+    /// `pixels`.
+    pub fn write_scanlines<T: ImageData>(
+        &mut self,
+        y: Range<i32>,
+        z: i32,
+        pixels: &[T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        let spec = self.spec();
+        let len = strides.buffer_len(spec.num_channels(), spec.width() as usize, y.len(), 1);
+        if len > pixels.len() {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let [xstride, ystride, _] = strides.in_bytes::<T>();
+        let write_result = unsafe {
+            sys::OIIO_ImageOutput_write_scanlines(
+                self.0.ptr,
+                y.start,
+                y.end,
+                z,
+                T::DESC.0,
+                pixels.as_ptr() as *const c_void,
+                xstride,
+                ystride,
+            )
+        };
+        self.check_write(write_result)
+    }
+
+    /// Writes an arbitrary region of the image from a buffer with the specified strides.
+    ///
+    /// The channel range of `roi` is ignored: `pixels` contains all channels of the image.
+    /// If `roi` is [Roi::ALL], the whole image is written.
+    /// Returns [Error::InvalidForImageType] if the writer does not support writing rectangles,
+    /// and [Error::BufferTooSmall] if pixels would be read outside of `pixels`.
+    pub fn write_rectangle<T: ImageData>(
+        &mut self,
+        roi: Roi,
+        pixels: &[T],
+        strides: Strides,
+    ) -> Result<(), Error> {
+        if !self.0.supports(OutputCapability::Rectangles) {
+            return Err(Error::InvalidForImageType);
+        }
+        let roi = self.region(roi);
+        let len = strides.buffer_len(
+            self.spec().num_channels(),
+            roi.width() as usize,
+            roi.height() as usize,
+            roi.depth() as usize,
+        );
+        if len > pixels.len() {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let [xstride, ystride, zstride] = strides.in_bytes::<T>();
+        let write_result = unsafe {
+            sys::OIIO_ImageOutput_write_rectangle(
+                self.0.ptr,
+                roi.0.xbegin,
+                roi.0.xend,
+                roi.0.ybegin,
+                roi.0.yend,
+                roi.0.zbegin,
+                roi.0.zend,
+                T::DESC.0,
+                pixels.as_ptr() as *const c_void,
+                xstride,
+                ystride,
+                zstride,
+            )
+        };
+        self.check_write(write_result)
+    }
+
+    /// Writes one tile of the image.
+    ///
+    /// `(x, y, z)` are the pixel coordinates of the origin of the tile, which must be on a tile
+    /// boundary. `pixels` must contain a whole tile, for all channels of the image, even for
+    /// tiles on the edges of the image (pixels outside the image are ignored).
+    /// Tiles can be written in any order.
+    ///
+    /// Returns [Error::InvalidForImageType] if the output is not tiled, or if the writer does
+    /// not support tiles.
+    pub fn write_tile<T: ImageData>(
+        &mut self,
+        x: i32,
+        y: i32,
+        z: i32,
+        pixels: &[T],
+    ) -> Result<(), Error> {
+        self.check_tiled()?;
+        let spec = self.spec();
+        let n = (spec.tile_width() * spec.tile_height() * spec.tile_depth().max(1)) as usize
+            * spec.num_channels();
+        if pixels.len() < n {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let write_result = unsafe {
+            sys::OIIO_ImageOutput_write_tile(
+                self.0.ptr,
+                x,
+                y,
+                z,
+                T::DESC.0,
+                pixels.as_ptr() as *const c_void,
+                sys::OIIO_AutoStride,
+                sys::OIIO_AutoStride,
+                sys::OIIO_AutoStride,
+            )
+        };
+        self.check_write(write_result)
+    }
+
+    /// Writes the tiles covering the specified region.
+    ///
+    /// The region must be aligned on tile boundaries (or on the edges of the image); its
+    /// channel range is ignored. If `roi` is [Roi::ALL], all tiles are written. `pixels` must
+    /// contain the pixels of the region, for all channels of the image.
+    ///
+    /// Returns [Error::InvalidForImageType] if the output is not tiled, or if the writer does
+    /// not support tiles.
+    pub fn write_tiles<T: ImageData>(&mut self, roi: Roi, pixels: &[T]) -> Result<(), Error> {
+        self.check_tiled()?;
+        let roi = self.region(roi);
+        let n = roi.npixels() as usize * self.spec().num_channels();
+        if pixels.len() < n {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let write_result = unsafe {
+            sys::OIIO_ImageOutput_write_tiles(
+                self.0.ptr,
+                roi.0.xbegin,
+                roi.0.xend,
+                roi.0.ybegin,
+                roi.0.yend,
+                roi.0.zbegin,
+                roi.0.zend,
+                T::DESC.0,
+                pixels.as_ptr() as *const c_void,
+                sys::OIIO_AutoStride,
+                sys::OIIO_AutoStride,
+                sys::OIIO_AutoStride,
+            )
+        };
+        self.check_write(write_result)
+    }
+
+    /// Returns `roi`, or the data window of this subimage if it is [Roi::ALL].
+    fn region(&self, roi: Roi) -> Roi {
+        if roi.is_defined() {
+            roi
         } else {
-            Ok(())
+            self.spec().roi()
+        }
+    }
+
+    fn check_tiled(&self) -> Result<(), Error> {
+        // writers that don't support tiles silently write scanline images
+        if self.spec().tile_width() == 0 || !self.0.supports(OutputCapability::Tiles) {
+            return Err(Error::InvalidForImageType);
+        }
+        Ok(())
+    }
+
+    /// Writes the pixels of an [ImageBuf] to this subimage.
+    pub fn write_image_buf(&mut self, buf: &ImageBuf) -> Result<(), Error> {
+        let write_result =
+            unsafe { sys::OIIO_ImageBuf_write_output(buf.ptr, self.0.ptr, None, ptr::null_mut()) };
+        if !write_result {
+            return Err(Error::WriteError(buf.get_last_error()));
+        }
+        self.0.pixels_written();
+        Ok(())
+    }
+
+    /// Writes a range of scanlines of a deep image.
+    ///
+    /// `data` must contain `y.len()` rows of pixels, for all channels of the image.
+    /// Returns [Error::InvalidForImageType] if the spec of the output is not deep.
+    pub fn write_deep_scanlines(
+        &mut self,
+        y: Range<i32>,
+        z: i32,
+        data: &DeepData,
+    ) -> Result<(), Error> {
+        let ptr = self.0.ptr;
+        self.write_deep(|| unsafe {
+            sys::OIIO_ImageOutput_write_deep_scanlines(ptr, y.start, y.end, z, data.ptr)
+        })
+    }
+
+    /// Writes the tiles of a deep image covering the specified region.
+    ///
+    /// The region must be aligned on tile boundaries (or on the edges of the image); its
+    /// channel range is ignored. If `roi` is [Roi::ALL], all tiles are written.
+    /// Returns [Error::InvalidForImageType] if the spec of the output is not deep.
+    pub fn write_deep_tiles(&mut self, roi: Roi, data: &DeepData) -> Result<(), Error> {
+        let roi = self.region(roi);
+        let ptr = self.0.ptr;
+        self.write_deep(|| unsafe {
+            sys::OIIO_ImageOutput_write_deep_tiles(
+                ptr,
+                roi.0.xbegin,
+                roi.0.xend,
+                roi.0.ybegin,
+                roi.0.yend,
+                roi.0.zbegin,
+                roi.0.zend,
+                data.ptr,
+            )
+        })
+    }
+
+    /// Writes all pixels of a deep image.
+    ///
+    /// Returns [Error::InvalidForImageType] if the spec of the output is not deep.
+    pub fn write_deep_image(&mut self, data: &DeepData) -> Result<(), Error> {
+        let ptr = self.0.ptr;
+        self.write_deep(|| unsafe { sys::OIIO_ImageOutput_write_deep_image(ptr, data.ptr) })
+    }
+
+    fn write_deep(&mut self, f: impl FnOnce() -> bool) -> Result<(), Error> {
+        if !self.spec().is_deep() {
+            return Err(Error::InvalidForImageType);
+        }
+        let success = f();
+        self.check_write(success)
+    }
+
+    /// Copies the pixels of a subimage and MIP level of an image input to this subimage.
+    ///
+    /// Pixels are copied in their native format, without conversion when the formats allow
+    /// it, and sometimes without decompressing them (e.g. from JPEG to JPEG). Metadata is not
+    /// copied: open this output with the spec of the input to keep it. Both images must have
+    /// the same size.
+    ///
+    /// Returns [Error::ReadError] if the input could not be read, and [Error::WriteError] if
+    /// the pixels could not be written.
+    pub fn copy_from(&mut self, input: &SubimageInput) -> Result<(), Error> {
+        let image = input.input;
+        let success = unsafe {
+            sys::OIIO_ImageOutput_copy_image(
+                self.0.ptr,
+                image.ptr,
+                input.subimage_index() as i32,
+                input.miplevel_index() as i32,
+            )
+        };
+        if success {
+            self.0.pixels_written();
+            return Ok(());
+        }
+        // the input reports seek and read failures, the output the others
+        match image.get_last_error() {
+            msg if msg.is_empty() => Err(Error::WriteError(self.0.get_last_error())),
+            msg => Err(Error::ReadError(msg)),
         }
     }
 
     // finish writing to this subimage (and release the borrow)
     pub fn close(self) {}
 }
+
+impl<'a> MultiImageOutput<'a> {
+    /// Returns the index of the subimage being written.
+    pub fn subimage_index(&self) -> usize {
+        self.0 .0.started_subimages - 1
+    }
+
+    /// Returns the number of subimages declared in [ImageOutput::open_multi].
+    pub fn num_subimages(&self) -> usize {
+        self.0 .0.subimages
+    }
+
+    /// Finishes the current subimage, and starts writing the next one, with the specified
+    /// spec.
+    ///
+    /// The spec is usually the one declared in [ImageOutput::open_multi] for this subimage.
+    /// Returns [Error::SubimageNotFound] if all declared subimages were already started.
+    pub fn append_subimage(&mut self, spec: &ImageSpec) -> Result<(), Error> {
+        let output = &mut *self.0 .0;
+        if output.started_subimages >= output.subimages {
+            return Err(Error::SubimageNotFound);
+        }
+        output.reopen(spec, sys::OIIO_ImageOutput_OpenMode_AppendSubimage)?;
+        output.started_subimages += 1;
+        output.current_written = false;
+        Ok(())
+    }
+
+    // finish writing (and release the borrow)
+    pub fn close(self) {}
+}
+
+// pixels are written to the current subimage and MIP level
+impl<'a> Deref for MultiImageOutput<'a> {
+    type Target = SingleImageOutput<'a>;
+
+    fn deref(&self) -> &SingleImageOutput<'a> {
+        &self.0
+    }
+}
+
+impl<'a> DerefMut for MultiImageOutput<'a> {
+    fn deref_mut(&mut self) -> &mut SingleImageOutput<'a> {
+        &mut self.0
+    }
+}
+
+/// An optional capability of an image writer, queried with [ImageOutput::supports].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum OutputCapability {
+    /// The writer can write tiled images.
+    Tiles,
+    /// The writer can write arbitrary rectangles of pixels.
+    Rectangles,
+    /// The writer can write tiles or scanlines in any order.
+    RandomAccess,
+    /// The writer can write multiple subimages in one file.
+    MultiImage,
+    /// The writer can append subimages to a file opened for a single subimage.
+    AppendSubimage,
+    /// The writer can write MIP levels.
+    MipMap,
+    /// The writer can write volume (3D) images.
+    Volumes,
+    /// The writer can write a different format for each channel.
+    ChannelFormats,
+    /// The writer can write deep images.
+    Deep,
+    /// The writer can write to memory or custom streams instead of files.
+    IoProxy,
+    /// The writer can write EXIF metadata.
+    Exif,
+    /// The writer can write IPTC metadata.
+    Iptc,
+    /// The format can store arbitrarily named metadata.
+    ArbitraryMetadata,
+}
+
+impl OutputCapability {
+    /// Returns the OpenImageIO name of this capability.
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputCapability::Tiles => "tiles",
+            OutputCapability::Rectangles => "rectangles",
+            OutputCapability::RandomAccess => "random_access",
+            OutputCapability::MultiImage => "multiimage",
+            OutputCapability::AppendSubimage => "appendsubimage",
+            OutputCapability::MipMap => "mipmap",
+            OutputCapability::Volumes => "volumes",
+            OutputCapability::ChannelFormats => "channelformats",
+            OutputCapability::Deep => "deep",
+            OutputCapability::IoProxy => "ioproxy",
+            OutputCapability::Exif => "exif",
+            OutputCapability::Iptc => "iptc",
+            OutputCapability::ArbitraryMetadata => "arbitrary_metadata",
+        }
+    }
+}
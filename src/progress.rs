@@ -0,0 +1,97 @@
+//! Progress reporting and cancellation of long-running operations.
+use crate::Error;
+use openimageio_sys as sys;
+use std::{
+    any::Any,
+    ffi::c_void,
+    ops::ControlFlow,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+/// Wraps a user progress callback so that it can be passed to OpenImageIO.
+///
+/// The callback receives the portion of the operation that is done, between 0 and 1, and
+/// returns [ControlFlow::Break] to cancel the operation.
+pub(crate) struct Progress<'a> {
+    callback: &'a mut dyn FnMut(f32) -> ControlFlow<()>,
+    cancelled: bool,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<'a> Progress<'a> {
+    pub(crate) fn new(callback: &'a mut dyn FnMut(f32) -> ControlFlow<()>) -> Progress<'a> {
+        Progress {
+            callback,
+            cancelled: false,
+            panic: None,
+        }
+    }
+
+    /// Calls the callback, and returns whether the operation should stop.
+    ///
+    /// Once cancelled, the callback is not called anymore.
+    pub(crate) fn report(&mut self, portion_done: f32) -> bool {
+        if !self.cancelled {
+            let callback = &mut self.callback;
+            // panics must not unwind into C++: stop the operation and resume in `finish`
+            match panic::catch_unwind(AssertUnwindSafe(|| callback(portion_done))) {
+                Ok(flow) => self.cancelled = flow.is_break(),
+                Err(payload) => {
+                    self.panic = Some(payload);
+                    self.cancelled = true;
+                }
+            }
+        }
+        self.cancelled
+    }
+
+    /// Converts the result of an operation that reported progress into a `Result`.
+    ///
+    /// Returns [Error::Cancelled] if the callback asked to stop, even if OpenImageIO reported
+    /// success (some operations stop early without failing), and resumes a panic of the callback.
+    pub(crate) fn finish(self, success: bool, error: impl FnOnce() -> Error) -> Result<(), Error> {
+        if let Some(payload) = self.panic {
+            panic::resume_unwind(payload);
+        }
+        if self.cancelled {
+            Err(Error::Cancelled)
+        } else if success {
+            Ok(())
+        } else {
+            Err(error())
+        }
+    }
+}
+
+/// Returns the callback and callback data to pass to OpenImageIO for an optional progress
+/// callback.
+pub(crate) fn raw_callback(
+    progress: Option<&mut Progress>,
+) -> (sys::ProgressCallback, *mut c_void) {
+    match progress {
+        Some(progress) => (
+            Some(progress_callback),
+            progress as *mut Progress as *mut c_void,
+        ),
+        None => (None, ptr::null_mut()),
+    }
+}
+
+/// Converts the result of an operation that may have reported progress into a `Result`.
+pub(crate) fn finish(
+    progress: Option<Progress>,
+    success: bool,
+    error: impl FnOnce() -> Error,
+) -> Result<(), Error> {
+    match progress {
+        Some(progress) => progress.finish(success, error),
+        None if success => Ok(()),
+        None => Err(error()),
+    }
+}
+
+unsafe extern "C" fn progress_callback(opaque: *mut c_void, portion_done: f32) -> bool {
+    let progress = &mut *(opaque as *mut Progress);
+    progress.report(portion_done)
+}
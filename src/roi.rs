@@ -1,6 +1,223 @@
-/*
-pub struct Roi {
-    pub xs: Range<i32>,
-    pub ys: Range<i32>,
-    pub zs: Range<i32>,
-}*/
+//! Regions of interest.
+use crate::spec::Window;
+use openimageio_sys as sys;
+use std::{cmp, ops::Range};
+
+/// Region of interest: a range of pixels in x, y and z, and a range of channels.
+///
+/// The special value [Roi::ALL] is _undefined_ and means "the whole image" to the functions
+/// that accept a `Roi`.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Roi(pub(crate) sys::OIIO_ROI);
+
+impl Roi {
+    /// Undefined region, meaning "all pixels and all channels of the image".
+    pub const ALL: Roi = Roi(sys::OIIO_ROI {
+        xbegin: i32::MIN,
+        xend: 0,
+        ybegin: 0,
+        yend: 0,
+        zbegin: 0,
+        zend: 0,
+        chbegin: 0,
+        chend: 0,
+    });
+
+    /// Creates a region from the specified pixel and channel ranges.
+    pub fn new(xs: Range<i32>, ys: Range<i32>, zs: Range<i32>, channels: Range<usize>) -> Roi {
+        Roi(sys::OIIO_ROI {
+            xbegin: xs.start,
+            xend: xs.end,
+            ybegin: ys.start,
+            yend: ys.end,
+            zbegin: zs.start,
+            zend: zs.end,
+            chbegin: channels.start as i32,
+            chend: channels.end as i32,
+        })
+    }
+
+    /// Creates a 2D region (`z` is `0..1`) covering all channels.
+    pub fn new_2d(xs: Range<i32>, ys: Range<i32>) -> Roi {
+        // same default channel range as OpenImageIO
+        Roi::new(xs, ys, 0..1, 0..10000)
+    }
+
+    /// Returns whether this region is defined (i.e. not [Roi::ALL]).
+    pub fn is_defined(&self) -> bool {
+        self.0.xbegin != i32::MIN
+    }
+
+    /// Returns the range of x coordinates of this region.
+    pub fn xs(&self) -> Range<i32> {
+        self.0.xbegin..self.0.xend
+    }
+
+    /// Returns the range of y coordinates of this region.
+    pub fn ys(&self) -> Range<i32> {
+        self.0.ybegin..self.0.yend
+    }
+
+    /// Returns the range of z coordinates of this region.
+    pub fn zs(&self) -> Range<i32> {
+        self.0.zbegin..self.0.zend
+    }
+
+    /// Returns the range of channels of this region.
+    pub fn channels(&self) -> Range<usize> {
+        self.0.chbegin as usize..self.0.chend as usize
+    }
+
+    /// Returns a copy of this region with the specified channel range.
+    pub fn with_channels(self, channels: Range<usize>) -> Roi {
+        let mut roi = self;
+        roi.0.chbegin = channels.start as i32;
+        roi.0.chend = channels.end as i32;
+        roi
+    }
+
+    /// Returns the width of this region, or 0 if it is undefined.
+    pub fn width(&self) -> u32 {
+        self.extent(self.0.xbegin, self.0.xend)
+    }
+
+    /// Returns the height of this region, or 0 if it is undefined.
+    pub fn height(&self) -> u32 {
+        self.extent(self.0.ybegin, self.0.yend)
+    }
+
+    /// Returns the depth of this region, or 0 if it is undefined.
+    pub fn depth(&self) -> u32 {
+        self.extent(self.0.zbegin, self.0.zend)
+    }
+
+    fn extent(&self, begin: i32, end: i32) -> u32 {
+        if self.is_defined() {
+            // the difference of two i32 may not fit in an i32
+            cmp::max(end as i64 - begin as i64, 0) as u32
+        } else {
+            0
+        }
+    }
+
+    /// Returns the number of channels in this region.
+    pub fn num_channels(&self) -> usize {
+        cmp::max(self.0.chend - self.0.chbegin, 0) as usize
+    }
+
+    /// Returns the number of pixels in this region, or 0 if it is undefined.
+    pub fn npixels(&self) -> u64 {
+        if self.is_defined() {
+            self.width() as u64 * self.height() as u64 * self.depth() as u64
+        } else {
+            0
+        }
+    }
+
+    /// Returns whether this region is defined but contains no pixels or no channels.
+    pub fn is_empty(&self) -> bool {
+        self.is_defined() && (self.npixels() == 0 || self.num_channels() == 0)
+    }
+
+    /// Returns whether the pixel at `(x, y, z)` is inside this region.
+    ///
+    /// An undefined region contains all pixels.
+    pub fn contains_pixel(&self, x: i32, y: i32, z: i32) -> bool {
+        !self.is_defined()
+            || (self.xs().contains(&x) && self.ys().contains(&y) && self.zs().contains(&z))
+    }
+
+    /// Returns whether `other` is entirely inside this region, including its channel range.
+    ///
+    /// An undefined region contains all regions.
+    pub fn contains(&self, other: &Roi) -> bool {
+        if !self.is_defined() {
+            return true;
+        }
+        if !other.is_defined() {
+            return false;
+        }
+        let (a, b) = (&self.0, &other.0);
+        b.xbegin >= a.xbegin
+            && b.xend <= a.xend
+            && b.ybegin >= a.ybegin
+            && b.yend <= a.yend
+            && b.zbegin >= a.zbegin
+            && b.zend <= a.zend
+            && b.chbegin >= a.chbegin
+            && b.chend <= a.chend
+    }
+
+    /// Returns the smallest region containing both this region and `other`.
+    ///
+    /// If one of the regions is undefined, the other one is returned.
+    pub fn union(&self, other: &Roi) -> Roi {
+        if !self.is_defined() {
+            return *other;
+        }
+        if !other.is_defined() {
+            return *self;
+        }
+        let (a, b) = (&self.0, &other.0);
+        Roi(sys::OIIO_ROI {
+            xbegin: cmp::min(a.xbegin, b.xbegin),
+            xend: cmp::max(a.xend, b.xend),
+            ybegin: cmp::min(a.ybegin, b.ybegin),
+            yend: cmp::max(a.yend, b.yend),
+            zbegin: cmp::min(a.zbegin, b.zbegin),
+            zend: cmp::max(a.zend, b.zend),
+            chbegin: cmp::min(a.chbegin, b.chbegin),
+            chend: cmp::max(a.chend, b.chend),
+        })
+    }
+
+    /// Returns the region covered by both this region and `other`, which may be empty.
+    ///
+    /// If one of the regions is undefined, the other one is returned.
+    pub fn intersection(&self, other: &Roi) -> Roi {
+        if !self.is_defined() {
+            return *other;
+        }
+        if !other.is_defined() {
+            return *self;
+        }
+        let (a, b) = (&self.0, &other.0);
+        Roi(sys::OIIO_ROI {
+            xbegin: cmp::max(a.xbegin, b.xbegin),
+            xend: cmp::min(a.xend, b.xend),
+            ybegin: cmp::max(a.ybegin, b.ybegin),
+            yend: cmp::min(a.yend, b.yend),
+            zbegin: cmp::max(a.zbegin, b.zbegin),
+            zend: cmp::min(a.zend, b.zend),
+            chbegin: cmp::max(a.chbegin, b.chbegin),
+            chend: cmp::min(a.chend, b.chend),
+        })
+    }
+}
+
+impl Default for Roi {
+    fn default() -> Roi {
+        Roi::ALL
+    }
+}
+
+/// Region covering the window, and all channels.
+impl From<Window> for Roi {
+    fn from(w: Window) -> Roi {
+        Roi::new(
+            w.x..w.x + w.width as i32,
+            w.y..w.y + w.height as i32,
+            w.z..w.z + w.depth as i32,
+            0..10000,
+        )
+    }
+}
+
+/// Region covering the `(xs, ys, zs)` ranges returned by
+/// [ImageSpec::calculate_bounds](crate::ImageSpec::calculate_bounds), and all channels.
+impl From<(Range<i32>, Range<i32>, Range<i32>)> for Roi {
+    fn from((xs, ys, zs): (Range<i32>, Range<i32>, Range<i32>)) -> Roi {
+        Roi::new(xs, ys, zs, 0..10000)
+    }
+}
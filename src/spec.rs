@@ -1,8 +1,9 @@
 //! Types describing the format and content of an image file.
-use crate::{Error, TypeDesc};
+use crate::{attribute::AttributeType, Error, Roi, TypeDesc};
 use openimageio_sys as sys;
 use openimageio_sys::AsStringRef;
 use std::{
+    borrow::Borrow,
     ffi::CStr,
     ops::{Bound, Deref, Range, RangeBounds},
     os::raw::c_int,
@@ -41,6 +42,7 @@ pub struct ChannelDesc {
 pub struct ImageSpec(pub(crate) sys::OIIO_ImageSpec); // ImageSpec is zero-sized
 
 /// Represents a rectangular window in some coordinate space.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Window {
     pub x: i32,
     pub y: i32,
@@ -73,6 +75,11 @@ impl ImageSpec {
         }
     }
 
+    /// Returns the data window of the image as a [Roi] covering all channels.
+    pub fn roi(&self) -> Roi {
+        Roi::from(self.data_window()).with_channels(self.all_channels())
+    }
+
     /// Equivalent to `self.data_window().x`.
     pub fn x(&self) -> i32 {
         unsafe { sys::OIIO_ImageSpec_x(&self.0) }
@@ -144,6 +151,11 @@ impl ImageSpec {
         }
     }
 
+    /// Returns the display window of the image as a [Roi] covering all channels.
+    pub fn display_roi(&self) -> Roi {
+        Roi::from(self.display_window()).with_channels(self.all_channels())
+    }
+
     /// Equivalent to `self.display_window().x`.
     pub fn display_x(&self) -> i32 {
         unsafe { sys::OIIO_ImageSpec_full_x(&self.0) }
@@ -204,6 +216,13 @@ impl ImageSpec {
         unsafe { sys::OIIO_ImageSpec_tile_depth(&self.0) as u32 }
     }
 
+    /// Returns whether this is a deep image, with a variable number of samples per pixel.
+    ///
+    /// The pixels of deep images are read and written as [DeepData](crate::DeepData).
+    pub fn is_deep(&self) -> bool {
+        unsafe { sys::OIIO_ImageSpec_deep(&self.0) }
+    }
+
     /// (OpenImageIO) The number of channels (color values) present in each pixel of the image.
     ///
     /// For example, an RGB image has 3 channels.
@@ -407,6 +426,10 @@ impl ImageSpec {
 /// Version of [ImageSpec] that owns its data.
 pub struct ImageSpecOwned(pub(crate) *mut sys::OIIO_ImageSpec);
 
+// The spec is plain data owned by this object, only mutated through `&mut self`.
+unsafe impl Send for ImageSpecOwned {}
+unsafe impl Sync for ImageSpecOwned {}
+
 impl Clone for ImageSpecOwned {
     fn clone(&self) -> Self {
         unsafe { ImageSpecOwned(sys::OIIO_ImageSpec_clone(self.0)) }
@@ -444,6 +467,58 @@ impl ImageSpecOwned {
 
         ImageSpecOwned(ptr)
     }
+
+    /// Sets whether this describes a deep image.
+    pub fn set_deep(&mut self, deep: bool) {
+        unsafe { sys::OIIO_ImageSpec_set_deep(self.0, deep) }
+    }
+
+    /// Sets the size of the tiles of the image, in pixels. A width of 0 describes a scanline
+    /// image; use a depth of 1 for 2D images.
+    ///
+    /// Writers that don't support tiles (see
+    /// [OutputCapability::Tiles](crate::OutputCapability::Tiles)) ignore the tile size.
+    pub fn set_tile_size(&mut self, width: u32, height: u32, depth: u32) {
+        unsafe {
+            sys::OIIO_ImageSpec_set_tile_width(self.0, width as c_int);
+            sys::OIIO_ImageSpec_set_tile_height(self.0, height as c_int);
+            sys::OIIO_ImageSpec_set_tile_depth(self.0, depth as c_int);
+        }
+    }
+
+    /// Same as [ImageSpecOwned::set_tile_size], but takes and returns the spec, to chain with
+    /// its constructors.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use openimageio::{ImageSpecOwned, TypeDesc};
+    ///
+    /// let spec = ImageSpecOwned::new_2d(TypeDesc::HALF, 1920, 1080, &["R", "G", "B", "A"])
+    ///     .with_tile_size(64, 64, 1);
+    /// ```
+    pub fn with_tile_size(mut self, width: u32, height: u32, depth: u32) -> ImageSpecOwned {
+        self.set_tile_size(width, height, depth);
+        self
+    }
+
+    /// Sets the value of an attribute (arbitrary named metadata), replacing any existing
+    /// attribute with the same name.
+    pub fn set_attribute<A: AttributeType>(&mut self, name: &str, value: A) {
+        unsafe {
+            value
+                .set(|ptr| {
+                    sys::OIIO_ImageSpec_attribute_type_data(
+                        self.0,
+                        name.as_stringref(),
+                        A::TYPEDESC.0,
+                        ptr,
+                    );
+                    Ok(())
+                })
+                .unwrap()
+        }
+    }
 }
 
 impl Drop for ImageSpecOwned {
@@ -461,3 +536,17 @@ impl Deref for ImageSpecOwned {
         unsafe { &*(self.0 as *const ImageSpec) }
     }
 }
+
+impl Borrow<ImageSpec> for ImageSpecOwned {
+    fn borrow(&self) -> &ImageSpec {
+        self
+    }
+}
+
+impl ToOwned for ImageSpec {
+    type Owned = ImageSpecOwned;
+
+    fn to_owned(&self) -> ImageSpecOwned {
+        unsafe { ImageSpecOwned(sys::OIIO_ImageSpec_clone(&self.0)) }
+    }
+}
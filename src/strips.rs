@@ -0,0 +1,114 @@
+//! Strip-by-strip reading of scanlines.
+use crate::{typedesc::ImageData, Error, ImageBuffer, Strides, SubimageInput};
+use std::ops::Range;
+
+/// A strip of scanlines, returned by [Strips::next_strip].
+pub struct Strip<'s, T: ImageData> {
+    /// Range of scanlines of the strip, in the coordinates of the data window.
+    pub rows: Range<i32>,
+    /// Pixels of the strip.
+    pub data: &'s ImageBuffer<T>,
+}
+
+/// Reader of successive strips of scanlines, returned by [ImageInput::strips].
+///
+/// Strips are read into a single buffer, which is reused for every strip: memory usage does not
+/// depend on the height of the image. Because of this, `Strips` is not an [Iterator]; call
+/// [Strips::next_strip] in a loop instead:
+///
+/// ```no_run
+/// # use openimageio::{ImageInput, Error};
+/// # fn main() -> Result<(), Error> {
+/// let input = ImageInput::open("scan.tif")?;
+/// let mut strips = input.strips::<f32>(0, 0, 64, input.all_channels())?;
+/// while let Some(strip) = strips.next_strip() {
+///     let strip = strip?;
+///     // process `strip.data`, which contains the scanlines `strip.rows`
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [ImageInput::strips]: crate::ImageInput::strips
+pub struct Strips<'a, T: ImageData> {
+    input: SubimageInput<'a>,
+    channels: Range<usize>,
+    rows_per_strip: usize,
+    rows: Range<i32>,
+    buffer: ImageBuffer<T>,
+}
+
+impl<'a, T: ImageData> Strips<'a, T> {
+    pub(crate) fn new(
+        input: SubimageInput<'a>,
+        rows_per_strip: usize,
+        channels: Range<usize>,
+    ) -> Result<Self, Error> {
+        if rows_per_strip == 0 {
+            return Err(Error::InvalidParameter);
+        }
+        if input.depth() > 1 {
+            return Err(Error::InvalidForImageType);
+        }
+        let spec = input.spec();
+        // OIIO clamps the channels to those of the image: the buffer would not match
+        let channels = spec.channel_range(channels)?;
+        let rows = spec.y()..spec.y() + spec.height() as i32;
+        let width = spec.width() as usize;
+        // a strip never has more rows than the image, which also keeps it in range of i32
+        let rows_per_strip = rows_per_strip.min(rows.len());
+        let capacity = width * rows_per_strip * channels.len();
+        Ok(Strips {
+            buffer: ImageBuffer {
+                width,
+                height: 0,
+                depth: 1,
+                num_channels: channels.len(),
+                data: Vec::with_capacity(capacity),
+            },
+            input,
+            channels,
+            rows_per_strip,
+            rows,
+        })
+    }
+
+    /// Reads the next strip.
+    ///
+    /// The last strip may have fewer rows. Returns `None` once all strips have been read, or
+    /// after an error.
+    pub fn next_strip(&mut self) -> Option<Result<Strip<'_, T>, Error>> {
+        if self.rows.is_empty() {
+            return None;
+        }
+        let start = self.rows.start;
+        let end = (start + self.rows_per_strip as i32).min(self.rows.end);
+        let n = self.buffer.width * (end - start) as usize * self.channels.len();
+
+        self.buffer.data.clear();
+        self.buffer.data.reserve(n);
+        let z = self.input.spec().z();
+        let result = unsafe {
+            self.input.read_scanlines_unchecked(
+                start..end,
+                z,
+                self.channels.clone(),
+                self.buffer.data.as_mut_ptr(),
+                Strides::contiguous(self.channels.len(), self.buffer.width, 1),
+            )
+        };
+        if let Err(err) = result {
+            self.rows.start = self.rows.end;
+            return Some(Err(err));
+        }
+        unsafe {
+            self.buffer.data.set_len(n);
+        }
+        self.buffer.height = (end - start) as usize;
+        self.rows.start = end;
+        Some(Ok(Strip {
+            rows: start..end,
+            data: &self.buffer,
+        }))
+    }
+}
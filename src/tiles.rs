@@ -0,0 +1,206 @@
+//! Tile-by-tile reading of tiled images.
+use crate::{
+    typedesc::ImageData, Error, ImageBuffer, ImageInput, ImageSpec, Roi, Strides, SubimageInput,
+};
+use std::{
+    marker::PhantomData,
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// A tile of an image, returned by [ImageInput::tiles] and [ImageInput::par_read_tiles].
+pub struct Tile<T: ImageData> {
+    /// Column of the tile in the tile grid, starting at 0.
+    pub x: usize,
+    /// Row of the tile in the tile grid, starting at 0.
+    pub y: usize,
+    /// Layer of the tile in the tile grid (for volume images), starting at 0.
+    pub z: usize,
+    /// Pixels and channels covered by the tile, clipped to the data window of the image.
+    pub roi: Roi,
+    /// Pixels of the tile. Its size is the size of `roi`.
+    pub data: ImageBuffer<T>,
+}
+
+/// Division of the data window of an image into tiles.
+struct TileGrid {
+    window: Roi,
+    tile_size: (i32, i32, i32),
+    counts: (usize, usize, usize),
+}
+
+impl TileGrid {
+    fn new(spec: &ImageSpec, channels: Range<usize>) -> Result<TileGrid, Error> {
+        if spec.tile_width() == 0 {
+            // scanline image
+            return Err(Error::InvalidForImageType);
+        }
+        // OIIO clamps the channels to those of the image: the buffers would not match
+        let channels = spec.channel_range(channels)?;
+        let tile_size = (
+            spec.tile_width() as i32,
+            spec.tile_height().max(1) as i32,
+            spec.tile_depth().max(1) as i32,
+        );
+        let count = |size: u32, tile_size: i32| (size as usize).div_ceil(tile_size as usize);
+        Ok(TileGrid {
+            window: spec.roi().with_channels(channels),
+            tile_size,
+            counts: (
+                count(spec.width(), tile_size.0),
+                count(spec.height(), tile_size.1),
+                count(spec.depth(), tile_size.2),
+            ),
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.counts.0 * self.counts.1 * self.counts.2
+    }
+
+    /// Returns the position in the grid and the region of the tile with the specified index,
+    /// in file order (x first, then y, then z).
+    fn tile(&self, index: usize) -> (usize, usize, usize, Roi) {
+        let x = index % self.counts.0;
+        let y = index / self.counts.0 % self.counts.1;
+        let z = index / (self.counts.0 * self.counts.1);
+        let w = &self.window.0;
+        let range = |begin: i32, end: i32, i: usize, size: i32| {
+            let start = begin + i as i32 * size;
+            start..(start + size).min(end)
+        };
+        let roi = Roi::new(
+            range(w.xbegin, w.xend, x, self.tile_size.0),
+            range(w.ybegin, w.yend, y, self.tile_size.1),
+            range(w.zbegin, w.zend, z, self.tile_size.2),
+            w.chbegin as usize..w.chend as usize,
+        );
+        (x, y, z, roi)
+    }
+
+    fn read_tile<T: ImageData>(
+        &self,
+        input: &SubimageInput,
+        index: usize,
+    ) -> Result<Tile<T>, Error> {
+        let (x, y, z, roi) = self.tile(index);
+        let n = roi.npixels() as usize * roi.num_channels();
+        let strides = Strides::contiguous(
+            roi.num_channels(),
+            roi.width() as usize,
+            roi.height() as usize,
+        );
+        let mut data = Vec::with_capacity(n);
+        unsafe {
+            input.read_tiles_unchecked(roi, data.as_mut_ptr(), strides)?;
+            data.set_len(n);
+        }
+        Ok(Tile {
+            x,
+            y,
+            z,
+            roi,
+            data: ImageBuffer {
+                width: roi.width() as usize,
+                height: roi.height() as usize,
+                depth: roi.depth() as usize,
+                num_channels: roi.num_channels(),
+                data,
+            },
+        })
+    }
+}
+
+/// Iterator over the tiles of an image, returned by [ImageInput::tiles].
+pub struct Tiles<'a, T: ImageData> {
+    input: SubimageInput<'a>,
+    grid: TileGrid,
+    next: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: ImageData> Tiles<'a, T> {
+    pub(crate) fn new(input: SubimageInput<'a>, channels: Range<usize>) -> Result<Self, Error> {
+        let grid = TileGrid::new(input.spec(), channels)?;
+        Ok(Tiles {
+            input,
+            grid,
+            next: 0,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<'a, T: ImageData> Iterator for Tiles<'a, T> {
+    type Item = Result<Tile<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.grid.len() {
+            return None;
+        }
+        let tile = self.grid.read_tile(&self.input, self.next);
+        self.next += 1;
+        Some(tile)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.grid.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Implementation of [ImageInput::par_read_tiles].
+pub(crate) fn par_read_tiles<T: ImageData>(
+    input: &ImageInput,
+    subimage: &SubimageInput,
+    channels: Range<usize>,
+    nthreads: usize,
+    f: impl Fn(Tile<T>) + Sync,
+) -> Result<(), Error> {
+    let grid = TileGrid::new(subimage.spec(), channels)?;
+    let nthreads = match nthreads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(grid.len());
+    // `ImageInput` is not `Sync`: open the inputs here and move one to each thread
+    let inputs = (0..nthreads)
+        .map(|_| input.reopen())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (subimage, miplevel) = (subimage.subimage_index(), subimage.miplevel_index());
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let error = Mutex::new(None);
+    thread::scope(|scope| {
+        for input in inputs {
+            let (grid, next, stop, error, f) = (&grid, &next, &stop, &error, &f);
+            scope.spawn(move || {
+                let result = input
+                    .subimage_miplevel(subimage, miplevel)
+                    .and_then(|input| loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= grid.len() || stop.load(Ordering::Relaxed) {
+                            break Ok(());
+                        }
+                        match grid.read_tile(&input, index) {
+                            Ok(tile) => f(tile),
+                            Err(err) => break Err(err),
+                        }
+                    });
+                if let Err(err) = result {
+                    stop.store(true, Ordering::Relaxed);
+                    error.lock().unwrap().get_or_insert(err);
+                }
+            });
+        }
+    });
+    match error.into_inner().unwrap() {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
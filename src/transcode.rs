@@ -0,0 +1,111 @@
+//! Copying images to another file or format.
+use crate::{
+    writeoptions, Error, ImageInput, ImageOutput, ImageSpec, ImageSpecOwned, OutputCapability,
+    WriteOptions,
+};
+use std::{path::Path, sync::Arc};
+
+type ApplyOptions = dyn Fn(&mut ImageSpecOwned) -> Result<(), Error> + Send + Sync;
+
+/// Options of [transcode].
+#[derive(Clone, Default)]
+pub struct TranscodeOptions {
+    // format of the writer options, and function applying them to a spec
+    write_options: Option<(&'static str, Arc<ApplyOptions>)>,
+}
+
+impl TranscodeOptions {
+    /// Creates a set of options that copies images with the default options of the writer.
+    pub fn new() -> TranscodeOptions {
+        TranscodeOptions::default()
+    }
+
+    /// Sets the options of the writer, applied to every subimage and MIP level.
+    ///
+    /// [transcode] returns [Error::InvalidOption] if they are for another format than the
+    /// destination file.
+    pub fn write_options<O>(&mut self, options: &O) -> &mut TranscodeOptions
+    where
+        O: WriteOptions + Clone + Send + Sync + 'static,
+    {
+        let options = options.clone();
+        self.write_options = Some((
+            O::FORMAT_NAME,
+            Arc::new(move |spec: &mut ImageSpecOwned| options.apply(spec)),
+        ));
+        self
+    }
+}
+
+/// Copies all subimages and MIP levels of the image file `src` to the file `dst`, whose format
+/// is deduced from its extension.
+///
+/// Pixels are copied in their native format, including per-channel formats when the
+/// destination format supports them, so that no precision is lost. All metadata of the
+/// source is kept, although the destination format may not be able to store all of it.
+///
+/// Returns [Error::InvalidForImageType] if the source has several subimages or MIP levels and
+/// the destination format cannot store them.
+///
+/// # Example
+///
+/// ```no_run
+/// use openimageio::{transcode, ExrCompression, ExrOptions, TranscodeOptions};
+///
+/// transcode(
+///     "scan.tif",
+///     "scan.exr",
+///     TranscodeOptions::new().write_options(ExrOptions::new().compression(ExrCompression::Zip)),
+/// )
+/// .unwrap();
+/// ```
+pub fn transcode(
+    src: impl AsRef<Path>,
+    dst: impl AsRef<Path>,
+    options: &TranscodeOptions,
+) -> Result<(), Error> {
+    let input = ImageInput::open(src)?;
+    // specs of the MIP levels of each subimage
+    let mut levels = input
+        .subimages()
+        .map(|subimage| {
+            subimage
+                .miplevels()
+                .map(|level| level.spec().to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = ImageOutput::create(dst)?;
+    if levels.len() > 1 && !output.supports(OutputCapability::MultiImage) {
+        return Err(Error::InvalidForImageType);
+    }
+    if levels.iter().any(|specs| specs.len() > 1) && !output.supports(OutputCapability::MipMap) {
+        return Err(Error::InvalidForImageType);
+    }
+    if let Some((format_name, ref apply)) = options.write_options {
+        writeoptions::check_format(format_name, output.format_name())?;
+        for spec in levels.iter_mut().flatten() {
+            apply(spec)?;
+        }
+    }
+
+    let subimage_specs = levels
+        .iter()
+        .map(|specs| &*specs[0])
+        .collect::<Vec<&ImageSpec>>();
+    let mut out = output.open_multi(&subimage_specs)?;
+    for (subimage, specs) in levels.iter().enumerate() {
+        if subimage > 0 {
+            out.append_subimage(&specs[0])?;
+        }
+        for (miplevel, spec) in specs.iter().enumerate() {
+            if miplevel > 0 {
+                out.append_miplevel(spec)?;
+            }
+            out.copy_from(&input.subimage_miplevel(subimage, miplevel)?)?;
+        }
+    }
+    out.close();
+    output.close()
+}
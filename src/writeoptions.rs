@@ -0,0 +1,591 @@
+//! Typed options of the image writers of common formats.
+//!
+//! Each option struct sets the attributes understood by the writer of one format. Apply them
+//! to the spec of an image with [WriteOptions::apply] before opening the output, or open the
+//! output with [ImageOutput::open_with](crate::ImageOutput::open_with), which also checks
+//! that the options match the format of the output.
+use crate::{typedesc::BaseType, Error, ImageSpec, ImageSpecOwned};
+
+/// Options of the writer of an image format.
+pub trait WriteOptions {
+    /// Name of the format of the writer (e.g. `"openexr"`), as returned by
+    /// [ImageOutput::format_name](crate::ImageOutput::format_name).
+    const FORMAT_NAME: &'static str;
+
+    /// Sets the attributes corresponding to these options on `spec`.
+    ///
+    /// Returns [Error::InvalidOption] if an option has an invalid value, or cannot be used
+    /// with the other options or with the image described by `spec`; `spec` is left unchanged
+    /// in this case.
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error>;
+}
+
+/// Returns [Error::InvalidOption] if options for the format `format_name` cannot be used with
+/// the writer `writer_name`.
+pub(crate) fn check_format(format_name: &str, writer_name: &str) -> Result<(), Error> {
+    if format_name != writer_name {
+        return Err(Error::InvalidOption(format!(
+            "{} options cannot be used with the {} writer",
+            format_name, writer_name
+        )));
+    }
+    Ok(())
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidOption(message.to_owned())
+}
+
+fn is_float(spec: &ImageSpec) -> bool {
+    let basetype = spec.format().0.basetype;
+    basetype == BaseType::Half as u8
+        || basetype == BaseType::Float as u8
+        || basetype == BaseType::Double as u8
+}
+
+/// Compression method of OpenEXR files.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ExrCompression {
+    None,
+    Rle,
+    /// Zlib compression, one scanline at a time.
+    Zips,
+    /// Zlib compression, in blocks of 16 scanlines.
+    Zip,
+    Piz,
+    Pxr24,
+    B44,
+    B44a,
+    /// Lossy DWA compression in blocks of 32 scanlines, with an optional compression level
+    /// (45 by default; higher is smaller and lossier).
+    Dwaa(Option<f32>),
+    /// Lossy DWA compression in blocks of 256 scanlines, with an optional compression level.
+    Dwab(Option<f32>),
+}
+
+impl ExrCompression {
+    fn to_attribute(self) -> Result<String, Error> {
+        let (name, level) = match self {
+            ExrCompression::None => ("none", None),
+            ExrCompression::Rle => ("rle", None),
+            ExrCompression::Zips => ("zips", None),
+            ExrCompression::Zip => ("zip", None),
+            ExrCompression::Piz => ("piz", None),
+            ExrCompression::Pxr24 => ("pxr24", None),
+            ExrCompression::B44 => ("b44", None),
+            ExrCompression::B44a => ("b44a", None),
+            ExrCompression::Dwaa(level) => ("dwaa", level),
+            ExrCompression::Dwab(level) => ("dwab", level),
+        };
+        match level {
+            None => Ok(name.to_owned()),
+            Some(level) if level.is_finite() && level > 0.0 => Ok(format!("{}:{}", name, level)),
+            Some(_) => Err(invalid("DWA compression level must be positive")),
+        }
+    }
+}
+
+/// Order in which the scanlines or tiles of an OpenEXR file are stored.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ExrLineOrder {
+    IncreasingY,
+    DecreasingY,
+    /// Tiles are stored in the order they are written. Only for tiled images.
+    RandomY,
+}
+
+/// Options of the OpenEXR writer.
+///
+/// # Example
+///
+/// ```no_run
+/// use openimageio::{ExrCompression, ExrOptions, ImageOutput, ImageSpecOwned, TypeDesc};
+///
+/// let spec = ImageSpecOwned::new_2d(TypeDesc::HALF, 1920, 1080, &["R", "G", "B", "A"]);
+/// let mut output = ImageOutput::create("beauty.exr").unwrap();
+/// let out = output
+///     .open_with(&spec, ExrOptions::new().compression(ExrCompression::Dwaa(Some(60.0))))
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExrOptions {
+    compression: Option<ExrCompression>,
+    line_order: Option<ExrLineOrder>,
+    tile_size: Option<(u32, u32)>,
+}
+
+impl ExrOptions {
+    /// Creates a set of options that keeps the defaults of the writer.
+    pub fn new() -> ExrOptions {
+        ExrOptions::default()
+    }
+
+    /// Sets the compression method (`compression`).
+    ///
+    /// Deep images can only use [ExrCompression::None], [ExrCompression::Rle],
+    /// [ExrCompression::Zips] or [ExrCompression::Zip].
+    pub fn compression(&mut self, compression: ExrCompression) -> &mut ExrOptions {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the order of the scanlines or tiles in the file (`openexr:lineOrder`).
+    pub fn line_order(&mut self, line_order: ExrLineOrder) -> &mut ExrOptions {
+        self.line_order = Some(line_order);
+        self
+    }
+
+    /// Writes a tiled file with tiles of the specified size, instead of a scanline file.
+    pub fn tile_size(&mut self, width: u32, height: u32) -> &mut ExrOptions {
+        self.tile_size = Some((width, height));
+        self
+    }
+}
+
+impl WriteOptions for ExrOptions {
+    const FORMAT_NAME: &'static str = "openexr";
+
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error> {
+        let compression = self
+            .compression
+            .map(ExrCompression::to_attribute)
+            .transpose()?;
+        if spec.is_deep() {
+            match self.compression {
+                None
+                | Some(ExrCompression::None)
+                | Some(ExrCompression::Rle)
+                | Some(ExrCompression::Zips)
+                | Some(ExrCompression::Zip) => {}
+                Some(_) => return Err(invalid("deep images only support none, rle, zips or zip")),
+            }
+        }
+        if let Some((width, height)) = self.tile_size {
+            if width == 0 || height == 0 {
+                return Err(invalid("tile size must not be zero"));
+            }
+        }
+        let tiled = self.tile_size.is_some() || spec.tile_width() != 0;
+        if self.line_order == Some(ExrLineOrder::RandomY) && !tiled {
+            return Err(invalid("random line order requires a tiled image"));
+        }
+
+        if let Some(compression) = compression {
+            spec.set_attribute("compression", compression.as_str());
+        }
+        if let Some(line_order) = self.line_order {
+            let line_order = match line_order {
+                ExrLineOrder::IncreasingY => "increasingY",
+                ExrLineOrder::DecreasingY => "decreasingY",
+                ExrLineOrder::RandomY => "randomY",
+            };
+            spec.set_attribute("openexr:lineOrder", line_order);
+        }
+        if let Some((width, height)) = self.tile_size {
+            spec.set_tile_size(width, height, 1);
+        }
+        Ok(())
+    }
+}
+
+/// Filter applied to the rows of PNG files before compression.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PngFilter {
+    None,
+    Sub,
+    Up,
+    Average,
+    Paeth,
+    /// Lets the encoder choose the best filter for each row.
+    All,
+}
+
+/// Options of the PNG writer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PngOptions {
+    compression_level: Option<u32>,
+    filter: Option<PngFilter>,
+}
+
+impl PngOptions {
+    /// Creates a set of options that keeps the defaults of the writer.
+    pub fn new() -> PngOptions {
+        PngOptions::default()
+    }
+
+    /// Sets the zlib compression level, between 1 (fastest) and 9 (smallest)
+    /// (`compression`, as `zip:<level>`).
+    pub fn compression_level(&mut self, level: u32) -> &mut PngOptions {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Sets the row filter (`png:filter`).
+    pub fn filter(&mut self, filter: PngFilter) -> &mut PngOptions {
+        self.filter = Some(filter);
+        self
+    }
+}
+
+impl WriteOptions for PngOptions {
+    const FORMAT_NAME: &'static str = "png";
+
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error> {
+        if let Some(level) = self.compression_level {
+            if !(1..=9).contains(&level) {
+                return Err(invalid("PNG compression level must be between 1 and 9"));
+            }
+        }
+
+        if let Some(level) = self.compression_level {
+            spec.set_attribute("compression", format!("zip:{}", level).as_str());
+        }
+        if let Some(filter) = self.filter {
+            // PNG_FILTER_* flags of libpng
+            let flags = match filter {
+                PngFilter::None => 0x08,
+                PngFilter::Sub => 0x10,
+                PngFilter::Up => 0x20,
+                PngFilter::Average => 0x40,
+                PngFilter::Paeth => 0x80,
+                PngFilter::All => 0xf8,
+            };
+            spec.set_attribute("png:filter", flags);
+        }
+        Ok(())
+    }
+}
+
+/// Chroma subsampling of JPEG files.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum JpegSubsampling {
+    /// No subsampling.
+    S444,
+    /// Half horizontal chroma resolution.
+    S422,
+    /// Half horizontal and vertical chroma resolution.
+    S420,
+    /// Quarter horizontal chroma resolution.
+    S411,
+}
+
+/// Options of the JPEG writer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JpegOptions {
+    quality: Option<u32>,
+    subsampling: Option<JpegSubsampling>,
+    progressive: Option<bool>,
+}
+
+impl JpegOptions {
+    /// Creates a set of options that keeps the defaults of the writer.
+    pub fn new() -> JpegOptions {
+        JpegOptions::default()
+    }
+
+    /// Sets the quality, between 1 and 100 (`compression`, as `jpeg:<quality>`).
+    pub fn quality(&mut self, quality: u32) -> &mut JpegOptions {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Sets the chroma subsampling (`jpeg:subsampling`).
+    pub fn subsampling(&mut self, subsampling: JpegSubsampling) -> &mut JpegOptions {
+        self.subsampling = Some(subsampling);
+        self
+    }
+
+    /// Sets whether to write a progressive JPEG file (`jpeg:progressive`).
+    pub fn progressive(&mut self, progressive: bool) -> &mut JpegOptions {
+        self.progressive = Some(progressive);
+        self
+    }
+}
+
+impl WriteOptions for JpegOptions {
+    const FORMAT_NAME: &'static str = "jpeg";
+
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error> {
+        if let Some(quality) = self.quality {
+            if !(1..=100).contains(&quality) {
+                return Err(invalid("JPEG quality must be between 1 and 100"));
+            }
+        }
+
+        if let Some(quality) = self.quality {
+            spec.set_attribute("compression", format!("jpeg:{}", quality).as_str());
+        }
+        if let Some(subsampling) = self.subsampling {
+            let subsampling = match subsampling {
+                JpegSubsampling::S444 => "4:4:4",
+                JpegSubsampling::S422 => "4:2:2",
+                JpegSubsampling::S420 => "4:2:0",
+                JpegSubsampling::S411 => "4:1:1",
+            };
+            spec.set_attribute("jpeg:subsampling", subsampling);
+        }
+        if let Some(progressive) = self.progressive {
+            spec.set_attribute("jpeg:progressive", progressive as i32);
+        }
+        Ok(())
+    }
+}
+
+/// Compression method of TIFF files.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TiffCompression {
+    None,
+    Lzw,
+    /// Deflate (zlib) compression.
+    Zip,
+    PackBits,
+    /// Lossy JPEG compression. Only for 8-bit images.
+    Jpeg,
+}
+
+/// Predictor applied to TIFF data before LZW or zip compression.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TiffPredictor {
+    None,
+    /// Horizontal differencing, for integer data.
+    Horizontal,
+    /// Floating-point predictor, for half, float and double data.
+    FloatingPoint,
+}
+
+/// Layout of the channels of TIFF files.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TiffPlanarConfig {
+    /// The channels of each pixel are interleaved.
+    Contiguous,
+    /// Each channel is stored in a separate plane.
+    Separate,
+}
+
+/// Options of the TIFF writer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TiffOptions {
+    compression: Option<TiffCompression>,
+    predictor: Option<TiffPredictor>,
+    bits_per_sample: Option<u32>,
+    planar_config: Option<TiffPlanarConfig>,
+}
+
+impl TiffOptions {
+    /// Creates a set of options that keeps the defaults of the writer.
+    pub fn new() -> TiffOptions {
+        TiffOptions::default()
+    }
+
+    /// Sets the compression method (`compression`).
+    pub fn compression(&mut self, compression: TiffCompression) -> &mut TiffOptions {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the predictor (`tiff:Predictor`). Only for LZW and zip compression.
+    pub fn predictor(&mut self, predictor: TiffPredictor) -> &mut TiffOptions {
+        self.predictor = Some(predictor);
+        self
+    }
+
+    /// Sets the number of bits used to store each sample, if smaller than the size of the
+    /// format of the image (`oiio:BitsPerSample`), e.g. 10 or 12 for 16-bit images.
+    pub fn bits_per_sample(&mut self, bits: u32) -> &mut TiffOptions {
+        self.bits_per_sample = Some(bits);
+        self
+    }
+
+    /// Sets the layout of the channels (`planarconfig`).
+    pub fn planar_config(&mut self, planar_config: TiffPlanarConfig) -> &mut TiffOptions {
+        self.planar_config = Some(planar_config);
+        self
+    }
+}
+
+impl WriteOptions for TiffOptions {
+    const FORMAT_NAME: &'static str = "tiff";
+
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error> {
+        if self.compression == Some(TiffCompression::Jpeg)
+            && spec.format().0.basetype != BaseType::UInt8 as u8
+        {
+            return Err(invalid("TIFF JPEG compression requires an 8-bit image"));
+        }
+        match self.predictor {
+            None | Some(TiffPredictor::None) => {}
+            Some(predictor) => {
+                match self.compression {
+                    None | Some(TiffCompression::Lzw) | Some(TiffCompression::Zip) => {}
+                    Some(_) => {
+                        return Err(invalid("TIFF predictors require LZW or zip compression"))
+                    }
+                }
+                if predictor == TiffPredictor::FloatingPoint && !is_float(spec) {
+                    return Err(invalid(
+                        "the TIFF floating-point predictor requires a floating-point image",
+                    ));
+                }
+            }
+        }
+        if let Some(bits) = self.bits_per_sample {
+            if ![1, 2, 4, 8, 10, 12, 16, 32].contains(&bits) {
+                return Err(invalid("unsupported number of TIFF bits per sample"));
+            }
+        }
+
+        if let Some(compression) = self.compression {
+            let compression = match compression {
+                TiffCompression::None => "none",
+                TiffCompression::Lzw => "lzw",
+                TiffCompression::Zip => "zip",
+                TiffCompression::PackBits => "packbits",
+                TiffCompression::Jpeg => "jpeg",
+            };
+            spec.set_attribute("compression", compression);
+        }
+        if let Some(predictor) = self.predictor {
+            // values of the TIFF predictor tag
+            let predictor = match predictor {
+                TiffPredictor::None => 1,
+                TiffPredictor::Horizontal => 2,
+                TiffPredictor::FloatingPoint => 3,
+            };
+            spec.set_attribute("tiff:Predictor", predictor);
+        }
+        if let Some(bits) = self.bits_per_sample {
+            spec.set_attribute("oiio:BitsPerSample", bits as i32);
+        }
+        if let Some(planar_config) = self.planar_config {
+            let planar_config = match planar_config {
+                TiffPlanarConfig::Contiguous => "contig",
+                TiffPlanarConfig::Separate => "separate",
+            };
+            spec.set_attribute("planarconfig", planar_config);
+        }
+        Ok(())
+    }
+}
+
+/// Options of the WebP writer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WebpOptions {
+    quality: Option<f32>,
+    lossless: Option<bool>,
+}
+
+impl WebpOptions {
+    /// Creates a set of options that keeps the defaults of the writer.
+    pub fn new() -> WebpOptions {
+        WebpOptions::default()
+    }
+
+    /// Sets the quality, between 0 and 100 (`compression`, as `webp:<quality>`). For lossless
+    /// images, this sets the compression effort instead.
+    pub fn quality(&mut self, quality: f32) -> &mut WebpOptions {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Sets whether to use lossless compression (`webp:lossless`).
+    pub fn lossless(&mut self, lossless: bool) -> &mut WebpOptions {
+        self.lossless = Some(lossless);
+        self
+    }
+}
+
+impl WriteOptions for WebpOptions {
+    const FORMAT_NAME: &'static str = "webp";
+
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error> {
+        if let Some(quality) = self.quality {
+            if !(0.0..=100.0).contains(&quality) {
+                return Err(invalid("WebP quality must be between 0 and 100"));
+            }
+        }
+
+        if let Some(quality) = self.quality {
+            spec.set_attribute("compression", format!("webp:{}", quality).as_str());
+        }
+        if let Some(lossless) = self.lossless {
+            spec.set_attribute("webp:lossless", lossless as i32);
+        }
+        Ok(())
+    }
+}
+
+/// Transfer characteristic of DPX files.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DpxTransfer {
+    UserDefined,
+    PrintingDensity,
+    Linear,
+    Logarithmic,
+    UnspecifiedVideo,
+    Smpte274M,
+    ItuR709,
+    Ntsc,
+    Pal,
+}
+
+/// Options of the DPX writer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DpxOptions {
+    bit_depth: Option<u32>,
+    transfer: Option<DpxTransfer>,
+}
+
+impl DpxOptions {
+    /// Creates a set of options that keeps the defaults of the writer.
+    pub fn new() -> DpxOptions {
+        DpxOptions::default()
+    }
+
+    /// Sets the number of bits of each sample: 8, 10, 12 or 16 (`oiio:BitsPerSample`).
+    pub fn bit_depth(&mut self, bits: u32) -> &mut DpxOptions {
+        self.bit_depth = Some(bits);
+        self
+    }
+
+    /// Sets the transfer characteristic (`dpx:Transfer`).
+    pub fn transfer(&mut self, transfer: DpxTransfer) -> &mut DpxOptions {
+        self.transfer = Some(transfer);
+        self
+    }
+}
+
+impl WriteOptions for DpxOptions {
+    const FORMAT_NAME: &'static str = "dpx";
+
+    fn apply(&self, spec: &mut ImageSpecOwned) -> Result<(), Error> {
+        if let Some(bits) = self.bit_depth {
+            if ![8, 10, 12, 16].contains(&bits) {
+                return Err(invalid("DPX bit depth must be 8, 10, 12 or 16"));
+            }
+            if is_float(spec) {
+                return Err(invalid(
+                    "DPX bit depth cannot be set for floating-point images",
+                ));
+            }
+        }
+
+        if let Some(bits) = self.bit_depth {
+            spec.set_attribute("oiio:BitsPerSample", bits as i32);
+        }
+        if let Some(transfer) = self.transfer {
+            let transfer = match transfer {
+                DpxTransfer::UserDefined => "User defined",
+                DpxTransfer::PrintingDensity => "Printing density",
+                DpxTransfer::Linear => "Linear",
+                DpxTransfer::Logarithmic => "Logarithmic",
+                DpxTransfer::UnspecifiedVideo => "Unspecified video",
+                DpxTransfer::Smpte274M => "SMPTE 274M",
+                DpxTransfer::ItuR709 => "ITU-R 709-4",
+                DpxTransfer::Ntsc => "NTSC composite video",
+                DpxTransfer::Pal => "PAL composite video",
+            };
+            spec.set_attribute("dpx:Transfer", transfer);
+        }
+        Ok(())
+    }
+}